@@ -0,0 +1,35 @@
+//! Digest algorithms for hash-mode claims, where only a document's digest goes on chain.
+
+use frame_support::pallet_prelude::{Decode, Encode, MaxEncodedLen, RuntimeDebug, TypeInfo};
+
+/// Length in bytes of every supported digest.
+pub const DIGEST_LENGTH: usize = 32;
+
+/// A document digest as submitted to `create_claim_hash`.
+pub type Digest = [u8; DIGEST_LENGTH];
+
+/// The algorithm a digest was computed with.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum HashAlgorithm {
+	Blake2_256,
+	Sha2_256,
+	Keccak256,
+}
+
+/// Hashes documents so they can be matched against hash-mode claims.
+pub trait ClaimHasher {
+	fn hash(algorithm: HashAlgorithm, data: &[u8]) -> Digest;
+}
+
+/// `ClaimHasher` backed by the `sp_io::hashing` host functions.
+pub struct SpIoHasher;
+
+impl ClaimHasher for SpIoHasher {
+	fn hash(algorithm: HashAlgorithm, data: &[u8]) -> Digest {
+		match algorithm {
+			HashAlgorithm::Blake2_256 => sp_io::hashing::blake2_256(data),
+			HashAlgorithm::Sha2_256 => sp_io::hashing::sha2_256(data),
+			HashAlgorithm::Keccak256 => sp_io::hashing::keccak_256(data),
+		}
+	}
+}
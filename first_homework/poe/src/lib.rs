@@ -1,6 +1,8 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
+pub mod hashing;
 pub mod weights;
+pub use hashing::{ClaimHasher, HashAlgorithm, SpIoHasher};
 pub use pallet::*;
 pub use weights::*;
 
@@ -28,6 +30,9 @@ pub mod pallet {
 		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
 
 		type WeightInfo: WeightInfo;
+
+		/// Hashes documents for `verify_document`.
+		type ClaimHasher: ClaimHasher;
 	}
 
 	#[pallet::pallet]
@@ -41,6 +46,11 @@ pub mod pallet {
 		(T::AccountId, T::BlockNumber),
 	>;
 
+	/// The algorithm each `create_claim_hash` digest in `Proofs` was made with.
+	#[pallet::storage]
+	pub type ClaimAlgorithms<T: Config> =
+		StorageMap<_, Blake2_128Concat, BoundedVec<u8, T::MaxClaimLength>, HashAlgorithm>;
+
 	// Pallets use events to inform users when important changes are made.
 	// Event documentation should end with an array that provides descriptive names for parameters.
 	#[pallet::event]
@@ -50,6 +60,10 @@ pub mod pallet {
 		ClaimCreated(T::AccountId, Vec<u8>),
 		/// Event emitted when a claim is revoked by the owner.
 		ClaimRevoked(T::AccountId, Vec<u8>),
+		/// Event emitted when a claim is transferred by the owner. [owner, dest, claim]
+		ClaimTransferred(T::AccountId, T::AccountId, Vec<u8>),
+		/// Event emitted when a claim has been created from a document digest. [owner, algorithm, digest]
+		ClaimHashCreated(T::AccountId, HashAlgorithm, hashing::Digest),
 	}
 
 	#[pallet::error]
@@ -115,6 +129,7 @@ pub mod pallet {
 
 			// Remove claim from storage.
 			Proofs::<T>::remove(&bounded_claim);
+			ClaimAlgorithms::<T>::remove(&bounded_claim);
 
 			// Emit an event that the claim was erased.
 			Self::deposit_event(Event::ClaimRevoked(sender, claim));
@@ -142,10 +157,60 @@ pub mod pallet {
 			// Get the block number from the FRAME System pallet.
 			let current_block = <frame_system::Pallet<T>>::block_number();
 
-			// Store the claim with the sender and block number.
-			Proofs::<T>::insert(&bounded_claim, (dest, current_block));
+			// Store the claim with the new owner and block number.
+			Proofs::<T>::insert(&bounded_claim, (dest.clone(), current_block));
+
+			// Emit an event that the claim was transferred.
+			Self::deposit_event(Event::ClaimTransferred(sender, dest, claim));
 
 			Ok(().into())
 		}
+
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::create_claim_hash())]
+		pub fn create_claim_hash(
+			origin: OriginFor<T>,
+			algorithm: HashAlgorithm,
+			digest: hashing::Digest,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			// Only the digest is stored, so the document itself never goes on chain.
+			let bounded_claim = BoundedVec::<u8, T::MaxClaimLength>::try_from(digest.to_vec())
+				.map_err(|_| Error::<T>::ClaimTooLong)?;
+
+			// Verify that the specified digest has not already been stored.
+			ensure!(!Proofs::<T>::contains_key(&bounded_claim), Error::<T>::ProofAlreadyExist);
+
+			// Get the block number from the FRAME System pallet.
+			let current_block = <frame_system::Pallet<T>>::block_number();
+
+			// Store the digest with the sender and block number, and the algorithm it was made with.
+			Proofs::<T>::insert(&bounded_claim, (sender.clone(), current_block));
+			ClaimAlgorithms::<T>::insert(&bounded_claim, algorithm);
+
+			// Emit an event that the claim was created.
+			Self::deposit_event(Event::ClaimHashCreated(sender, algorithm, digest));
+
+			Ok(().into())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Hash `document` with `algorithm` and return the proof stored for its digest, if any.
+		///
+		/// Only digests created with the same algorithm through `create_claim_hash` match, not raw
+		/// claims that happen to hold the same bytes.
+		pub fn verify_document(
+			algorithm: HashAlgorithm,
+			document: &[u8],
+		) -> Option<(T::AccountId, T::BlockNumber)> {
+			let digest = T::ClaimHasher::hash(algorithm, document);
+			let bounded_claim = BoundedVec::<u8, T::MaxClaimLength>::try_from(digest.to_vec()).ok()?;
+			if ClaimAlgorithms::<T>::get(&bounded_claim) != Some(algorithm) {
+				return None;
+			}
+			Proofs::<T>::get(&bounded_claim)
+		}
 	}
 }
\ No newline at end of file
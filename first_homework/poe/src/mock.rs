@@ -53,6 +53,7 @@ impl pallet_peo::Config for Test {
 	type Event = RuntimeEvent;
 	type RuntimeEvent = RuntimeEvent;
 	type WeightInfo = ();
+	type ClaimHasher = pallet_peo::SpIoHasher;
 }
 
 // Build genesis storage according to the mock runtime.
@@ -1,4 +1,4 @@
-use crate::{mock::*, Error};
+use crate::{mock::*, ClaimHasher, Error, HashAlgorithm, SpIoHasher};
 use frame_support::{assert_noop, assert_ok};
 
 #[test]
@@ -37,7 +37,9 @@ fn test_transfer_claim() {
 	new_test_ext().execute_with(|| {
 		let claim: Vec<u8> = vec![1,3,1,2,3,4];
 		assert_ok!(PoeModule::create_claim(RuntimeOrigin::signed(10086), claim.clone()));
+		System::set_block_number(1);
 		assert_ok!(PoeModule::transfer_claim(RuntimeOrigin::signed(10086), claim.clone(), 10000));
+		System::assert_last_event(crate::Event::ClaimTransferred(10086, 10000, claim).into());
 	});
 }
 
@@ -51,4 +53,50 @@ fn test_transfer_claim_failed() {
 			Error::<Test>::NotClaimOwner
 		);
 	});
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_create_claim_hash() {
+	new_test_ext().execute_with(|| {
+		let document: Vec<u8> = b"notarised contract".to_vec();
+		let digest = SpIoHasher::hash(HashAlgorithm::Blake2_256, &document);
+
+		assert_ok!(PoeModule::create_claim_hash(
+			RuntimeOrigin::signed(10086),
+			HashAlgorithm::Blake2_256,
+			digest
+		));
+		assert_noop!(
+			PoeModule::create_claim_hash(RuntimeOrigin::signed(10086), HashAlgorithm::Blake2_256, digest),
+			Error::<Test>::ProofAlreadyExist
+		);
+
+		assert_eq!(
+			PoeModule::verify_document(HashAlgorithm::Blake2_256, &document),
+			Some((10086, System::block_number()))
+		);
+		assert_eq!(PoeModule::verify_document(HashAlgorithm::Sha2_256, &document), None);
+	});
+}
+
+#[test]
+fn test_verify_document_ignores_raw_claims() {
+	new_test_ext().execute_with(|| {
+		let document: Vec<u8> = b"notarised contract".to_vec();
+		let digest = SpIoHasher::hash(HashAlgorithm::Blake2_256, &document);
+
+		// A raw claim holding the same bytes as the digest is not a proof of the document.
+		assert_ok!(PoeModule::create_claim(RuntimeOrigin::signed(10086), digest.to_vec()));
+		assert_eq!(PoeModule::verify_document(HashAlgorithm::Blake2_256, &document), None);
+
+		assert_ok!(PoeModule::revoke_claim(RuntimeOrigin::signed(10086), digest.to_vec()));
+		assert_ok!(PoeModule::create_claim_hash(
+			RuntimeOrigin::signed(10086),
+			HashAlgorithm::Blake2_256,
+			digest
+		));
+		assert_ok!(PoeModule::revoke_claim(RuntimeOrigin::signed(10086), digest.to_vec()));
+		assert_ok!(PoeModule::create_claim(RuntimeOrigin::signed(10086), digest.to_vec()));
+		assert_eq!(PoeModule::verify_document(HashAlgorithm::Blake2_256, &document), None);
+	});
+}
@@ -13,12 +13,12 @@ mod tests;
 
 use codec::{Decode, Encode};
 use frame_system::offchain::{
-	AppCrypto, CreateSignedTransaction, SendUnsignedTransaction, SignedPayload, Signer,
-	SigningTypes,
+	AppCrypto, CreateSignedTransaction, SendSignedTransaction, SendUnsignedTransaction,
+	SignedPayload, Signer, SigningTypes, SubmitTransaction,
 };
 use sp_runtime::{
 	transaction_validity::{InvalidTransaction, TransactionValidity, ValidTransaction},
-	RuntimeDebug,
+	RuntimeAppPublic, RuntimeDebug,
 };
 
 use sp_core::crypto::KeyTypeId;
@@ -47,7 +47,21 @@ pub mod pallet {
 	use serde::{Deserialize, Deserializer};
 	use sp_runtime::offchain::storage::StorageValueRef;
 	use sp_runtime::offchain::{http, Duration};
-	use sp_runtime::traits::AccountIdConversion;
+	use sp_runtime::traits::{AccountIdConversion, IdentifyAccount, One};
+	use sp_runtime::SaturatedConversion;
+
+	/// How the off-chain worker submits the prices it fetches.
+	#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+	pub enum SubmissionMode {
+		/// Signed transactions from every local oracle authority key, which pay fees.
+		Signed,
+		/// Unsigned transactions carrying a payload signed by an oracle authority.
+		UnsignedWithSignedPayload,
+		/// Unsigned transactions that nothing authenticates, at most one per round. The pool
+		/// only takes them from the local off-chain worker and does not gossip them, so only
+		/// block authors get theirs included.
+		Unsigned,
+	}
 
 	#[pallet::pallet]
 	pub struct Pallet<T>(_);
@@ -63,6 +77,15 @@ pub mod pallet {
 		type PalletId: Get<PalletId>;
 		/// The identifier type for an offchain worker.
 		type AuthorityId: AppCrypto<Self::Public, Self::Signature>;
+		/// The most accounts `OracleAuthorities` can hold.
+		#[pallet::constant]
+		type MaxOracleAuthorities: Get<u32>;
+		/// Each oracle authority can submit one price per round of this many blocks.
+		#[pallet::constant]
+		type OracleSubmissionInterval: Get<Self::BlockNumber>;
+		/// How the off-chain worker submits prices. Only that kind of submission is accepted.
+		#[pallet::constant]
+		type SubmissionMode: Get<SubmissionMode>;
 	}
 	// The pallet's runtime storage items.
 	// https://docs.substrate.io/main-docs/build/runtime-storage/
@@ -72,6 +95,22 @@ pub mod pallet {
 	// https://docs.substrate.io/main-docs/build/runtime-storage/#declaring-storage-items
 	pub type Something<T> = StorageValue<_, u32>;
 
+	/// The accounts whose off-chain workers may submit prices.
+	#[pallet::storage]
+	#[pallet::getter(fn oracle_authorities)]
+	pub type OracleAuthorities<T: Config> =
+		StorageValue<_, BoundedVec<T::AccountId, T::MaxOracleAuthorities>, ValueQuery>;
+
+	/// The round of the last price accepted from each oracle authority.
+	#[pallet::storage]
+	#[pallet::getter(fn last_oracle_round)]
+	pub type LastOracleRound<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, T::BlockNumber>;
+
+	/// The round of the last price accepted in a pure unsigned transaction.
+	#[pallet::storage]
+	pub type LastUnsignedRound<T: Config> = StorageValue<_, T::BlockNumber>;
+
 	// Pallets use events to inform users when important changes are made.
 	// https://docs.substrate.io/main-docs/build/events-errors/
 	#[pallet::event]
@@ -80,6 +119,8 @@ pub mod pallet {
 		/// Event documentation should end with an array that provides descriptive names for event
 		/// parameters. [something, who]
 		SomethingStored { something: u32, who: T::AccountId },
+		OracleAuthorityAdded { who: T::AccountId },
+		OracleAuthorityRemoved { who: T::AccountId },
 	}
 
 	// Errors inform users that something went wrong.
@@ -89,15 +130,27 @@ pub mod pallet {
 		NoneValue,
 		/// Errors should have helpful documentation associated with them.
 		StorageOverflow,
+		/// The account is not in `OracleAuthorities`.
+		NotOracleAuthority,
+		/// The account is already in `OracleAuthorities`.
+		AlreadyOracleAuthority,
+		/// `OracleAuthorities` already holds `MaxOracleAuthorities` accounts.
+		TooManyOracleAuthorities,
+		/// The oracle authority already submitted a price in this round.
+		OracleRateLimited,
+		/// Prices are not submitted this way under the configured `SubmissionMode`.
+		WrongSubmissionMode,
 	}
 
+	/// A price signed by an oracle authority, and the block its off-chain worker fetched it in.
 	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
-	pub struct Payload<Public> {
-		price: Vec<u8>,
-		public: Public,
+	pub struct Payload<Public, BlockNumber> {
+		pub price: Vec<u8>,
+		pub block_number: BlockNumber,
+		pub public: Public,
 	}
 
-	impl<T: SigningTypes> SignedPayload<T> for Payload<T::Public> {
+	impl<T: SigningTypes> SignedPayload<T> for Payload<T::Public, T::BlockNumber> {
 		fn public(&self) -> T::Public {
 			self.public.clone()
 		}
@@ -131,10 +184,25 @@ pub mod pallet {
 	}
 
 	const ONCHAIN_TX_KEY: &[u8] = b"ocw-template::storage::tx";
+	const SIGNER_STATE_KEY: &[u8] = b"ocw-template::signer/";
+	/// A signer that keeps being rejected waits at most `2^MAX_BACKOFF_EXPONENT` rounds.
+	const MAX_BACKOFF_EXPONENT: u32 = 5;
 
 	#[derive(Debug, Deserialize, Encode, Decode, Default)]
 	struct IndexingData(Vec<u8>, [u8; 8]);
 
+	/// What the off-chain worker remembers about an account it signs price transactions with.
+	#[derive(Encode, Decode, Default, RuntimeDebug)]
+	struct SignerState<Index, BlockNumber> {
+		/// The account nonce once the last transaction the pool accepted is included, and the
+		/// block it was sent in.
+		pending: Option<(Index, BlockNumber)>,
+		/// How many transactions in a row the pool rejected.
+		failures: u32,
+		/// After a rejection, the block before which the account does not send again.
+		retry_at: BlockNumber,
+	}
+
 	// Dispatchable functions allows users to interact with the pallet and invoke state changes.
 	// These functions materialize as "extrinsics", which are often compared to transactions.
 	// Dispatchable functions must be annotated with a weight and must return a DispatchResult.
@@ -195,13 +263,90 @@ pub mod pallet {
 		#[pallet::weight(10_000)]
 		pub fn unsigned_extrinsic_with_signed_payload(
 			origin: OriginFor<T>,
-			payload: Payload<T::Public>,
+			payload: Payload<T::Public, T::BlockNumber>,
 			_signature: T::Signature,
 		) -> DispatchResult {
 			ensure_none(origin)?;
+			ensure!(
+				T::SubmissionMode::get() == SubmissionMode::UnsignedWithSignedPayload,
+				Error::<T>::WrongSubmissionMode
+			);
 
 			log::info!("OCW ==> in call unsigned_extrinsic_with_signed_payload: {:?}", payload);
-			// Return a successful DispatchResultWithPostInfo
+			let who = payload.public.clone().into_account();
+			Self::do_submit_price(&who, payload.block_number)
+		}
+
+		/// Accept prices submitted by the off-chain worker holding the key of `who`.
+		#[pallet::call_index(4)]
+		#[pallet::weight(10_000)]
+		pub fn add_oracle_authority(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+			ensure_root(origin)?;
+
+			OracleAuthorities::<T>::try_mutate(|authorities| -> DispatchResult {
+				ensure!(!authorities.contains(&who), Error::<T>::AlreadyOracleAuthority);
+				authorities
+					.try_push(who.clone())
+					.map_err(|_| Error::<T>::TooManyOracleAuthorities)?;
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::OracleAuthorityAdded { who });
+
+			Ok(())
+		}
+
+		#[pallet::call_index(5)]
+		#[pallet::weight(10_000)]
+		pub fn remove_oracle_authority(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+			ensure_root(origin)?;
+
+			OracleAuthorities::<T>::try_mutate(|authorities| -> DispatchResult {
+				let index = authorities
+					.iter()
+					.position(|authority| *authority == who)
+					.ok_or(Error::<T>::NotOracleAuthority)?;
+				authorities.remove(index);
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::OracleAuthorityRemoved { who });
+
+			Ok(())
+		}
+
+		/// Accept a price from an oracle authority in a signed transaction.
+		#[pallet::call_index(6)]
+		#[pallet::weight(10_000)]
+		pub fn submit_price(origin: OriginFor<T>, price: Vec<u8>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(
+				T::SubmissionMode::get() == SubmissionMode::Signed,
+				Error::<T>::WrongSubmissionMode
+			);
+
+			log::info!("OCW ==> in call submit_price: {:?} from {:?}", price, who);
+			Self::do_submit_price(&who, frame_system::Pallet::<T>::block_number())
+		}
+
+		/// Accept a price fetched by the off-chain worker of a block author in the round of
+		/// `block_number`. The first one of each round wins.
+		#[pallet::call_index(7)]
+		#[pallet::weight(10_000)]
+		pub fn submit_price_unsigned(
+			origin: OriginFor<T>,
+			price: Vec<u8>,
+			block_number: T::BlockNumber,
+		) -> DispatchResult {
+			ensure_none(origin)?;
+			ensure!(
+				T::SubmissionMode::get() == SubmissionMode::Unsigned,
+				Error::<T>::WrongSubmissionMode
+			);
+			ensure!(Self::can_submit_unsigned(block_number), Error::<T>::OracleRateLimited);
+
+			log::info!("OCW ==> in call submit_price_unsigned: {:?}", price);
+			LastUnsignedRound::<T>::put(Self::oracle_round(block_number));
 			Ok(())
 		}
 	}
@@ -222,31 +367,11 @@ pub mod pallet {
 				Ok(info) => {
 					log::info!("OCW ==> BNB Info: {:?}", info);
 
-					// Retrieve the signer to sign the payload
-					let signer = Signer::<T, T::AuthorityId>::any_account();
-					if let Some((_, res)) = signer.send_unsigned_transaction(
-						// this line is to prepare and return payload
-						|acct| Payload { price: info.price.clone(), public: acct.public.clone() },
-						|payload, signature| Call::unsigned_extrinsic_with_signed_payload {
-							payload,
-							signature,
-						},
-					) {
-						match res {
-							Ok(()) => {
-								log::info!(
-									"OCW ==> unsigned tx with signed payload successfully sent."
-								);
-							},
-							Err(()) => {
-								log::error!(
-									"OCW ==> sending unsigned tx with signed payload failed."
-								);
-							},
-						};
-					} else {
-						// The case of `None`: no account is available for sending
-						log::error!("OCW ==> No local account available");
+					match T::SubmissionMode::get() {
+						SubmissionMode::Signed => Self::send_signed(block_number, info.price),
+						SubmissionMode::UnsignedWithSignedPayload =>
+							Self::send_unsigned_with_signed_payload(block_number, info.price),
+						SubmissionMode::Unsigned => Self::send_unsigned(block_number, info.price),
 					}
 				},
 				Err(e) => {
@@ -268,6 +393,155 @@ pub mod pallet {
 					.collect::<Vec<u8>>()
 			})
 		}
+
+		/// The oracle round `block_number` falls in.
+		fn oracle_round(block_number: T::BlockNumber) -> T::BlockNumber {
+			block_number / T::OracleSubmissionInterval::get().max(One::one())
+		}
+
+		/// Whether `who` has not submitted a price in the round of `block_number` or a later one.
+		fn can_submit(who: &T::AccountId, block_number: T::BlockNumber) -> bool {
+			Self::last_oracle_round(who)
+				.map_or(true, |last| Self::oracle_round(block_number) > last)
+		}
+
+		/// Whether no pure unsigned price was accepted in the round of `block_number` or a later
+		/// one.
+		fn can_submit_unsigned(block_number: T::BlockNumber) -> bool {
+			LastUnsignedRound::<T>::get()
+				.map_or(true, |last| Self::oracle_round(block_number) > last)
+		}
+
+		/// Accept a price from the oracle authority `who` for the round of `block_number`.
+		fn do_submit_price(who: &T::AccountId, block_number: T::BlockNumber) -> DispatchResult {
+			ensure!(Self::oracle_authorities().contains(who), Error::<T>::NotOracleAuthority);
+			ensure!(Self::can_submit(who, block_number), Error::<T>::OracleRateLimited);
+			LastOracleRound::<T>::insert(who, Self::oracle_round(block_number));
+			Ok(())
+		}
+
+		/// Send `price` in a signed transaction from every local oracle authority key that is
+		/// not waiting on an earlier one.
+		fn send_signed(block_number: T::BlockNumber, price: Vec<u8>) {
+			let signer = Signer::<T, T::AuthorityId>::all_accounts()
+				.with_filter(Self::ready_signers(block_number));
+			if !signer.can_sign() {
+				log::error!("OCW ==> No local account ready to sign");
+				return;
+			}
+
+			let results =
+				signer.send_signed_transaction(|_acct| Call::submit_price { price: price.clone() });
+			for (acct, res) in &results {
+				let mut state = Self::signer_state(&acct.id);
+				match res {
+					Ok(()) => {
+						log::info!("OCW ==> signed tx from {:?} successfully sent.", acct.id);
+						// `send_signed_transaction` bumped the nonce in the worker's own copy of
+						// the state, to what it is once the transaction is included.
+						let nonce = frame_system::Pallet::<T>::account_nonce(&acct.id);
+						state = SignerState {
+							pending: Some((nonce, block_number)),
+							..Default::default()
+						};
+					},
+					Err(()) => {
+						state.failures = state.failures.saturating_add(1);
+						state.retry_at = block_number.saturating_add(Self::backoff(state.failures));
+						log::error!(
+							"OCW ==> sending signed tx from {:?} failed, {} times in a row. \
+							Retrying at block {:?}.",
+							acct.id,
+							state.failures,
+							state.retry_at
+						);
+					},
+				}
+				StorageValueRef::persistent(&Self::signer_key(&acct.id)).set(&state);
+			}
+		}
+
+		/// The local oracle authority keys whose accounts can submit in the round of
+		/// `block_number` and have no transaction still waiting in the pool.
+		fn ready_signers(block_number: T::BlockNumber) -> Vec<T::Public> {
+			<T::AuthorityId as AppCrypto<T::Public, T::Signature>>::RuntimeAppPublic::all()
+				.into_iter()
+				.map(|key| -> T::Public {
+					<T::AuthorityId as AppCrypto<T::Public, T::Signature>>::GenericPublic::from(key)
+						.into()
+				})
+				.filter(|public: &T::Public| {
+					let who = public.clone().into_account();
+					Self::oracle_authorities().contains(&who) &&
+						Self::can_submit(&who, block_number) &&
+						!Self::is_waiting(&who, block_number)
+				})
+				.collect()
+		}
+
+		/// Whether `who` is backing off after a rejected transaction, or the last transaction
+		/// it sent may still be in the pool. That one is not once the account nonce has reached
+		/// the one it leaves behind, or once a round has gone by and the pool dropped it.
+		fn is_waiting(who: &T::AccountId, block_number: T::BlockNumber) -> bool {
+			let state = Self::signer_state(who);
+			block_number < state.retry_at ||
+				state.pending.map_or(false, |(nonce, sent)| {
+					frame_system::Pallet::<T>::account_nonce(who) < nonce &&
+						block_number < sent.saturating_add(T::OracleSubmissionInterval::get())
+				})
+		}
+
+		/// How long an account waits after `failures` rejections in a row: one round, doubling
+		/// with each further rejection up to `2^MAX_BACKOFF_EXPONENT` rounds.
+		fn backoff(failures: u32) -> T::BlockNumber {
+			let rounds = 1u32 << failures.saturating_sub(1).min(MAX_BACKOFF_EXPONENT);
+			T::OracleSubmissionInterval::get().saturating_mul(rounds.into())
+		}
+
+		fn signer_key(who: &T::AccountId) -> Vec<u8> {
+			who.using_encoded(|encoded| [SIGNER_STATE_KEY, encoded].concat())
+		}
+
+		fn signer_state(who: &T::AccountId) -> SignerState<T::Index, T::BlockNumber> {
+			StorageValueRef::persistent(&Self::signer_key(who))
+				.get()
+				.ok()
+				.flatten()
+				.unwrap_or_default()
+		}
+
+		fn send_unsigned_with_signed_payload(block_number: T::BlockNumber, price: Vec<u8>) {
+			// Retrieve the signer to sign the payload
+			let signer = Signer::<T, T::AuthorityId>::any_account();
+			if let Some((_, res)) = signer.send_unsigned_transaction(
+				// this line is to prepare and return payload
+				|acct| Payload { price: price.clone(), block_number, public: acct.public.clone() },
+				|payload, signature| Call::unsigned_extrinsic_with_signed_payload {
+					payload,
+					signature,
+				},
+			) {
+				match res {
+					Ok(()) => {
+						log::info!("OCW ==> unsigned tx with signed payload successfully sent.");
+					},
+					Err(()) => {
+						log::error!("OCW ==> sending unsigned tx with signed payload failed.");
+					},
+				};
+			} else {
+				// The case of `None`: no account is available for sending
+				log::error!("OCW ==> No local account available");
+			}
+		}
+
+		fn send_unsigned(block_number: T::BlockNumber, price: Vec<u8>) {
+			let call = Call::submit_price_unsigned { price, block_number };
+			match SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(call.into()) {
+				Ok(()) => log::info!("OCW ==> unsigned tx successfully sent."),
+				Err(()) => log::error!("OCW ==> sending unsigned tx failed."),
+			}
+		}
 	}
 
 
@@ -280,14 +554,15 @@ pub mod pallet {
 		/// By default unsigned transactions are disallowed, but implementing the validator
 		/// here we make sure that some particular calls (the ones produced by offchain worker)
 		/// are being whitelisted and marked as valid.
-		fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+		fn validate_unsigned(source: TransactionSource, call: &Self::Call) -> TransactionValidity {
 			const UNSIGNED_TXS_PRIORITY: u64 = 100;
-			let valid_tx = |provide| {
+			let interval = T::OracleSubmissionInterval::get();
+			let valid_tx = |provide: Vec<u8>, propagate: bool| {
 				ValidTransaction::with_tag_prefix("my-pallet")
 					.priority(UNSIGNED_TXS_PRIORITY) // please define `UNSIGNED_TXS_PRIORITY` before this line
 					.and_provides([&provide])
-					.longevity(3)
-					.propagate(true)
+					.longevity(interval.saturated_into::<u64>().max(1))
+					.propagate(propagate)
 					.build()
 			};
 
@@ -296,12 +571,51 @@ pub mod pallet {
 			// 	_ => InvalidTransaction::Call.into(),
 			// }
 
+			let now = frame_system::Pallet::<T>::block_number();
 			match call {
 				Call::unsigned_extrinsic_with_signed_payload { ref payload, ref signature } => {
+					if T::SubmissionMode::get() != SubmissionMode::UnsignedWithSignedPayload {
+						return InvalidTransaction::Call.into();
+					}
 					if !SignedPayload::<T>::verify::<T::AuthorityId>(payload, signature.clone()) {
 						return InvalidTransaction::BadProof.into();
 					}
-					valid_tx(b"unsigned_extrinsic_with_signed_payload".to_vec())
+					let who = payload.public.clone().into_account();
+					if !Self::oracle_authorities().contains(&who) {
+						return InvalidTransaction::BadSigner.into();
+					}
+
+					if payload.block_number > now {
+						return InvalidTransaction::Future.into();
+					}
+					// Old payloads are rejected so that nobody can replay an outdated price.
+					if now - payload.block_number > interval ||
+						!Self::can_submit(&who, payload.block_number)
+					{
+						return InvalidTransaction::Stale.into();
+					}
+
+					// One transaction per authority and round, even before any is included.
+					valid_tx((who, Self::oracle_round(payload.block_number)).encode(), true)
+				},
+				Call::submit_price_unsigned { price: _, block_number } => {
+					if T::SubmissionMode::get() != SubmissionMode::Unsigned {
+						return InvalidTransaction::Call.into();
+					}
+					// Nothing authenticates the price, so only the local off-chain worker may put
+					// it in the pool, and only the block author it runs for includes it.
+					if !matches!(source, TransactionSource::Local | TransactionSource::InBlock) {
+						return InvalidTransaction::BadSigner.into();
+					}
+					if *block_number > now {
+						return InvalidTransaction::Future.into();
+					}
+					if now - *block_number > interval || !Self::can_submit_unsigned(*block_number) {
+						return InvalidTransaction::Stale.into();
+					}
+
+					// The first transaction of each round wins.
+					valid_tx(Self::oracle_round(*block_number).encode(), false)
 				},
 				_ => InvalidTransaction::Call.into(),
 			}
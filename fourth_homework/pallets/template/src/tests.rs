@@ -1,5 +1,34 @@
-use crate::{mock::*, Error, Event};
-use frame_support::{assert_noop, assert_ok};
+use crate::{mock::*, Call, Error, Event, Payload, SubmissionMode};
+use codec::{Decode, Encode};
+use frame_support::{assert_noop, assert_ok, traits::Hooks};
+use sp_core::offchain::{
+	testing, OffchainDbExt, OffchainWorkerExt, TransactionPool, TransactionPoolExt,
+};
+use sp_runtime::{
+	testing::{TestSignature, UintAuthorityId},
+	traits::ValidateUnsigned,
+	transaction_validity::{InvalidTransaction, TransactionSource, TransactionValidity},
+	DispatchError,
+};
+use std::sync::{
+	atomic::{AtomicU32, Ordering},
+	Arc,
+};
+
+/// A price payload from the key of `who` for `block_number`, signed by the key of `signer`.
+fn signed_payload(
+	who: u64,
+	block_number: u64,
+	signer: u64,
+) -> (Payload<UintAuthorityId, u64>, TestSignature) {
+	let payload = Payload { price: b"300".to_vec(), block_number, public: UintAuthorityId(who) };
+	let signature = TestSignature(signer, payload.encode());
+	(payload, signature)
+}
+
+fn validate(call: Call<Test>) -> TransactionValidity {
+	TemplateModule::validate_unsigned(TransactionSource::External, &call)
+}
 
 #[test]
 fn it_works_for_default_value() {
@@ -25,3 +54,226 @@ fn correct_error_for_none_value() {
 		);
 	});
 }
+
+#[test]
+fn oracle_authorities_are_managed_by_root() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			TemplateModule::add_oracle_authority(RuntimeOrigin::signed(1), 1),
+			DispatchError::BadOrigin
+		);
+
+		assert_ok!(TemplateModule::add_oracle_authority(RuntimeOrigin::root(), 1));
+		System::assert_last_event(Event::OracleAuthorityAdded { who: 1 }.into());
+		assert_noop!(
+			TemplateModule::add_oracle_authority(RuntimeOrigin::root(), 1),
+			Error::<Test>::AlreadyOracleAuthority
+		);
+		assert_ok!(TemplateModule::add_oracle_authority(RuntimeOrigin::root(), 2));
+		assert_noop!(
+			TemplateModule::add_oracle_authority(RuntimeOrigin::root(), 3),
+			Error::<Test>::TooManyOracleAuthorities
+		);
+
+		assert_ok!(TemplateModule::remove_oracle_authority(RuntimeOrigin::root(), 1));
+		System::assert_last_event(Event::OracleAuthorityRemoved { who: 1 }.into());
+		assert_eq!(TemplateModule::oracle_authorities().to_vec(), vec![2]);
+		assert_noop!(
+			TemplateModule::remove_oracle_authority(RuntimeOrigin::root(), 1),
+			Error::<Test>::NotOracleAuthority
+		);
+	});
+}
+
+#[test]
+fn signed_prices_are_limited_to_one_per_round() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let submit = || TemplateModule::submit_price(RuntimeOrigin::signed(1), b"300".to_vec());
+
+		assert_noop!(submit(), Error::<Test>::WrongSubmissionMode);
+
+		OcwSubmissionMode::set(SubmissionMode::Signed);
+		assert_noop!(submit(), Error::<Test>::NotOracleAuthority);
+		assert_ok!(TemplateModule::add_oracle_authority(RuntimeOrigin::root(), 1));
+		assert_ok!(submit());
+		assert_eq!(TemplateModule::last_oracle_round(1), Some(0));
+		assert_noop!(submit(), Error::<Test>::OracleRateLimited);
+
+		// Blocks 5 to 9 are the next round.
+		System::set_block_number(5);
+		assert_ok!(submit());
+		assert_eq!(TemplateModule::last_oracle_round(1), Some(1));
+	});
+}
+
+#[test]
+fn signed_payloads_need_an_oracle_authority() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		let call = |who, block_number, signer| {
+			let (payload, signature) = signed_payload(who, block_number, signer);
+			Call::unsigned_extrinsic_with_signed_payload { payload, signature }
+		};
+
+		assert_eq!(validate(call(1, 2, 1)), Err(InvalidTransaction::BadSigner.into()));
+		assert_ok!(TemplateModule::add_oracle_authority(RuntimeOrigin::root(), 1));
+		assert_ok!(TemplateModule::add_oracle_authority(RuntimeOrigin::root(), 2));
+		assert_eq!(validate(call(1, 2, 2)), Err(InvalidTransaction::BadProof.into()));
+		assert_eq!(validate(call(1, 3, 1)), Err(InvalidTransaction::Future.into()));
+
+		// One transaction per authority and round.
+		let provides = |call| validate(call).unwrap().provides;
+		assert_eq!(provides(call(1, 2, 1)), provides(call(1, 1, 1)));
+		assert_ne!(provides(call(1, 2, 1)), provides(call(2, 2, 2)));
+
+		let (payload, signature) = signed_payload(1, 2, 1);
+		assert_ok!(TemplateModule::unsigned_extrinsic_with_signed_payload(
+			RuntimeOrigin::none(),
+			payload,
+			signature
+		));
+		assert_eq!(TemplateModule::last_oracle_round(1), Some(0));
+		assert_eq!(validate(call(1, 2, 1)), Err(InvalidTransaction::Stale.into()));
+
+		System::set_block_number(8);
+		assert_eq!(validate(call(2, 2, 2)), Err(InvalidTransaction::Stale.into()));
+		assert_ok!(validate(call(1, 8, 1)));
+
+		OcwSubmissionMode::set(SubmissionMode::Signed);
+		assert_eq!(validate(call(1, 8, 1)), Err(InvalidTransaction::Call.into()));
+	});
+}
+
+#[test]
+fn unsigned_prices_are_limited_to_one_per_round() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(2);
+		let call =
+			|block_number| Call::submit_price_unsigned { price: b"300".to_vec(), block_number };
+		let submit =
+			|| TemplateModule::submit_price_unsigned(RuntimeOrigin::none(), b"300".to_vec(), 2);
+
+		assert_eq!(validate(call(2)), Err(InvalidTransaction::Call.into()));
+		assert_noop!(submit(), Error::<Test>::WrongSubmissionMode);
+
+		OcwSubmissionMode::set(SubmissionMode::Unsigned);
+		// Nothing authenticates these, so they are only taken from the local off-chain worker
+		// and not gossiped.
+		assert_eq!(validate(call(2)), Err(InvalidTransaction::BadSigner.into()));
+		let validate_local =
+			|call| TemplateModule::validate_unsigned(TransactionSource::Local, &call);
+		assert!(!validate_local(call(2)).unwrap().propagate);
+		assert_ok!(TemplateModule::validate_unsigned(TransactionSource::InBlock, &call(2)));
+		assert_eq!(validate_local(call(3)), Err(InvalidTransaction::Future.into()));
+		assert_ok!(submit());
+		assert_noop!(submit(), Error::<Test>::OracleRateLimited);
+		assert_eq!(validate_local(call(2)), Err(InvalidTransaction::Stale.into()));
+	});
+}
+
+const BNB_PRICE: &[u8] = b"312.25";
+
+/// Test externalities in which the off-chain worker can run `runs` times, getting `BNB_PRICE`
+/// for each price request and sending its transactions to `pool`.
+fn offchain_test_ext(
+	pool: impl TransactionPool + Send + 'static,
+	runs: usize,
+) -> sp_io::TestExternalities {
+	let (offchain, state) = testing::TestOffchainExt::new();
+	for _ in 0..runs {
+		state.write().expect_request(testing::PendingRequest {
+			method: "GET".into(),
+			uri: "https://data.binance.com/api/v3/avgPrice?symbol=BNBUSDT".into(),
+			response: Some(br#"{"mins":5,"price":"312.25"}"#.to_vec()),
+			sent: true,
+			..Default::default()
+		});
+	}
+
+	let mut ext = new_test_ext();
+	ext.register_extension(OffchainDbExt::new(offchain.clone()));
+	ext.register_extension(OffchainWorkerExt::new(offchain));
+	ext.register_extension(TransactionPoolExt::new(pool));
+	ext
+}
+
+/// Run the off-chain worker for `block_number` and throw away its changes to the chain state,
+/// as a node does.
+fn run_offchain_worker(block_number: u64) {
+	sp_io::storage::start_transaction();
+	TemplateModule::offchain_worker(block_number);
+	sp_io::storage::rollback_transaction();
+}
+
+/// A transaction pool that rejects every transaction, counting them.
+#[derive(Default)]
+struct RejectingPool(Arc<AtomicU32>);
+
+impl TransactionPool for RejectingPool {
+	fn submit_transaction(&mut self, _extrinsic: Vec<u8>) -> Result<(), ()> {
+		self.0.fetch_add(1, Ordering::SeqCst);
+		Err(())
+	}
+}
+
+#[test]
+fn signed_prices_wait_until_the_last_one_is_included() {
+	let (pool, pool_state) = testing::TestTransactionPoolExt::new();
+	UintAuthorityId::set_all_keys(vec![1u64]);
+	OcwSubmissionMode::set(SubmissionMode::Signed);
+
+	offchain_test_ext(pool, 3).execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(TemplateModule::add_oracle_authority(RuntimeOrigin::root(), 1));
+		let sent = || {
+			let tx = pool_state.write().transactions.pop()?;
+			let tx = Extrinsic::decode(&mut &*tx).unwrap();
+			assert_eq!(
+				tx.call,
+				RuntimeCall::TemplateModule(Call::submit_price { price: BNB_PRICE.to_vec() })
+			);
+			tx.signature.map(|(nonce, ())| nonce)
+		};
+
+		run_offchain_worker(1);
+		assert_eq!(sent(), Some(0));
+
+		// The account nonce has not moved, so the transaction may still be in the pool.
+		run_offchain_worker(2);
+		assert_eq!(sent(), None);
+
+		// Once it is included, the account submits again in the next round.
+		assert_ok!(TemplateModule::submit_price(RuntimeOrigin::signed(1), BNB_PRICE.to_vec()));
+		System::inc_account_nonce(1u64);
+		run_offchain_worker(5);
+		assert_eq!(sent(), Some(1));
+		assert!(pool_state.read().transactions.is_empty());
+	});
+}
+
+#[test]
+fn rejected_signers_back_off_longer_each_time() {
+	let pool = RejectingPool::default();
+	let attempts = pool.0.clone();
+	UintAuthorityId::set_all_keys(vec![1u64]);
+	OcwSubmissionMode::set(SubmissionMode::Signed);
+
+	offchain_test_ext(pool, 7).execute_with(|| {
+		assert_ok!(TemplateModule::add_oracle_authority(RuntimeOrigin::root(), 1));
+		let attempts_at = |block_number| {
+			run_offchain_worker(block_number);
+			attempts.load(Ordering::SeqCst)
+		};
+
+		// Waits of one round of 5 blocks, then 10 blocks, then 20.
+		assert_eq!(attempts_at(1), 1);
+		assert_eq!(attempts_at(5), 1);
+		assert_eq!(attempts_at(6), 2);
+		assert_eq!(attempts_at(15), 2);
+		assert_eq!(attempts_at(16), 3);
+		assert_eq!(attempts_at(35), 3);
+		assert_eq!(attempts_at(36), 4);
+	});
+}
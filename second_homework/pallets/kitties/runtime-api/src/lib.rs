@@ -0,0 +1,18 @@
+//! Runtime API definition for the kitties pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_std::vec::Vec;
+
+pub use pallet_kitties::KittyId;
+
+sp_api::decl_runtime_apis! {
+	pub trait KittiesApi<AccountId>
+	where
+		AccountId: Codec,
+	{
+		/// The kitties owned by `owner`, at most `limit` of them starting from the `start`-th.
+		fn kitties_of(owner: AccountId, start: u32, limit: u32) -> Vec<KittyId>;
+	}
+}
@@ -4,6 +4,8 @@
 /// <https://docs.substrate.io/reference/frame-pallets/>
 pub use pallet::*;
 
+mod migrations;
+
 #[cfg(test)]
 mod mock;
 
@@ -15,15 +17,21 @@ pub mod pallet {
 	use super::*;
 	use frame_support::{pallet_prelude::{*, OptionQuery, DispatchResult}, Blake2_128Concat};
 	use frame_system::pallet_prelude::*;
+	use frame_support::inherent::Vec;
 
 	use sp_io::hashing::blake2_128;
-	use frame_support::traits::Randomness;
+	use frame_support::traits::{Currency, ExistenceRequirement, Randomness};
 
 	pub type KittyId=u32;
+	pub type BalanceOf<T> =
+		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 	#[derive(Encode,Decode,Clone,Copy,RuntimeDebug,PartialEq,Eq,Default,TypeInfo,MaxEncodedLen)]
 	pub struct Kitty(pub [u8;16]);
 
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	/// Configure the pallet by specifying the parameters and types on which it depends.
@@ -32,6 +40,11 @@ pub mod pallet {
 		/// Because this pallet emits events, it depends on the runtime's definition of an event.
 		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
 		type Randomness: Randomness<Self::Hash,Self::BlockNumber>;
+		/// The maximum number of kitties a single account can own.
+		#[pallet::constant]
+		type MaxKittiesOwned: Get<u32>;
+		/// The currency stud fees are paid in.
+		type Currency: Currency<Self::AccountId>;
 	}
 
 	// The pallet's runtime storage items.
@@ -48,10 +61,29 @@ pub mod pallet {
 	#[pallet::getter(fn kitty_owner)]
 	pub type KittyOwner<T:Config>=StorageMap<_,Blake2_128Concat,KittyId,T::AccountId>;
 
+	/// The kitties owned by each account, kept in sync with `KittyOwner`.
+	#[pallet::storage]
+	#[pallet::getter(fn owned_kitties)]
+	pub type OwnedKitties<T:Config>=StorageMap<_,Blake2_128Concat,T::AccountId,BoundedVec<KittyId,T::MaxKittiesOwned>,ValueQuery>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn kitty_parents)]
 	pub type KittyParents<T:Config> = StorageMap<_, Blake2_128Concat,KittyId,(KittyId,KittyId),OptionQuery>;
 
+	/// The account allowed to transfer each kitty on its owner's behalf.
+	#[pallet::storage]
+	#[pallet::getter(fn kitty_approval)]
+	pub type KittyApprovals<T:Config>=StorageMap<_,Blake2_128Concat,KittyId,T::AccountId>;
+
+	/// Operators allowed to transfer every kitty of an owner, keyed by owner then operator.
+	#[pallet::storage]
+	pub type OperatorApprovals<T:Config>=StorageDoubleMap<_,Blake2_128Concat,T::AccountId,Blake2_128Concat,T::AccountId,()>;
+
+	/// The fee for breeding with each kitty offered as a stud.
+	#[pallet::storage]
+	#[pallet::getter(fn stud_fee)]
+	pub type StudFees<T:Config>=StorageMap<_,Blake2_128Concat,KittyId,BalanceOf<T>>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
@@ -59,7 +91,16 @@ pub mod pallet {
 		/// parameters. [something, who]
 		KittyCreated { who: T::AccountId, kitty_id: KittyId,kitty:Kitty },
 		KittyBreed{who:T::AccountId,kitty_id: KittyId,kitty:Kitty},
-		KittyTransferred{who:T::AccountId,recipient:T::AccountId,kitty_id:KittyId}
+		KittyTransferred{who:T::AccountId,recipient:T::AccountId,kitty_id:KittyId},
+		/// `operator` may now transfer the kitty, or nobody may if it is `None`.
+		Approval{owner:T::AccountId,kitty_id:KittyId,operator:Option<T::AccountId>},
+		/// `operator` may now transfer every kitty of `owner`, or no longer may.
+		ApprovalForAll{owner:T::AccountId,operator:T::AccountId,approved:bool},
+		/// Anyone may now breed with the kitty for `fee`.
+		StudOffered{who:T::AccountId,kitty_id:KittyId,fee:BalanceOf<T>},
+		StudWithdrawn{who:T::AccountId,kitty_id:KittyId},
+		/// `who` paid `fee` to `owner` to breed with the stud `kitty_id`.
+		StudFeePaid{who:T::AccountId,owner:T::AccountId,kitty_id:KittyId,fee:BalanceOf<T>},
 	}
 
 	// Errors inform users that something went wrong.
@@ -69,6 +110,25 @@ pub mod pallet {
 		InvalidKittyId,
 		NotOwner,
 		SameKittyId,
+		/// The account already owns `MaxKittiesOwned` kitties.
+		TooManyKitties,
+		/// The caller is neither the owner nor approved for the kitty.
+		NotApproved,
+		/// An owner cannot approve themselves.
+		ApproveToOwner,
+		/// The kitty is not offered as a stud.
+		NotAStud,
+		/// Owners breed with their own studs through `breed`.
+		OwnStud,
+		/// The stud fee is above the most the caller would pay.
+		FeeTooHigh,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_runtime_upgrade() -> Weight {
+			migrations::v1::migrate::<T>()
+		}
 	}
 
 	// Dispatchable functions allows users to interact with the pallet and invoke state changes.
@@ -93,6 +153,7 @@ pub mod pallet {
 			Kitties::<T>::insert(kitty_id,&kitty);
 
 			KittyOwner::<T>::insert(kitty_id,&who);
+			Self::add_owned_kitty(&who,kitty_id)?;
 
 
 			// Emit an event.
@@ -101,6 +162,7 @@ pub mod pallet {
 			Ok(())
 		}
 
+		/// Breed a new kitty from two kitties owned by the caller.
 		#[pallet::call_index(1)]
 		#[pallet::weight(10_000+T::DbWeight::get().writes(1).ref_time())]
 		pub fn breed(origin:OriginFor<T>,kitty_id_1:KittyId,kitty_id_2:KittyId)->DispatchResult{
@@ -111,6 +173,123 @@ pub mod pallet {
 			ensure!(Kitties::<T>::contains_key(kitty_id_1),Error::<T>::InvalidKittyId);
 			ensure!(Kitties::<T>::contains_key(kitty_id_2),Error::<T>::InvalidKittyId);
 
+			ensure!(Self::kitty_owner(kitty_id_1)==Some(who.clone()),Error::<T>::NotOwner);
+			ensure!(Self::kitty_owner(kitty_id_2)==Some(who.clone()),Error::<T>::NotOwner);
+
+			Self::do_breed(who,kitty_id_1,kitty_id_2)
+		}
+
+		#[pallet::call_index(2)]
+		#[pallet::weight(10_000+T::DbWeight::get().writes(1).ref_time())]
+		pub fn transfer(origin:OriginFor<T>,recipient:T::AccountId,kitty_id:KittyId)->DispatchResult{
+			let who=ensure_signed(origin)?;
+			Self::do_transfer(who,recipient,kitty_id)
+		}
+
+		/// Let `operator` transfer `kitty_id`, or nobody if it is `None`. Callable by the owner or
+		/// one of their operators.
+		#[pallet::call_index(3)]
+		#[pallet::weight(10_000+T::DbWeight::get().writes(1).ref_time())]
+		pub fn approve(origin:OriginFor<T>,kitty_id:KittyId,operator:Option<T::AccountId>)->DispatchResult{
+			let who=ensure_signed(origin)?;
+
+			let owner=Self::kitty_owner(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
+			ensure!(owner==who||Self::is_approved_for_all(&owner,&who),Error::<T>::NotApproved);
+			ensure!(operator.as_ref()!=Some(&owner),Error::<T>::ApproveToOwner);
+
+			match &operator{
+				Some(operator)=>KittyApprovals::<T>::insert(kitty_id,operator),
+				None=>KittyApprovals::<T>::remove(kitty_id),
+			}
+			Self::deposit_event(Event::Approval{owner,kitty_id,operator});
+			Ok(())
+		}
+
+		/// Let `operator` transfer and approve every kitty of the caller, now and later.
+		#[pallet::call_index(4)]
+		#[pallet::weight(10_000+T::DbWeight::get().writes(1).ref_time())]
+		pub fn set_approval_for_all(origin:OriginFor<T>,operator:T::AccountId,approved:bool)->DispatchResult{
+			let who=ensure_signed(origin)?;
+			ensure!(operator!=who,Error::<T>::ApproveToOwner);
+
+			if approved{
+				OperatorApprovals::<T>::insert(&who,&operator,());
+			}else{
+				OperatorApprovals::<T>::remove(&who,&operator);
+			}
+			Self::deposit_event(Event::ApprovalForAll{owner:who,operator,approved});
+			Ok(())
+		}
+
+		/// Transfer `kitty_id` from `from` to `to`. Callable by the owner, the account approved for
+		/// the kitty, or an operator of the owner.
+		#[pallet::call_index(5)]
+		#[pallet::weight(10_000+T::DbWeight::get().writes(1).ref_time())]
+		pub fn transfer_from(origin:OriginFor<T>,from:T::AccountId,to:T::AccountId,kitty_id:KittyId)->DispatchResult{
+			let who=ensure_signed(origin)?;
+			ensure!(
+				who==from||Self::kitty_approval(kitty_id)==Some(who.clone())||Self::is_approved_for_all(&from,&who),
+				Error::<T>::NotApproved
+			);
+			Self::do_transfer(from,to,kitty_id)
+		}
+
+		/// Let anyone breed with `kitty_id` for `fee`, paid to its owner. Offering a stud again
+		/// replaces its fee.
+		#[pallet::call_index(6)]
+		#[pallet::weight(10_000+T::DbWeight::get().writes(1).ref_time())]
+		pub fn offer_stud(origin:OriginFor<T>,kitty_id:KittyId,fee:BalanceOf<T>)->DispatchResult{
+			let who=ensure_signed(origin)?;
+			ensure!(Self::kitty_owner(kitty_id)==Some(who.clone()),Error::<T>::NotOwner);
+
+			StudFees::<T>::insert(kitty_id,fee);
+			Self::deposit_event(Event::StudOffered{who,kitty_id,fee});
+			Ok(())
+		}
+
+		#[pallet::call_index(7)]
+		#[pallet::weight(10_000+T::DbWeight::get().writes(1).ref_time())]
+		pub fn withdraw_stud(origin:OriginFor<T>,kitty_id:KittyId)->DispatchResult{
+			let who=ensure_signed(origin)?;
+			ensure!(Self::kitty_owner(kitty_id)==Some(who.clone()),Error::<T>::NotOwner);
+
+			StudFees::<T>::take(kitty_id).ok_or(Error::<T>::NotAStud)?;
+			Self::deposit_event(Event::StudWithdrawn{who,kitty_id});
+			Ok(())
+		}
+
+		/// Breed the caller's `kitty_id` with someone else's stud, paying the stud fee to its
+		/// owner. Fails if the fee is above `max_fee`. The child goes to the caller.
+		#[pallet::call_index(8)]
+		#[pallet::weight(10_000+T::DbWeight::get().writes(1).ref_time())]
+		pub fn breed_with_stud(origin:OriginFor<T>,kitty_id:KittyId,stud_id:KittyId,max_fee:BalanceOf<T>)->DispatchResult{
+			let who=ensure_signed(origin)?;
+
+			ensure!(Self::kitty_owner(kitty_id)==Some(who.clone()),Error::<T>::NotOwner);
+			let owner=Self::kitty_owner(stud_id).ok_or(Error::<T>::InvalidKittyId)?;
+			ensure!(owner!=who,Error::<T>::OwnStud);
+			let fee=Self::stud_fee(stud_id).ok_or(Error::<T>::NotAStud)?;
+			ensure!(fee<=max_fee,Error::<T>::FeeTooHigh);
+
+			T::Currency::transfer(&who,&owner,fee,ExistenceRequirement::KeepAlive)?;
+			Self::deposit_event(Event::StudFeePaid{who:who.clone(),owner,kitty_id:stud_id,fee});
+
+			Self::do_breed(who,kitty_id,stud_id)
+		}
+	}
+
+	impl<T:Config> Pallet<T>{
+		fn get_next_id()->Result<KittyId,DispatchError>{
+			NextKittyId::<T>::try_mutate(|next_id|->Result<KittyId,DispatchError>{
+				let current_id=*next_id;
+				*next_id=next_id.checked_add(1).ok_or::<DispatchError>(Error::<T>::InvalidKittyId.into())?;
+				Ok(current_id)
+			})
+		}
+
+		/// Breed a child of `kitty_id_1` and `kitty_id_2` for `who`. Callers are responsible for
+		/// checking that `who` may use both parents.
+		fn do_breed(who:T::AccountId,kitty_id_1:KittyId,kitty_id_2:KittyId)->DispatchResult{
 			let kitty_id=Self::get_next_id()?;
 			let kitty_1=Self::kitties(kitty_id_1).ok_or(Error::<T>::InvalidKittyId)?;
 			let kitty_2=Self::kitties(kitty_id_2).ok_or(Error::<T>::InvalidKittyId)?;
@@ -123,36 +302,60 @@ pub mod pallet {
 			let kitty=Kitty(data);
 			Kitties::<T>::insert(kitty_id, &kitty);
 			KittyOwner::<T>::insert(kitty_id, &who);
+			Self::add_owned_kitty(&who,kitty_id)?;
 			KittyParents::<T>::insert(kitty_id,(kitty_id_1,kitty_id_2));
 
 			Self::deposit_event(Event::KittyBreed{who,kitty_id,kitty});
 			Ok(())
 		}
 
-		#[pallet::call_index(2)]
-		#[pallet::weight(10_000+T::DbWeight::get().writes(1).ref_time())]
-		pub fn transfer(origin:OriginFor<T>,recipient:T::AccountId,kitty_id:KittyId)->DispatchResult{
-			let who=ensure_signed(origin)?;
+		/// Move `kitty_id` from `from`, which must own it, to `recipient`, clearing its approval.
+		fn do_transfer(from:T::AccountId,recipient:T::AccountId,kitty_id:KittyId)->DispatchResult{
 			ensure!(KittyOwner::<T>::contains_key(kitty_id),Error::<T>::InvalidKittyId);
 
 			let owner=Self::kitty_owner(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
-			ensure!(owner==who,Error::<T>::NotOwner);
+			ensure!(owner==from,Error::<T>::NotOwner);
 
 			KittyOwner::<T>::insert(kitty_id, &recipient);
-			Self::deposit_event(Event::KittyTransferred{who,recipient,kitty_id});
+			KittyApprovals::<T>::remove(kitty_id);
+			StudFees::<T>::remove(kitty_id);
+			Self::remove_owned_kitty(&from,kitty_id);
+			Self::add_owned_kitty(&recipient,kitty_id)?;
+			Self::deposit_event(Event::KittyTransferred{who:from,recipient,kitty_id});
 			Ok(())
 		}
-	}
 
-	impl<T:Config> Pallet<T>{
-		fn get_next_id()->Result<KittyId,DispatchError>{
-			NextKittyId::<T>::try_mutate(|next_id|->Result<KittyId,DispatchError>{
-				let current_id=*next_id;
-				*next_id=next_id.checked_add(1).ok_or::<DispatchError>(Error::<T>::InvalidKittyId.into())?;
-				Ok(current_id)
+		/// Whether `operator` may transfer every kitty of `owner`.
+		pub fn is_approved_for_all(owner:&T::AccountId,operator:&T::AccountId)->bool{
+			OperatorApprovals::<T>::contains_key(owner,operator)
+		}
+
+		/// Index `kitty_id` under `owner`, failing if the owner is already at `MaxKittiesOwned`.
+		fn add_owned_kitty(owner:&T::AccountId,kitty_id:KittyId)->DispatchResult{
+			OwnedKitties::<T>::try_mutate(owner,|kitties|{
+				kitties.try_push(kitty_id).map_err(|_|Error::<T>::TooManyKitties.into())
 			})
 		}
 
+		/// Drop `kitty_id` from the index of `owner`.
+		fn remove_owned_kitty(owner:&T::AccountId,kitty_id:KittyId){
+			OwnedKitties::<T>::mutate_exists(owner,|kitties|{
+				if let Some(owned)=kitties{
+					owned.retain(|id|*id!=kitty_id);
+					if owned.is_empty(){
+						*kitties=None;
+					}
+				}
+			});
+		}
+
+		/// Return at most `limit` kitties of `owner`, skipping the first `start`.
+		///
+		/// Used by the `KittiesApi` runtime API.
+		pub fn kitties_of(owner:T::AccountId,start:u32,limit:u32)->Vec<KittyId>{
+			Self::owned_kitties(owner).into_iter().skip(start as usize).take(limit as usize).collect()
+		}
+
 		fn random_value(sender: &T::AccountId)->[u8;16]{
 			let payload=(
 				T::Randomness::random_seed(),
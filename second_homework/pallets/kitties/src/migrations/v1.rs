@@ -0,0 +1,36 @@
+use frame_support::pallet_prelude::*;
+use frame_support::traits::GetStorageVersion;
+use frame_support::weights::Weight;
+
+use crate::{Config, KittyOwner, OwnedKitties, Pallet};
+
+/// Add every kitty in `KittyOwner` to its owner's `OwnedKitties`, which kitties created before the
+/// index existed are missing from.
+///
+/// An owner's kitties beyond `MaxKittiesOwned` are left out of the index.
+pub fn migrate<T: Config>() -> Weight {
+	let on_chain_version = Pallet::<T>::on_chain_storage_version();
+
+	if on_chain_version >= 1 {
+		return T::DbWeight::get().reads(1);
+	}
+
+	let mut reads = 1u64;
+	let mut writes = 1u64;
+	for (kitty_id, owner) in KittyOwner::<T>::iter() {
+		reads += 2;
+		let indexed = OwnedKitties::<T>::try_mutate(&owner, |kitties| {
+			if kitties.contains(&kitty_id) {
+				return Ok(false)
+			}
+			kitties.try_push(kitty_id).map(|_| true)
+		});
+		if let Ok(true) = indexed {
+			writes += 1;
+		}
+	}
+
+	StorageVersion::new(1).put::<Pallet<T>>();
+
+	T::DbWeight::get().reads_writes(reads, writes)
+}
@@ -1,6 +1,6 @@
 use super::*;
 use crate::{mock::*, Error, Event};
-use frame_support::{assert_noop, assert_ok};
+use frame_support::{assert_noop, assert_ok, traits::GetStorageVersion};
 
 #[test]
 fn it_works_for_create(){
@@ -54,6 +54,11 @@ fn it_works_for_breed(){
 
         assert_eq!(KittiesModule::next_kitty_id(),kitty_id+2);
 
+        assert_noop!(
+            KittiesModule::breed(RuntimeOrigin::signed(account_id+1),kitty_id,kitty_id+1),
+            Error::<Test>::NotOwner,
+        );
+
         assert_ok!(KittiesModule::breed(
             RuntimeOrigin::signed(account_id),
             kitty_id,
@@ -127,4 +132,196 @@ fn it_works_for_transfer(){
 
         assert_eq!(KittiesModule::kitty_owner(kitty_id),Some(account_id));
     });
-}
\ No newline at end of file
+}
+#[test]
+fn owned_kitties_follow_the_owner(){
+    new_test_ext().execute_with(||{
+        let account_id=1;
+        let recipient=2;
+
+        assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id)));
+        assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id)));
+        assert_ok!(KittiesModule::breed(RuntimeOrigin::signed(account_id),0,1));
+        assert_eq!(KittiesModule::owned_kitties(account_id).to_vec(),vec![0,1,2]);
+
+        assert_ok!(KittiesModule::transfer(RuntimeOrigin::signed(account_id),recipient,1));
+        assert_eq!(KittiesModule::owned_kitties(account_id).to_vec(),vec![0,2]);
+        assert_eq!(KittiesModule::owned_kitties(recipient).to_vec(),vec![1]);
+
+        assert_eq!(KittiesModule::kitties_of(account_id,0,1),vec![0]);
+        assert_eq!(KittiesModule::kitties_of(account_id,1,10),vec![2]);
+        assert_eq!(KittiesModule::kitties_of(account_id,2,10),Vec::<KittyId>::new());
+    });
+}
+
+#[test]
+fn too_many_kitties(){
+    new_test_ext().execute_with(||{
+        let account_id=1;
+        let recipient=2;
+
+        for _ in 0..3{
+            assert_ok!(KittiesModule::create(RuntimeOrigin::signed(recipient)));
+        }
+        assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id)));
+        assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id)));
+
+        assert_noop!(
+            KittiesModule::create(RuntimeOrigin::signed(recipient)),
+            Error::<Test>::TooManyKitties,
+        );
+        assert_noop!(
+            KittiesModule::breed(RuntimeOrigin::signed(recipient),0,1),
+            Error::<Test>::TooManyKitties,
+        );
+        assert_noop!(
+            KittiesModule::transfer(RuntimeOrigin::signed(account_id),recipient,3),
+            Error::<Test>::TooManyKitties,
+        );
+    });
+}
+
+#[test]
+fn approved_account_can_transfer_once(){
+    new_test_ext().execute_with(||{
+        let kitty_id=0;
+        let owner=1;
+        let operator=2;
+        let recipient=3;
+
+        assert_ok!(KittiesModule::create(RuntimeOrigin::signed(owner)));
+
+        assert_noop!(
+            KittiesModule::transfer_from(RuntimeOrigin::signed(operator),owner,recipient,kitty_id),
+            Error::<Test>::NotApproved,
+        );
+        assert_noop!(
+            KittiesModule::approve(RuntimeOrigin::signed(owner),kitty_id,Some(owner)),
+            Error::<Test>::ApproveToOwner,
+        );
+
+        assert_ok!(KittiesModule::approve(RuntimeOrigin::signed(owner),kitty_id,Some(operator)));
+        assert_eq!(KittiesModule::kitty_approval(kitty_id),Some(operator));
+        System::assert_last_event(Event::<Test>::Approval{
+            owner:owner,
+            kitty_id:kitty_id,
+            operator:Some(operator),
+        }.into());
+
+        assert_ok!(KittiesModule::transfer_from(RuntimeOrigin::signed(operator),owner,recipient,kitty_id));
+        assert_eq!(KittiesModule::kitty_owner(kitty_id),Some(recipient));
+        assert_eq!(KittiesModule::kitty_approval(kitty_id),None);
+
+        assert_noop!(
+            KittiesModule::transfer_from(RuntimeOrigin::signed(operator),recipient,owner,kitty_id),
+            Error::<Test>::NotApproved,
+        );
+    });
+}
+
+#[test]
+fn operator_can_transfer_every_kitty(){
+    new_test_ext().execute_with(||{
+        let owner=1;
+        let operator=2;
+        let recipient=3;
+
+        assert_ok!(KittiesModule::create(RuntimeOrigin::signed(owner)));
+        assert_ok!(KittiesModule::create(RuntimeOrigin::signed(owner)));
+
+        assert_ok!(KittiesModule::set_approval_for_all(RuntimeOrigin::signed(owner),operator,true));
+        assert!(KittiesModule::is_approved_for_all(&owner,&operator));
+        System::assert_last_event(Event::<Test>::ApprovalForAll{
+            owner:owner,
+            operator:operator,
+            approved:true,
+        }.into());
+
+        assert_ok!(KittiesModule::transfer_from(RuntimeOrigin::signed(operator),owner,recipient,0));
+        assert_eq!(KittiesModule::kitty_owner(0),Some(recipient));
+
+        assert_ok!(KittiesModule::set_approval_for_all(RuntimeOrigin::signed(owner),operator,false));
+        assert_noop!(
+            KittiesModule::transfer_from(RuntimeOrigin::signed(operator),owner,recipient,1),
+            Error::<Test>::NotApproved,
+        );
+    });
+}
+
+#[test]
+fn migration_indexes_owned_kitties(){
+    new_test_ext().execute_with(||{
+        let account_id:u64=1;
+        let recipient:u64=2;
+
+        KittyOwner::<Test>::insert(0u32,account_id);
+        KittyOwner::<Test>::insert(1u32,recipient);
+        KittyOwner::<Test>::insert(2u32,account_id);
+        OwnedKitties::<Test>::insert(account_id,frame_support::BoundedVec::truncate_from(vec![2]));
+
+        crate::migrations::v1::migrate::<Test>();
+
+        let mut owned=KittiesModule::owned_kitties(account_id).to_vec();
+        owned.sort();
+        assert_eq!(owned,vec![0,2]);
+        assert_eq!(KittiesModule::owned_kitties(recipient).to_vec(),vec![1]);
+        assert_eq!(KittiesModule::on_chain_storage_version(),1);
+    });
+}
+
+#[test]
+fn stud_fee_goes_to_the_stud_owner(){
+    new_test_ext().execute_with(||{
+        let stud_id=0;
+        let kitty_id=1;
+        let owner=1;
+        let breeder=2;
+
+        #[allow(unused_must_use)]
+        {
+            Balances::force_set_balance(RuntimeOrigin::root(),breeder,1_000_000);
+        }
+
+        assert_ok!(KittiesModule::create(RuntimeOrigin::signed(owner)));
+        assert_ok!(KittiesModule::create(RuntimeOrigin::signed(breeder)));
+
+        assert_noop!(
+            KittiesModule::breed_with_stud(RuntimeOrigin::signed(breeder),kitty_id,stud_id,1_000),
+            Error::<Test>::NotAStud,
+        );
+        assert_noop!(
+            KittiesModule::offer_stud(RuntimeOrigin::signed(breeder),stud_id,1_000),
+            Error::<Test>::NotOwner,
+        );
+
+        assert_ok!(KittiesModule::offer_stud(RuntimeOrigin::signed(owner),stud_id,1_000));
+        assert_eq!(KittiesModule::stud_fee(stud_id),Some(1_000));
+        assert_noop!(
+            KittiesModule::breed_with_stud(RuntimeOrigin::signed(owner),stud_id,kitty_id,1_000),
+            Error::<Test>::NotOwner,
+        );
+        assert_noop!(
+            KittiesModule::breed_with_stud(RuntimeOrigin::signed(breeder),kitty_id,stud_id,999),
+            Error::<Test>::FeeTooHigh,
+        );
+
+        assert_ok!(KittiesModule::breed_with_stud(RuntimeOrigin::signed(breeder),kitty_id,stud_id,1_000));
+        System::assert_has_event(Event::<Test>::StudFeePaid{
+            who:breeder,
+            owner:owner,
+            kitty_id:stud_id,
+            fee:1_000,
+        }.into());
+        assert_eq!(Balances::free_balance(owner),1_000);
+        assert_eq!(Balances::free_balance(breeder),999_000);
+        assert_eq!(KittiesModule::kitty_owner(2),Some(breeder));
+        assert_eq!(KittiesModule::kitty_parents(2),Some((kitty_id,stud_id)));
+
+        assert_ok!(KittiesModule::withdraw_stud(RuntimeOrigin::signed(owner),stud_id));
+        assert_eq!(KittiesModule::stud_fee(stud_id),None);
+        assert_noop!(
+            KittiesModule::withdraw_stud(RuntimeOrigin::signed(owner),stud_id),
+            Error::<Test>::NotAStud,
+        );
+    });
+}
@@ -104,7 +104,7 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	//   `spec_version`, and `authoring_version` are the same between Wasm and native.
 	// This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
 	//   the compatible custom types.
-	spec_version: 100,
+	spec_version: 105,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
 	transaction_version: 1,
@@ -279,6 +279,8 @@ impl pallet_kitties::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	// type Randomness=dyn Randomness<dyn Output, BlockNumber>;
 	type Randomness = Random;
+	type MaxKittiesOwned = ConstU32<100>;
+	type Currency = Balances;
 }
 
 impl pallet_insecure_randomness_collective_flip::Config for Runtime{
@@ -525,6 +527,12 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_kitties_runtime_api::KittiesApi<Block, AccountId> for Runtime {
+		fn kitties_of(owner: AccountId, start: u32, limit: u32) -> Vec<pallet_kitties::KittyId> {
+			KittiesModule::kitties_of(owner, start, limit)
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn benchmark_metadata(extra: bool) -> (
@@ -0,0 +1,107 @@
+//! Off-chain construction of the Merkle trees anchored by `pallet_poe`'s `anchor_root`.
+//!
+//! The hashing scheme lives in `pallet_poe::merkle`, so trees built here always verify on chain.
+
+pub use pallet_poe::{hashing::Digest, merkle::root_from_proof};
+use pallet_poe::merkle::{leaf_hash, node_hash};
+
+/// A Merkle tree over a batch of document digests.
+pub struct MerkleTree {
+	/// Every level of the tree, from the hashed leaves up to the root.
+	levels: Vec<Vec<Digest>>,
+}
+
+impl MerkleTree {
+	/// Build the tree of `leaves`, kept in the order given. Returns `None` if there are no leaves.
+	pub fn new(leaves: &[Digest]) -> Option<Self> {
+		if leaves.is_empty() {
+			return None
+		}
+
+		let mut levels = vec![leaves.iter().map(leaf_hash).collect::<Vec<_>>()];
+		while let Some(level) = levels.last().filter(|level| level.len() > 1) {
+			let parents = level
+				.chunks(2)
+				.map(|pair| match pair {
+					[a, b] => node_hash(a, b),
+					[a] => *a,
+					_ => unreachable!("chunks are never empty"),
+				})
+				.collect();
+			levels.push(parents);
+		}
+		Some(Self { levels })
+	}
+
+	/// The root to pass to `anchor_root`.
+	pub fn root(&self) -> Digest {
+		self.levels[self.levels.len() - 1][0]
+	}
+
+	/// The number of leaves to pass to `anchor_root`.
+	pub fn leaf_count(&self) -> u32 {
+		self.levels[0].len() as u32
+	}
+
+	/// The proof that the leaf at `index` is in the tree, to pass to `verify_inclusion`.
+	pub fn proof(&self, index: usize) -> Option<Vec<Digest>> {
+		if index >= self.levels[0].len() {
+			return None
+		}
+
+		let mut index = index;
+		let mut proof = Vec::new();
+		for level in &self.levels[..self.levels.len() - 1] {
+			// The last node of an odd level has no sibling and is passed up as is.
+			if let Some(sibling) = level.get(index ^ 1) {
+				proof.push(*sibling);
+			}
+			index /= 2;
+		}
+		Some(proof)
+	}
+}
+
+/// Check `proof` against `root` without going through a node.
+pub fn verify(root: &Digest, leaf: &Digest, proof: &[Digest]) -> bool {
+	root_from_proof(leaf, proof) == *root
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pallet_poe::merkle::max_proof_length;
+
+	fn leaves(count: u8) -> Vec<Digest> {
+		(0..count).map(|i| [i; 32]).collect()
+	}
+
+	#[test]
+	fn empty_tree_is_rejected() {
+		assert!(MerkleTree::new(&[]).is_none());
+	}
+
+	#[test]
+	fn single_leaf_is_its_own_proof() {
+		let tree = MerkleTree::new(&leaves(1)).unwrap();
+		assert_eq!(tree.root(), leaf_hash(&[0; 32]));
+		assert_eq!(tree.proof(0), Some(vec![]));
+	}
+
+	#[test]
+	fn every_leaf_proves_against_the_root() {
+		for count in 1..=9 {
+			let leaves = leaves(count);
+			let tree = MerkleTree::new(&leaves).unwrap();
+			assert_eq!(tree.leaf_count(), count as u32);
+
+			for (index, leaf) in leaves.iter().enumerate() {
+				let proof = tree.proof(index).unwrap();
+				assert!(proof.len() <= max_proof_length(tree.leaf_count()));
+				assert!(verify(&tree.root(), leaf, &proof));
+				assert!(!verify(&tree.root(), &[0xff; 32], &proof));
+			}
+			assert_eq!(tree.proof(count as usize), None);
+		}
+	}
+}
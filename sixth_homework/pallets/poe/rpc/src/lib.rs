@@ -0,0 +1,127 @@
+//! RPC interface for the proof of existence pallet.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use codec::Codec;
+use jsonrpsee::{
+	core::{DeserializeOwned, RpcResult, Serialize},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::{Bytes, H256};
+use sp_runtime::traits::Block as BlockT;
+
+pub use pallet_poe_runtime_api::PoeApi as PoeRuntimeApi;
+
+/// Largest page `poe_claimsOf` will return, whatever the caller asks for.
+const MAX_PAGE_SIZE: u32 = 100;
+
+#[rpc(client, server)]
+pub trait PoeApi<BlockHash, AccountId, BlockNumber> {
+	/// List the claims held by `owner`, one page at a time.
+	#[method(name = "poe_claimsOf")]
+	fn claims_of(
+		&self,
+		owner: AccountId,
+		start_after: Option<Bytes>,
+		limit: u32,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<(Bytes, BlockNumber)>>;
+
+	/// Check that `leaf` is in the anchored Merkle tree `root`, returning the block it was
+	/// anchored at.
+	#[method(name = "poe_verifyInclusion")]
+	fn verify_inclusion(
+		&self,
+		root: H256,
+		leaf: H256,
+		proof: Vec<H256>,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<BlockNumber>>;
+}
+
+/// Provides RPC methods to query the proof of existence pallet.
+pub struct Poe<C, Block> {
+	client: Arc<C>,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> Poe<C, Block> {
+	/// Create new `Poe` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+impl<C, Block, AccountId, BlockNumber>
+	PoeApiServer<<Block as BlockT>::Hash, AccountId, BlockNumber> for Poe<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: PoeRuntimeApi<Block, AccountId, BlockNumber>,
+	AccountId: Codec + DeserializeOwned + Send + Sync + 'static,
+	BlockNumber: Codec + Serialize + Send + Sync + 'static,
+{
+	fn claims_of(
+		&self,
+		owner: AccountId,
+		start_after: Option<Bytes>,
+		limit: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<(Bytes, BlockNumber)>> {
+		let api = self.client.runtime_api();
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		let claims = api
+			.claims_of(at_hash, owner, start_after.map(|b| b.to_vec()), limit.min(MAX_PAGE_SIZE))
+			.map_err(|e| {
+				CallError::Custom(ErrorObject::owned(
+					Error::RuntimeError.into(),
+					"Unable to query claims.",
+					Some(e.to_string()),
+				))
+			})?;
+
+		Ok(claims.into_iter().map(|(claim, block_number)| (claim.into(), block_number)).collect())
+	}
+
+	fn verify_inclusion(
+		&self,
+		root: H256,
+		leaf: H256,
+		proof: Vec<H256>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<BlockNumber>> {
+		let api = self.client.runtime_api();
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		let proof = proof.into_iter().map(|node| node.to_fixed_bytes()).collect();
+		let anchor = api
+			.verify_inclusion(at_hash, root.to_fixed_bytes(), leaf.to_fixed_bytes(), proof)
+			.map_err(|e| {
+				CallError::Custom(ErrorObject::owned(
+					Error::RuntimeError.into(),
+					"Unable to verify inclusion.",
+					Some(e.to_string()),
+				))
+			})?;
+
+		Ok(anchor.map(|anchor| anchor.anchored_at))
+	}
+}
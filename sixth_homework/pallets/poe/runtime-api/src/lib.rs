@@ -0,0 +1,33 @@
+//! Runtime API definition for the proof of existence pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_std::vec::Vec;
+
+pub use pallet_poe::{hashing::Digest, AnchorInfo, ClaimInfo, ClaimRecord, HashAlgorithm, SigningStatus};
+
+sp_api::decl_runtime_apis! {
+	pub trait PoeApi<AccountId, BlockNumber>
+	where
+		AccountId: Codec,
+		BlockNumber: Codec,
+	{
+		/// Claims held by `owner` with the block they were recorded at, at most `limit` per page.
+		///
+		/// Pass the last claim of the previous page as `start_after` to fetch the next one.
+		fn claims_of(owner: AccountId, start_after: Option<Vec<u8>>, limit: u32) -> Vec<(Vec<u8>, BlockNumber)>;
+
+		/// Hash `document` with `algorithm` and return its proof, if any.
+		fn verify_document(algorithm: HashAlgorithm, document: Vec<u8>) -> Option<ClaimInfo<AccountId, BlockNumber>>;
+
+		/// The full record of `claim`, including its metadata.
+		fn claim(claim: Vec<u8>) -> Option<ClaimRecord<AccountId, BlockNumber>>;
+
+		/// The co-owners of a shared claim and the signatures collected for its pending action.
+		fn signing_status(claim: Vec<u8>) -> Option<SigningStatus<AccountId, BlockNumber>>;
+
+		/// The anchor of `root`, if `proof` shows that `leaf` is one of its leaves.
+		fn verify_inclusion(root: Digest, leaf: Digest, proof: Vec<Digest>) -> Option<AnchorInfo<AccountId, BlockNumber>>;
+	}
+}
@@ -1,33 +1,279 @@
 use crate::*;
 use frame_benchmarking::{benchmarks, whitelisted_caller, account};
+use frame_support::sp_runtime::traits::{Bounded, Zero};
+use frame_support::traits::Hooks;
 use frame_system::RawOrigin;
 
+fn fund_account<T: Config>(who: &T::AccountId) {
+	T::Currency::make_free_balance_be(who, BalanceOf::<T>::max_value() / 2u32.into());
+}
+
+/// A funded account that has made the whitelisted caller its delegate with every permission.
+fn delegating_owner<T: Config>() -> T::AccountId {
+	let owner: T::AccountId = account("owner", 0, 0);
+	fund_account::<T>(&owner);
+	let permissions = DelegatePermissions { create: true, revoke: true, transfer: true };
+	Delegates::<T>::insert(&owner, whitelisted_caller::<T::AccountId>(), permissions);
+	owner
+}
+
+/// The whitelisted caller followed by `n - 1` other accounts.
+fn co_owners<T: Config>(n: u32) -> BoundedVec<T::AccountId, T::MaxCoOwners> {
+	let mut owners = vec![whitelisted_caller()];
+	for i in 1 .. n {
+		owners.push(account("co-owner", i, 0));
+	}
+	BoundedVec::try_from(owners).unwrap()
+}
+
+/// `n` distinct claims: the first holds `b` bytes, the rest the 4-byte encoding of their index.
+fn batch<T: Config>(n: u32, b: u32) -> BoundedVec<BoundedVec<u8, T::MaxClaimLength>, T::MaxBatchSize> {
+	let mut claims = vec![BoundedVec::try_from(vec![0xff; b as usize]).unwrap()];
+	for i in 1 .. n {
+		claims.push(BoundedVec::try_from(i.to_le_bytes().to_vec()).unwrap());
+	}
+	BoundedVec::try_from(claims).unwrap()
+}
+
 benchmarks! {
     create_claim {
 		let d in 0 .. T::MaxClaimLength::get();
 		let claim = BoundedVec::try_from(vec![0; d as usize]).unwrap();
+		let owner = delegating_owner::<T>();
 		let caller: T::AccountId = whitelisted_caller();
-	}: _(RawOrigin::Signed(caller.clone()), claim.clone())
+	}: _(RawOrigin::Signed(caller), claim.clone(), None, Some(owner.clone()))
+	verify {
+		assert_eq!(T::Currency::reserved_balance(&owner), Pallet::<T>::claim_deposit(d as usize));
+	}
+
+
+	create_claim_hash {
+		let caller: T::AccountId = whitelisted_caller();
+		fund_account::<T>(&caller);
+		let digest = [1; hashing::DIGEST_LENGTH];
+		let expires_at = frame_system::Pallet::<T>::block_number() + 10u32.into();
+	}: _(RawOrigin::Signed(caller.clone()), HashAlgorithm::Sha2_256, digest, Some(expires_at))
+	verify {
+		assert_eq!(ClaimsCount::<T>::get(&caller), 1);
+	}
 
 
 	revoke_claim {
 		let d in 0 .. T::MaxClaimLength::get();
 		let claim = BoundedVec::try_from(vec![0; d as usize]).unwrap();
+		let owner = delegating_owner::<T>();
 		let caller: T::AccountId = whitelisted_caller();
-		assert!(Pallet::<T>::create_claim(RawOrigin::Signed(caller.clone()).into(), claim.clone()).is_ok());
-	}: _(RawOrigin::Signed(caller.clone()), claim.clone())
+		assert!(Pallet::<T>::create_claim(RawOrigin::Signed(owner.clone()).into(), claim.clone(), None, None).is_ok());
+	}: _(RawOrigin::Signed(caller), claim.clone())
+	verify {
+		assert!(T::Currency::reserved_balance(&owner).is_zero());
+	}
 
 
 	transfer_claim {
 		let d in 0 .. T::MaxClaimLength::get();
 		let claim = BoundedVec::try_from(vec![0; d as usize]).unwrap();
+		let owner = delegating_owner::<T>();
 		let caller: T::AccountId = whitelisted_caller();
 		let recipient: T::AccountId = account("recipient", 0, 0);
-		assert!(Pallet::<T>::create_claim(RawOrigin::Signed(caller.clone()).into(), claim.clone()).is_ok());
-	}: _(RawOrigin::Signed(caller), recipient, claim)
+		fund_account::<T>(&recipient);
+		assert!(Pallet::<T>::create_claim(RawOrigin::Signed(owner).into(), claim.clone(), None, None).is_ok());
+	}: _(RawOrigin::Signed(caller), recipient.clone(), claim.clone())
+	verify {
+		assert_eq!(PendingOffers::<T>::get(&claim).unwrap().recipient, recipient);
+	}
 
-    impl_benchmark_test_suite!(PoeModule, crate::mock::new_test_ext(), crate::mock::Test);
 
-}
+	renew_claim {
+		let d in 0 .. T::MaxClaimLength::get();
+		let claim: BoundedVec<u8, T::MaxClaimLength> = BoundedVec::try_from(vec![0; d as usize]).unwrap();
+		let caller: T::AccountId = whitelisted_caller();
+		fund_account::<T>(&caller);
+		let expires_at = frame_system::Pallet::<T>::block_number() + 10u32.into();
+		assert!(Pallet::<T>::create_claim(RawOrigin::Signed(caller.clone()).into(), claim.clone(), Some(expires_at), None).is_ok());
+		let renewed = expires_at + 10u32.into();
+	}: _(RawOrigin::Signed(caller), claim.clone(), Some(renewed))
+	verify {
+		assert_eq!(Proofs::<T>::get(&claim).and_then(|info| info.expires_at), Some(renewed));
+	}
+
+
+	expire_claims {
+		let n in 0 .. T::MaxExpiriesPerBlock::get();
+		let caller: T::AccountId = whitelisted_caller();
+		fund_account::<T>(&caller);
+		let expires_at = frame_system::Pallet::<T>::block_number() + 1u32.into();
+		for i in 0 .. n {
+			let claim = BoundedVec::try_from(i.to_le_bytes().to_vec()).unwrap();
+			assert!(Pallet::<T>::create_claim(RawOrigin::Signed(caller.clone()).into(), claim, Some(expires_at), None).is_ok());
+		}
+	}: {
+		Pallet::<T>::on_initialize(expires_at);
+	}
+	verify {
+		assert!(ExpiringClaims::<T>::get(expires_at).is_empty());
+		assert_eq!(ClaimsCount::<T>::get(&caller), 0);
+	}
+
+	offer_claim {
+		let d in 0 .. T::MaxClaimLength::get();
+		let claim: BoundedVec<u8, T::MaxClaimLength> = BoundedVec::try_from(vec![0; d as usize]).unwrap();
+		let caller: T::AccountId = whitelisted_caller();
+		let recipient: T::AccountId = account("recipient", 0, 0);
+		fund_account::<T>(&caller);
+		assert!(Pallet::<T>::create_claim(RawOrigin::Signed(caller.clone()).into(), claim.clone(), None, None).is_ok());
+	}: _(RawOrigin::Signed(caller), claim.clone(), recipient, None)
+	verify {
+		assert!(PendingOffers::<T>::contains_key(&claim));
+	}
+
+
+	accept_claim {
+		let d in 0 .. T::MaxClaimLength::get();
+		let claim: BoundedVec<u8, T::MaxClaimLength> = BoundedVec::try_from(vec![0; d as usize]).unwrap();
+		let caller: T::AccountId = whitelisted_caller();
+		let recipient: T::AccountId = account("recipient", 0, 0);
+		fund_account::<T>(&caller);
+		fund_account::<T>(&recipient);
+		assert!(Pallet::<T>::create_claim(RawOrigin::Signed(caller.clone()).into(), claim.clone(), None, None).is_ok());
+		assert!(Pallet::<T>::offer_claim(RawOrigin::Signed(caller).into(), claim.clone(), recipient.clone(), None).is_ok());
+	}: _(RawOrigin::Signed(recipient.clone()), claim.clone())
+	verify {
+		assert_eq!(Proofs::<T>::get(&claim).map(|info| info.owner), Some(recipient));
+	}
+
+
+	cancel_offer {
+		let d in 0 .. T::MaxClaimLength::get();
+		let claim: BoundedVec<u8, T::MaxClaimLength> = BoundedVec::try_from(vec![0; d as usize]).unwrap();
+		let caller: T::AccountId = whitelisted_caller();
+		let recipient: T::AccountId = account("recipient", 0, 0);
+		fund_account::<T>(&caller);
+		assert!(Pallet::<T>::create_claim(RawOrigin::Signed(caller.clone()).into(), claim.clone(), None, None).is_ok());
+		assert!(Pallet::<T>::offer_claim(RawOrigin::Signed(caller.clone()).into(), claim.clone(), recipient, None).is_ok());
+	}: _(RawOrigin::Signed(caller), claim.clone())
+	verify {
+		assert!(!PendingOffers::<T>::contains_key(&claim));
+	}
+
+	update_claim_metadata {
+		let d in 0 .. T::MaxClaimLength::get();
+		let m in 0 .. T::MaxMetadataLength::get();
+		let claim: BoundedVec<u8, T::MaxClaimLength> = BoundedVec::try_from(vec![0; d as usize]).unwrap();
+		let parent: BoundedVec<u8, T::MaxClaimLength> = BoundedVec::try_from(vec![1; d.max(1) as usize]).unwrap();
+		let caller: T::AccountId = whitelisted_caller();
+		fund_account::<T>(&caller);
+		assert!(Pallet::<T>::create_claim(RawOrigin::Signed(caller.clone()).into(), claim.clone(), None, None).is_ok());
+		assert!(Pallet::<T>::create_claim(RawOrigin::Signed(caller.clone()).into(), parent.clone(), None, None).is_ok());
+		let field: BoundedVec<u8, T::MaxMetadataLength> = BoundedVec::try_from(vec![b'a'; m as usize]).unwrap();
+		let metadata = ClaimMetadata::<T> {
+			content_type: field.clone(),
+			label: field.clone(),
+			uri: Some(field),
+			parent: Some(parent),
+		};
+	}: _(RawOrigin::Signed(caller), claim.clone(), metadata)
+	verify {
+		assert!(Metadata::<T>::contains_key(&claim));
+	}
+
+	create_claims {
+		let n in 1 .. T::MaxBatchSize::get();
+		let b in 0 .. T::MaxClaimLength::get();
+		let claims = batch::<T>(n, b);
+		let caller: T::AccountId = whitelisted_caller();
+		fund_account::<T>(&caller);
+	}: _(RawOrigin::Signed(caller.clone()), claims, false)
+	verify {
+		assert_eq!(ClaimsCount::<T>::get(&caller), n);
+	}
+
+
+	revoke_claims {
+		let n in 1 .. T::MaxBatchSize::get();
+		let b in 0 .. T::MaxClaimLength::get();
+		let claims = batch::<T>(n, b);
+		let caller: T::AccountId = whitelisted_caller();
+		fund_account::<T>(&caller);
+		assert!(Pallet::<T>::create_claims(RawOrigin::Signed(caller.clone()).into(), claims.clone(), true).is_ok());
+	}: _(RawOrigin::Signed(caller.clone()), claims, false)
+	verify {
+		assert_eq!(ClaimsCount::<T>::get(&caller), 0);
+	}
+
+	add_delegate {
+		let caller: T::AccountId = whitelisted_caller();
+		let delegate: T::AccountId = account("delegate", 0, 0);
+		let permissions = DelegatePermissions { create: true, revoke: true, transfer: true };
+	}: _(RawOrigin::Signed(caller.clone()), delegate.clone(), permissions)
+	verify {
+		assert!(Delegates::<T>::contains_key(&caller, &delegate));
+	}
+
+
+	remove_delegate {
+		let owner = delegating_owner::<T>();
+		let caller: T::AccountId = whitelisted_caller();
+	}: _(RawOrigin::Signed(owner.clone()), caller.clone())
+	verify {
+		assert!(!Delegates::<T>::contains_key(&owner, &caller));
+	}
 
+	create_shared_claim {
+		let d in 0 .. T::MaxClaimLength::get();
+		let n in 1 .. T::MaxCoOwners::get();
+		let claim = BoundedVec::try_from(vec![0; d as usize]).unwrap();
+		let caller: T::AccountId = whitelisted_caller();
+		fund_account::<T>(&caller);
+	}: _(RawOrigin::Signed(caller), claim.clone(), co_owners::<T>(n), 1)
+	verify {
+		assert_eq!(Proofs::<T>::get(&claim).unwrap().expires_at, None);
+	}
+
+
+	co_sign_claim {
+		let d in 0 .. T::MaxClaimLength::get();
+		let n in 2 .. T::MaxCoOwners::get();
+		let claim: BoundedVec<u8, T::MaxClaimLength> = BoundedVec::try_from(vec![0; d as usize]).unwrap();
+		let caller: T::AccountId = whitelisted_caller();
+		let recipient: T::AccountId = account("recipient", 0, 0);
+		fund_account::<T>(&caller);
+		fund_account::<T>(&recipient);
+		let owners = co_owners::<T>(n);
+		assert!(Pallet::<T>::create_shared_claim(RawOrigin::Signed(caller.clone()).into(), claim.clone(), owners.clone(), n).is_ok());
+		for owner in owners.iter().skip(1) {
+			assert!(Pallet::<T>::co_sign_claim(RawOrigin::Signed(owner.clone()).into(), claim.clone()).is_ok());
+		}
+		assert!(Pallet::<T>::transfer_claim(RawOrigin::Signed(caller).into(), recipient.clone(), claim.clone()).is_ok());
+		for owner in owners.iter().skip(1).take(n as usize - 2) {
+			assert!(Pallet::<T>::co_sign_claim(RawOrigin::Signed(owner.clone()).into(), claim.clone()).is_ok());
+		}
+		let last = owners[n as usize - 1].clone();
+	}: _(RawOrigin::Signed(last), claim.clone())
+	verify {
+		assert_eq!(PendingOffers::<T>::get(&claim).unwrap().recipient, recipient);
+	}
 
+	anchor_root {
+		let caller: T::AccountId = whitelisted_caller();
+		fund_account::<T>(&caller);
+		let root = [1; hashing::DIGEST_LENGTH];
+	}: _(RawOrigin::Signed(caller), root, u32::MAX)
+	verify {
+		assert!(Anchors::<T>::contains_key(root));
+	}
+
+	remove_anchor {
+		let caller: T::AccountId = whitelisted_caller();
+		fund_account::<T>(&caller);
+		let root = [1; hashing::DIGEST_LENGTH];
+		assert!(Pallet::<T>::anchor_root(RawOrigin::Signed(caller.clone()).into(), root, u32::MAX).is_ok());
+	}: _(RawOrigin::Signed(caller.clone()), root)
+	verify {
+		assert!(!Anchors::<T>::contains_key(root));
+		assert!(T::Currency::reserved_balance(&caller).is_zero());
+	}
+
+    impl_benchmark_test_suite!(PoeModule, crate::mock::new_test_ext(), crate::mock::Test);
+
+}
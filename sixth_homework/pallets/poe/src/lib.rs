@@ -1,5 +1,6 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
+pub use hashing::{ClaimHasher, HashAlgorithm, SpIoHasher};
 pub use pallet::*;
 pub use weights::WeightInfo;
 
@@ -12,6 +13,10 @@ mod tests;
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
+pub mod migrations;
+
+pub mod hashing;
+pub mod merkle;
 pub mod weights;
 
 
@@ -21,22 +26,169 @@ pub mod pallet {
 ///	pub use weights::WeightInfo;
 	pub use frame_support::inherent::Vec;
 	pub use frame_support::pallet_prelude::*;
+	pub use frame_support::traits::{BalanceStatus, Currency, ReservableCurrency};
+	use frame_support::storage::with_storage_layer;
 	pub use frame_system::{pallet_prelude::*};
+	use frame_support::sp_runtime::traits::{Saturating, Zero};
 	pub use sp_std::prelude::*;
 	use super::WeightInfo;
 
+	pub type BalanceOf<T> =
+		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+	/// A proof as recorded in `Proofs`.
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct ClaimInfo<AccountId, BlockNumber> {
+		pub owner: AccountId,
+		pub created_at: BlockNumber,
+		/// The block at which the claim is pruned, if it is not kept forever.
+		pub expires_at: Option<BlockNumber>,
+		/// The algorithm a `create_claim_hash` digest was made with, `None` for a raw claim.
+		pub algorithm: Option<HashAlgorithm>,
+	}
+
+	/// A transfer proposed by a claim's owner that the recipient has yet to accept.
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct ClaimOffer<AccountId, BlockNumber> {
+		pub recipient: AccountId,
+		/// The offer can no longer be accepted from this block on.
+		pub expires_at: Option<BlockNumber>,
+	}
+
+	/// What a delegate registered with `add_delegate` may do on the owner's behalf.
+	#[derive(Encode, Decode, Clone, Default, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct DelegatePermissions {
+		/// Create claims owned by, and paid for by, the owner.
+		pub create: bool,
+		/// Revoke the owner's claims.
+		pub revoke: bool,
+		/// Transfer the owner's claims.
+		pub transfer: bool,
+	}
+
+	/// Descriptive data the owner can attach to a claim.
+	#[derive(
+		Encode, Decode, CloneNoBound, PartialEqNoBound, EqNoBound, RuntimeDebugNoBound, TypeInfo, MaxEncodedLen,
+	)]
+	#[scale_info(skip_type_params(T))]
+	#[codec(mel_bound())]
+	pub struct ClaimMetadata<T: Config> {
+		/// MIME type of the notarised document, e.g. `application/pdf`.
+		pub content_type: BoundedVec<u8, T::MaxMetadataLength>,
+		/// Free-form label.
+		pub label: BoundedVec<u8, T::MaxMetadataLength>,
+		/// Where the document itself is kept off chain.
+		pub uri: Option<BoundedVec<u8, T::MaxMetadataLength>>,
+		/// The claim this one is a revision of.
+		pub parent: Option<BoundedVec<u8, T::MaxClaimLength>>,
+	}
+
+	/// A Merkle root recorded by `anchor_root`.
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct AnchorInfo<AccountId, BlockNumber> {
+		pub owner: AccountId,
+		/// Number of documents in the anchored tree.
+		pub leaf_count: u32,
+		pub anchored_at: BlockNumber,
+	}
+
+	/// What the co-owners of a shared claim are asked to sign.
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub enum SharedAction<AccountId> {
+		/// Make a newly created shared claim final.
+		Create,
+		Revoke,
+		Transfer(AccountId),
+	}
+
+	/// The co-owners of a shared claim and how many of them must sign each action.
+	#[derive(
+		Encode, Decode, CloneNoBound, PartialEqNoBound, EqNoBound, RuntimeDebugNoBound, TypeInfo, MaxEncodedLen,
+	)]
+	#[scale_info(skip_type_params(T))]
+	#[codec(mel_bound())]
+	pub struct CoOwnership<T: Config> {
+		pub owners: BoundedVec<T::AccountId, T::MaxCoOwners>,
+		pub threshold: u32,
+	}
+
+	/// An action on a shared claim that is still collecting signatures.
+	#[derive(
+		Encode, Decode, CloneNoBound, PartialEqNoBound, EqNoBound, RuntimeDebugNoBound, TypeInfo, MaxEncodedLen,
+	)]
+	#[scale_info(skip_type_params(T))]
+	#[codec(mel_bound())]
+	pub struct PendingSigning<T: Config> {
+		pub action: SharedAction<T::AccountId>,
+		pub signatures: BoundedVec<T::AccountId, T::MaxCoOwners>,
+		/// Signatures are no longer accepted from this block on.
+		pub deadline: T::BlockNumber,
+	}
+
+	/// The signing state of a shared claim, as returned by the `PoeApi` runtime API.
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+	pub struct SigningStatus<AccountId, BlockNumber> {
+		pub co_owners: Vec<AccountId>,
+		pub threshold: u32,
+		/// The action being signed, if any.
+		pub action: Option<SharedAction<AccountId>>,
+		pub signatures: Vec<AccountId>,
+		pub deadline: Option<BlockNumber>,
+	}
+
+	/// A claim with its metadata, as returned by the `PoeApi` runtime API.
+	///
+	/// `content_type` and `label` are empty if no metadata was attached.
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+	pub struct ClaimRecord<AccountId, BlockNumber> {
+		pub info: ClaimInfo<AccountId, BlockNumber>,
+		pub content_type: Vec<u8>,
+		pub label: Vec<u8>,
+		pub uri: Option<Vec<u8>>,
+		pub parent: Option<Vec<u8>>,
+	}
+
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
 		/// The maximum length of claim that can be added.
 		#[pallet::constant] // Declaring variable type as constant.
 		type  MaxClaimLength: Get<u32>; // Currently only accepting u32 type.
+		/// The maximum number of claims a single account can hold.
+		#[pallet::constant]
+		type MaxClaimsPerOwner: Get<u32>;
 		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
 		type WeightInfo: WeightInfo;
-
+		/// Hashes documents for `verify_document`.
+		type ClaimHasher: ClaimHasher;
+		/// The currency claim deposits are reserved in.
+		type Currency: ReservableCurrency<Self::AccountId>;
+		/// The flat part of the deposit reserved for every claim.
+		#[pallet::constant]
+		type ClaimDepositBase: Get<BalanceOf<Self>>;
+		/// The deposit reserved for each byte of a claim.
+		#[pallet::constant]
+		type ClaimDepositPerByte: Get<BalanceOf<Self>>;
+		/// The maximum number of claims that can expire in the same block.
+		#[pallet::constant]
+		type MaxExpiriesPerBlock: Get<u32>;
+		/// The maximum length of each text field of `ClaimMetadata`.
+		#[pallet::constant]
+		type MaxMetadataLength: Get<u32>;
+		/// The maximum number of claims in one `create_claims` or `revoke_claims` call.
+		#[pallet::constant]
+		type MaxBatchSize: Get<u32>;
+		/// The maximum number of co-owners of a shared claim.
+		#[pallet::constant]
+		type MaxCoOwners: Get<u32>;
+		/// How long co-owners have to sign an action on a shared claim. Must not be zero.
+		#[pallet::constant]
+		type SigningPeriod: Get<Self::BlockNumber>;
 	}
 
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
 
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	//存储项
@@ -46,9 +198,88 @@ pub mod pallet {
 	_,
 	Blake2_128Concat,
 	BoundedVec<u8, T::MaxClaimLength>, // 不能直接使用vec 类型，需要使用长度受限的vec 类型
-	(T::AccountId, T::BlockNumber),
+	ClaimInfo<T::AccountId, T::BlockNumber>,
+	>;
+
+	/// Per-owner index of `Proofs`, so an account's claims can be listed without a full scan.
+	#[pallet::storage]
+	pub type ClaimsByOwner<T: Config> = StorageDoubleMap<
+	_,
+	Blake2_128Concat,
+	T::AccountId,
+	Blake2_128Concat,
+	BoundedVec<u8, T::MaxClaimLength>,
+	T::BlockNumber,
+	>;
+
+	/// Deposit reserved from the owner of each claim, returned on revoke.
+	#[pallet::storage]
+	pub type Deposits<T: Config> =
+		StorageMap<_, Blake2_128Concat, BoundedVec<u8, T::MaxClaimLength>, BalanceOf<T>>;
+
+	/// Metadata attached to each claim, with the part of the claim's deposit it accounts for.
+	#[pallet::storage]
+	pub type Metadata<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		BoundedVec<u8, T::MaxClaimLength>,
+		(ClaimMetadata<T>, BalanceOf<T>),
 	>;
 
+	/// Claims to prune at the start of each block.
+	#[pallet::storage]
+	pub type ExpiringClaims<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::BlockNumber,
+		BoundedVec<BoundedVec<u8, T::MaxClaimLength>, T::MaxExpiriesPerBlock>,
+		ValueQuery,
+	>;
+
+	/// Pending transfers created by `offer_claim`, at most one per claim.
+	#[pallet::storage]
+	pub type PendingOffers<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		BoundedVec<u8, T::MaxClaimLength>,
+		ClaimOffer<T::AccountId, T::BlockNumber>,
+	>;
+
+	/// Accounts allowed to manage claims on behalf of an owner, keyed by owner then delegate.
+	#[pallet::storage]
+	pub type Delegates<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		T::AccountId,
+		DelegatePermissions,
+	>;
+
+	/// Co-owners of shared claims. The claim's entry in `Proofs` belongs to the account that
+	/// created it and paid its deposit.
+	#[pallet::storage]
+	pub type CoOwners<T: Config> =
+		StorageMap<_, Blake2_128Concat, BoundedVec<u8, T::MaxClaimLength>, CoOwnership<T>>;
+
+	/// The action each shared claim's co-owners are currently signing, at most one per claim.
+	#[pallet::storage]
+	pub type PendingSignatures<T: Config> =
+		StorageMap<_, Blake2_128Concat, BoundedVec<u8, T::MaxClaimLength>, PendingSigning<T>>;
+
+	/// Merkle roots of document batches, each standing for `leaf_count` claims without storing them.
+	#[pallet::storage]
+	pub type Anchors<T: Config> =
+		StorageMap<_, Blake2_128Concat, hashing::Digest, AnchorInfo<T::AccountId, T::BlockNumber>>;
+
+	/// Deposit reserved from the owner of each anchor, returned by `remove_anchor`.
+	#[pallet::storage]
+	pub type AnchorDeposits<T: Config> = StorageMap<_, Blake2_128Concat, hashing::Digest, BalanceOf<T>>;
+
+	/// Number of entries held in `ClaimsByOwner` for each account.
+	#[pallet::storage]
+	pub type ClaimsCount<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
 
 	/// Defining an event.
 	#[pallet::event]
@@ -58,11 +289,101 @@ pub mod pallet {
 	//	ClaimRevoked(T::AccountId, Vec<u8>), 
 	ClaimCreated(T::AccountId, BoundedVec<u8, T::MaxClaimLength>), 
 	ClaimRevoked(T::AccountId, BoundedVec<u8, T::MaxClaimLength>),
-	ClaimTransferred {
+	/// A claim was created from a document digest rather than the document itself.
+	ClaimHashCreated {
+		owner: T::AccountId,
+		algorithm: HashAlgorithm,
+		digest: hashing::Digest,
+	},
+	/// A claim's expiry was moved by its owner.
+	ClaimRenewed {
+		owner: T::AccountId,
+		claim: BoundedVec<u8, T::MaxClaimLength>,
+		expires_at: Option<T::BlockNumber>,
+	},
+	/// A claim in a best-effort batch could not be created or revoked.
+	ClaimBatchItemFailed {
+		index: u32,
+		claim: BoundedVec<u8, T::MaxClaimLength>,
+		error: DispatchError,
+	},
+	/// The owner of a claim replaced its metadata.
+	ClaimMetadataUpdated {
+		owner: T::AccountId,
+		claim: BoundedVec<u8, T::MaxClaimLength>,
+		metadata: ClaimMetadata<T>,
+	},
+	/// The owner of a claim offered it to `recipient`.
+	ClaimOffered {
+		owner: T::AccountId,
+		recipient: T::AccountId,
+		claim: BoundedVec<u8, T::MaxClaimLength>,
+		expires_at: Option<T::BlockNumber>,
+	},
+	/// The recipient of an offer accepted it and now owns the claim.
+	ClaimOfferAccepted {
 		sender: T::AccountId,
 		recipient: T::AccountId,
 		claim: BoundedVec<u8, T::MaxClaimLength>,
 	},
+	/// The owner of a claim withdrew its pending offer.
+	ClaimOfferCancelled {
+		owner: T::AccountId,
+		recipient: T::AccountId,
+		claim: BoundedVec<u8, T::MaxClaimLength>,
+	},
+	/// A claim reached its expiry and was pruned.
+	ClaimExpired {
+		owner: T::AccountId,
+		claim: BoundedVec<u8, T::MaxClaimLength>,
+	},
+	/// `owner` let `delegate` act on its claims with the given permissions.
+	DelegateAdded {
+		owner: T::AccountId,
+		delegate: T::AccountId,
+		permissions: DelegatePermissions,
+	},
+	/// `owner` withdrew the permissions of `delegate`.
+	DelegateRemoved {
+		owner: T::AccountId,
+		delegate: T::AccountId,
+	},
+	/// A shared claim was created and awaits the signatures of its co-owners.
+	SharedClaimCreated {
+		owner: T::AccountId,
+		claim: BoundedVec<u8, T::MaxClaimLength>,
+		co_owners: BoundedVec<T::AccountId, T::MaxCoOwners>,
+		threshold: u32,
+	},
+	/// A co-owner asked the others to sign `action` on a shared claim.
+	SharedActionProposed {
+		proposer: T::AccountId,
+		claim: BoundedVec<u8, T::MaxClaimLength>,
+		action: SharedAction<T::AccountId>,
+	},
+	/// A co-owner signed the pending action of a shared claim.
+	ClaimCoSigned {
+		signer: T::AccountId,
+		claim: BoundedVec<u8, T::MaxClaimLength>,
+		signatures: u32,
+	},
+	/// The Merkle root of a batch of `leaf_count` documents was anchored.
+	RootAnchored {
+		owner: T::AccountId,
+		root: hashing::Digest,
+		leaf_count: u32,
+	},
+	/// The owner of an anchored Merkle root removed it and got its deposit back.
+	AnchorRemoved {
+		owner: T::AccountId,
+		root: hashing::Digest,
+	},
+	/// The preceding event was caused by `delegate` acting on behalf of `owner`.
+	DelegateActed {
+		owner: T::AccountId,
+		delegate: T::AccountId,
+		claim: BoundedVec<u8, T::MaxClaimLength>,
+	},
 	}
 
 	/// Error message.
@@ -73,49 +394,127 @@ pub mod pallet {
 		ClaimNotExist,
 		NotClaimOwner,
 	    TransferToOwner,
+		/// The account already holds `MaxClaimsPerOwner` claims.
+		TooManyClaims,
+		/// The expiry is not in the future, or would shorten the claim's lifetime.
+		InvalidExpiry,
+		/// `MaxExpiriesPerBlock` claims already expire at the requested block.
+		TooManyExpiries,
+		/// The claim has no pending offer.
+		OfferNotExist,
+		/// The pending offer was made to another account.
+		NotOfferRecipient,
+		/// The pending offer is past its expiry.
+		OfferExpired,
+		/// The parent claim does not exist, or is the claim itself.
+		InvalidParent,
+		/// An account cannot be its own delegate.
+		InvalidDelegate,
+		/// The account is not a delegate of the owner.
+		DelegateNotExist,
+		/// The delegate has not been granted permission for this action.
+		DelegateNotPermitted,
+		/// The account is not a co-owner of the shared claim.
+		NotCoOwner,
+		/// The threshold is zero or larger than the number of co-owners.
+		InvalidThreshold,
+		/// An account is listed more than once as co-owner.
+		DuplicateCoOwner,
+		/// The claim is not a shared claim.
+		ClaimNotShared,
+		/// The shared claim has no action awaiting signatures.
+		SigningNotExist,
+		/// The pending action is past its signing deadline.
+		SigningExpired,
+		/// The co-owner has already signed the pending action.
+		AlreadySigned,
+		/// Another action on the shared claim is still collecting signatures.
+		SigningInProgress,
+		/// The claim is shared, so this needs its co-owners' signatures.
+		SharedClaim,
+		/// The Merkle root has already been anchored.
+		RootAlreadyAnchored,
+		/// An anchored tree must have at least one leaf.
+		EmptyAnchor,
+		/// The Merkle root has not been anchored.
+		AnchorNotExist,
+		/// The Merkle root was anchored by another account.
+		NotAnchorOwner,
 	}
 
 	/// 保留函数
 	#[pallet::hooks]
-	impl <T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+	impl <T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			// `MaxExpiriesPerBlock` bounds the work done here.
+			let expiring = ExpiringClaims::<T>::take(now);
+			let count = expiring.len() as u32;
+
+			for claim in expiring {
+				Self::expire_claim(claim);
+			}
+
+			T::WeightInfo::expire_claims(count)
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			migrations::v1::migrate::<T>()
+		}
+	}
 
 	/// 可调用函数
 	#[pallet::call]
 	impl <T: Config> Pallet<T> {
 		///#[pallet::weight(0)] // Specifying weight 0. claim created.
+		///
+		/// With `on_behalf_of` a delegate allowed to create claims records one owned, and paid
+		/// for, by that account instead.
+		#[pallet::call_index(0)]
 		#[pallet::weight(T::WeightInfo::create_claim(claim.len() as u32))]
-		pub fn create_claim(origin: OriginFor<T>, claim: BoundedVec<u8, T::MaxClaimLength>) -> DispatchResultWithPostInfo {
+		pub fn create_claim(
+			origin: OriginFor<T>,
+			claim: BoundedVec<u8, T::MaxClaimLength>,
+			expires_at: Option<T::BlockNumber>,
+			on_behalf_of: Option<T::AccountId>,
+		) -> DispatchResultWithPostInfo {
 			let sender = ensure_signed(origin)?;
+			let owner = on_behalf_of.unwrap_or_else(|| sender.clone());
+			let delegate = Self::ensure_can_act(&sender, &owner, |permissions| permissions.create)?;
 
-			ensure!(!Proofs::<T>::contains_key(&claim), Error::<T>::ProofAlreadyExist);
+			Self::do_create_claim(&owner, &claim, expires_at, None)?;
 
-			Proofs::<T>::insert(
-				&claim,
-				(sender.clone(), frame_system::Pallet::<T>::block_number()),
-			);
-
-			Self::deposit_event(Event::ClaimCreated(sender, claim));
+			Self::deposit_event(Event::ClaimCreated(owner.clone(), claim.clone()));
+			Self::deposit_delegate_event(owner, delegate, claim);
 
 			Ok(().into())
 		}
 
 		//claim revoke
 		///#[pallet::weight(0)]
+		#[pallet::call_index(1)]
 		#[pallet::weight(T::WeightInfo::revoke_claim(claim.len() as u32))]
 		pub fn revoke_claim(origin: OriginFor<T>, claim: BoundedVec<u8, T::MaxClaimLength>) ->DispatchResultWithPostInfo {
 			let sender = ensure_signed(origin)?;
 
-			let (owner, _) = Proofs::<T>::get(&claim).ok_or(Error::<T>::ClaimNotExist)?;
-			ensure!(owner == sender, Error::<T>::NotClaimOwner);
-
-			Proofs::<T>::remove(&claim);
+			let owner = Proofs::<T>::get(&claim).ok_or(Error::<T>::ClaimNotExist)?.owner;
+			if CoOwners::<T>::contains_key(&claim) {
+				Self::propose_shared_action(&sender, claim, SharedAction::Revoke)?;
+				return Ok(().into())
+			}
+			let delegate = Self::ensure_can_act(&sender, &owner, |permissions| permissions.revoke)?;
+			Self::do_revoke_claim(&owner, &claim)?;
 
-			Self::deposit_event(Event::ClaimRevoked(sender, claim));
+			Self::deposit_event(Event::ClaimRevoked(owner.clone(), claim.clone()));
+			Self::deposit_delegate_event(owner, delegate, claim);
 
 			Ok(().into())
 		}
 
-		///#[pallet::weight(0)]
+		/// Offer a claim to `recipient` with no expiry, as `offer_claim` does.
+		///
+		/// The claim only moves once `recipient` calls `accept_claim`. Co-owners of a shared claim
+		/// sign the offer first.
+		#[pallet::call_index(2)]
 		#[pallet::weight(T::WeightInfo::transfer_claim(claim.len() as u32))]
 		pub fn transfer_claim(
 			origin: OriginFor<T>,
@@ -125,19 +524,732 @@ pub mod pallet {
 			let signer = ensure_signed(origin)?;
 // Did it exceed the maximum length
 
-			let (owner, _block_number) =
-				Proofs::<T>::get(&claim).ok_or(Error::<T>::ClaimNotExist)?;
-			ensure!(signer == owner, Error::<T>::NotClaimOwner);
-			ensure!(signer != recipient, Error::<T>::TransferToOwner);
+			let info = Proofs::<T>::get(&claim).ok_or(Error::<T>::ClaimNotExist)?;
+			let owner = info.owner.clone();
+			if CoOwners::<T>::contains_key(&claim) {
+				ensure!(owner != recipient, Error::<T>::TransferToOwner);
+				return Self::propose_shared_action(&signer, claim, SharedAction::Transfer(recipient))
+			}
+			let delegate = Self::ensure_can_act(&signer, &owner, |permissions| permissions.transfer)?;
+			ensure!(owner != recipient, Error::<T>::TransferToOwner);
 
-			Proofs::<T>::insert(
+			let offer = ClaimOffer { recipient: recipient.clone(), expires_at: None };
+			PendingOffers::<T>::insert(&claim, offer);
+			Self::deposit_event(Event::ClaimOffered {
+				owner: owner.clone(),
+				recipient,
+				claim: claim.clone(),
+				expires_at: None,
+			});
+			Self::deposit_delegate_event(owner, delegate, claim);
+			Ok(())
+		}
+
+		/// Create a claim from the digest of a document, so the document itself never goes on chain.
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::create_claim_hash())]
+		pub fn create_claim_hash(
+			origin: OriginFor<T>,
+			algorithm: HashAlgorithm,
+			digest: hashing::Digest,
+			expires_at: Option<T::BlockNumber>,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			let claim = BoundedVec::<u8, T::MaxClaimLength>::try_from(digest.to_vec())
+				.map_err(|_| Error::<T>::ClaimTooLong)?;
+			Self::do_create_claim(&sender, &claim, expires_at, Some(algorithm))?;
+
+			Self::deposit_event(Event::ClaimHashCreated { owner: sender, algorithm, digest });
+
+			Ok(().into())
+		}
+
+		/// Move the expiry of a claim later, give an expiry to a claim kept forever, or drop the
+		/// expiry so the claim is kept forever.
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::WeightInfo::renew_claim(claim.len() as u32))]
+		pub fn renew_claim(
+			origin: OriginFor<T>,
+			claim: BoundedVec<u8, T::MaxClaimLength>,
+			expires_at: Option<T::BlockNumber>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let mut info = Proofs::<T>::get(&claim).ok_or(Error::<T>::ClaimNotExist)?;
+			ensure!(info.owner == sender, Error::<T>::NotClaimOwner);
+			// The expiry of a shared claim is its signing deadline until it is final.
+			ensure!(!CoOwners::<T>::contains_key(&claim), Error::<T>::SharedClaim);
+
+			match expires_at {
+				Some(new_expiry) => {
+					// A claim kept forever can be given an expiry, as long as it is in the future.
+					let earliest =
+						info.expires_at.unwrap_or_else(frame_system::Pallet::<T>::block_number);
+					ensure!(new_expiry > earliest, Error::<T>::InvalidExpiry);
+					Self::schedule_expiry(&claim, new_expiry)?;
+				},
+				None => ensure!(info.expires_at.is_some(), Error::<T>::InvalidExpiry),
+			}
+			if let Some(old_expiry) = info.expires_at {
+				Self::unschedule_expiry(&claim, old_expiry);
+			}
+
+			info.expires_at = expires_at;
+			Proofs::<T>::insert(&claim, info);
+
+			Self::deposit_event(Event::ClaimRenewed { owner: sender, claim, expires_at });
+			Ok(())
+		}
+
+		/// Offer a claim to `recipient`, who becomes its owner only once they call `accept_claim`.
+		///
+		/// A new offer replaces any pending one.
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::offer_claim(claim.len() as u32))]
+		pub fn offer_claim(
+			origin: OriginFor<T>,
+			claim: BoundedVec<u8, T::MaxClaimLength>,
+			recipient: T::AccountId,
+			expires_at: Option<T::BlockNumber>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let info = Proofs::<T>::get(&claim).ok_or(Error::<T>::ClaimNotExist)?;
+			ensure!(sender == info.owner, Error::<T>::NotClaimOwner);
+			ensure!(!CoOwners::<T>::contains_key(&claim), Error::<T>::SharedClaim);
+			ensure!(sender != recipient, Error::<T>::TransferToOwner);
+			if let Some(expires_at) = expires_at {
+				ensure!(
+					expires_at > frame_system::Pallet::<T>::block_number(),
+					Error::<T>::InvalidExpiry
+				);
+			}
+
+			PendingOffers::<T>::insert(&claim, ClaimOffer { recipient: recipient.clone(), expires_at });
+
+			Self::deposit_event(Event::ClaimOffered { owner: sender, recipient, claim, expires_at });
+			Ok(())
+		}
+
+		/// Accept a pending offer and take ownership of the claim.
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::WeightInfo::accept_claim(claim.len() as u32))]
+		pub fn accept_claim(
+			origin: OriginFor<T>,
+			claim: BoundedVec<u8, T::MaxClaimLength>,
+		) -> DispatchResult {
+			let recipient = ensure_signed(origin)?;
+
+			let offer = PendingOffers::<T>::get(&claim).ok_or(Error::<T>::OfferNotExist)?;
+			ensure!(offer.recipient == recipient, Error::<T>::NotOfferRecipient);
+			if let Some(expires_at) = offer.expires_at {
+				ensure!(
+					frame_system::Pallet::<T>::block_number() < expires_at,
+					Error::<T>::OfferExpired
+				);
+			}
+
+			let info = Proofs::<T>::get(&claim).ok_or(Error::<T>::ClaimNotExist)?;
+			let sender = info.owner.clone();
+			Self::do_transfer_claim(&claim, info, &recipient)?;
+
+			Self::deposit_event(Event::ClaimOfferAccepted { sender, recipient, claim });
+			Ok(())
+		}
+
+		/// Withdraw the pending offer of a claim.
+		#[pallet::call_index(7)]
+		#[pallet::weight(T::WeightInfo::cancel_offer(claim.len() as u32))]
+		pub fn cancel_offer(
+			origin: OriginFor<T>,
+			claim: BoundedVec<u8, T::MaxClaimLength>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let info = Proofs::<T>::get(&claim).ok_or(Error::<T>::ClaimNotExist)?;
+			ensure!(sender == info.owner, Error::<T>::NotClaimOwner);
+			// Co-owners replace the offer of a shared claim by signing a new transfer.
+			ensure!(!CoOwners::<T>::contains_key(&claim), Error::<T>::SharedClaim);
+			let offer = PendingOffers::<T>::take(&claim).ok_or(Error::<T>::OfferNotExist)?;
+
+			Self::deposit_event(Event::ClaimOfferCancelled {
+				owner: sender,
+				recipient: offer.recipient,
+				claim,
+			});
+			Ok(())
+		}
+
+		/// Attach `metadata` to a claim, replacing any it already had.
+		///
+		/// The claim's deposit is topped up or partly refunded to match the new metadata's size.
+		#[pallet::call_index(8)]
+		#[pallet::weight(T::WeightInfo::update_claim_metadata(claim.len() as u32, metadata.encoded_size() as u32))]
+		pub fn update_claim_metadata(
+			origin: OriginFor<T>,
+			claim: BoundedVec<u8, T::MaxClaimLength>,
+			metadata: ClaimMetadata<T>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let info = Proofs::<T>::get(&claim).ok_or(Error::<T>::ClaimNotExist)?;
+			ensure!(info.owner == sender, Error::<T>::NotClaimOwner);
+			if let Some(parent) = &metadata.parent {
+				ensure!(parent != &claim && Proofs::<T>::contains_key(parent), Error::<T>::InvalidParent);
+			}
+
+			let old_deposit =
+				Metadata::<T>::get(&claim).map(|(_, deposit)| deposit).unwrap_or_else(Zero::zero);
+			let new_deposit = T::ClaimDepositPerByte::get()
+				.saturating_mul((metadata.encoded_size() as u32).into());
+			if new_deposit > old_deposit {
+				T::Currency::reserve(&sender, new_deposit - old_deposit)?;
+			} else {
+				T::Currency::unreserve(&sender, old_deposit - new_deposit);
+			}
+			Deposits::<T>::mutate(&claim, |deposit| {
+				let total = deposit.unwrap_or_else(Zero::zero);
+				*deposit = Some(total.saturating_add(new_deposit).saturating_sub(old_deposit));
+			});
+
+			Metadata::<T>::insert(&claim, (metadata.clone(), new_deposit));
+
+			Self::deposit_event(Event::ClaimMetadataUpdated { owner: sender, claim, metadata });
+			Ok(())
+		}
+
+		/// Create several claims in one call.
+		///
+		/// With `fail_fast` the first failure aborts the whole batch. Otherwise every claim is
+		/// tried and a `ClaimBatchItemFailed` event is emitted for each one that fails.
+		#[pallet::call_index(9)]
+		#[pallet::weight(T::WeightInfo::create_claims(
+			claims.len() as u32,
+			claims.iter().map(|claim| claim.len() as u32).sum(),
+		))]
+		pub fn create_claims(
+			origin: OriginFor<T>,
+			claims: BoundedVec<BoundedVec<u8, T::MaxClaimLength>, T::MaxBatchSize>,
+			fail_fast: bool,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			for (index, claim) in claims.into_iter().enumerate() {
+				match with_storage_layer(|| Self::do_create_claim(&sender, &claim, None, None)) {
+					Ok(()) => Self::deposit_event(Event::ClaimCreated(sender.clone(), claim)),
+					Err(error) if fail_fast => return Err(error),
+					Err(error) => Self::deposit_event(Event::ClaimBatchItemFailed {
+						index: index as u32,
+						claim,
+						error,
+					}),
+				}
+			}
+
+			Ok(())
+		}
+
+		/// Revoke several claims in one call, with the same `fail_fast` semantics as `create_claims`.
+		#[pallet::call_index(10)]
+		#[pallet::weight(T::WeightInfo::revoke_claims(
+			claims.len() as u32,
+			claims.iter().map(|claim| claim.len() as u32).sum(),
+		))]
+		pub fn revoke_claims(
+			origin: OriginFor<T>,
+			claims: BoundedVec<BoundedVec<u8, T::MaxClaimLength>, T::MaxBatchSize>,
+			fail_fast: bool,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			for (index, claim) in claims.into_iter().enumerate() {
+				let revoke = || {
+					ensure!(!CoOwners::<T>::contains_key(&claim), Error::<T>::SharedClaim);
+					Self::do_revoke_claim(&sender, &claim)
+				};
+				match with_storage_layer(revoke) {
+					Ok(()) => Self::deposit_event(Event::ClaimRevoked(sender.clone(), claim)),
+					Err(error) if fail_fast => return Err(error),
+					Err(error) => Self::deposit_event(Event::ClaimBatchItemFailed {
+						index: index as u32,
+						claim,
+						error,
+					}),
+				}
+			}
+
+			Ok(())
+		}
+
+		/// Let `delegate` create, revoke or transfer claims on the caller's behalf.
+		///
+		/// Replaces the permissions of an existing delegate.
+		#[pallet::call_index(11)]
+		#[pallet::weight(T::WeightInfo::add_delegate())]
+		pub fn add_delegate(
+			origin: OriginFor<T>,
+			delegate: T::AccountId,
+			permissions: DelegatePermissions,
+		) -> DispatchResult {
+			let owner = ensure_signed(origin)?;
+			ensure!(owner != delegate, Error::<T>::InvalidDelegate);
+
+			Delegates::<T>::insert(&owner, &delegate, permissions.clone());
+
+			Self::deposit_event(Event::DelegateAdded { owner, delegate, permissions });
+			Ok(())
+		}
+
+		/// Withdraw all permissions of `delegate`.
+		#[pallet::call_index(12)]
+		#[pallet::weight(T::WeightInfo::remove_delegate())]
+		pub fn remove_delegate(origin: OriginFor<T>, delegate: T::AccountId) -> DispatchResult {
+			let owner = ensure_signed(origin)?;
+			Delegates::<T>::take(&owner, &delegate).ok_or(Error::<T>::DelegateNotExist)?;
+
+			Self::deposit_event(Event::DelegateRemoved { owner, delegate });
+			Ok(())
+		}
+
+		/// Create a claim owned together with `co_owners`, `threshold` of whom must sign before it
+		/// is final and before it can later be revoked or transferred.
+		///
+		/// The caller must be one of `co_owners`, pays the deposit and signs straight away. If the
+		/// claim is not final within `SigningPeriod` it is pruned.
+		#[pallet::call_index(13)]
+		#[pallet::weight(T::WeightInfo::create_shared_claim(claim.len() as u32, co_owners.len() as u32))]
+		pub fn create_shared_claim(
+			origin: OriginFor<T>,
+			claim: BoundedVec<u8, T::MaxClaimLength>,
+			co_owners: BoundedVec<T::AccountId, T::MaxCoOwners>,
+			threshold: u32,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			ensure!(co_owners.contains(&sender), Error::<T>::NotCoOwner);
+			ensure!(
+				threshold > 0 && threshold as usize <= co_owners.len(),
+				Error::<T>::InvalidThreshold
+			);
+			let mut distinct = co_owners.to_vec();
+			distinct.sort();
+			distinct.dedup();
+			ensure!(distinct.len() == co_owners.len(), Error::<T>::DuplicateCoOwner);
+
+			let deadline =
+				frame_system::Pallet::<T>::block_number().saturating_add(T::SigningPeriod::get());
+			Self::do_create_claim(&sender, &claim, Some(deadline), None)?;
+			CoOwners::<T>::insert(&claim, CoOwnership { owners: co_owners.clone(), threshold });
+			PendingSignatures::<T>::insert(
 				&claim,
-				(recipient.clone(), frame_system::Pallet::<T>::block_number()),
+				PendingSigning { action: SharedAction::Create, signatures: BoundedVec::default(), deadline },
 			);
-			Self::deposit_event(Event::ClaimTransferred { sender: signer, recipient, claim  });
+
+			Self::deposit_event(Event::SharedClaimCreated {
+				owner: sender.clone(),
+				claim: claim.clone(),
+				co_owners,
+				threshold,
+			});
+			Self::do_co_sign_claim(&sender, claim)
+		}
+
+		/// Sign the pending action of a shared claim, carrying it out once `threshold` co-owners
+		/// have signed.
+		///
+		/// Co-owners propose revoking or transferring a shared claim through `revoke_claim` and
+		/// `transfer_claim`.
+		#[pallet::call_index(14)]
+		#[pallet::weight(T::WeightInfo::co_sign_claim(claim.len() as u32, T::MaxCoOwners::get()))]
+		pub fn co_sign_claim(origin: OriginFor<T>, claim: BoundedVec<u8, T::MaxClaimLength>) -> DispatchResult {
+			let signer = ensure_signed(origin)?;
+
+			Self::do_co_sign_claim(&signer, claim)
+		}
+
+		/// Anchor the Merkle root of a batch of `leaf_count` document digests, reserving
+		/// `ClaimDepositBase` until it is removed.
+		///
+		/// See the `merkle` module for how the tree is built. Individual documents are then proven
+		/// through the `verify_inclusion` runtime API.
+		#[pallet::call_index(15)]
+		#[pallet::weight(T::WeightInfo::anchor_root())]
+		pub fn anchor_root(origin: OriginFor<T>, root: hashing::Digest, leaf_count: u32) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			ensure!(leaf_count > 0, Error::<T>::EmptyAnchor);
+			ensure!(!Anchors::<T>::contains_key(root), Error::<T>::RootAlreadyAnchored);
+
+			let deposit = T::ClaimDepositBase::get();
+			T::Currency::reserve(&sender, deposit)?;
+			AnchorDeposits::<T>::insert(root, deposit);
+
+			let anchored_at = frame_system::Pallet::<T>::block_number();
+			Anchors::<T>::insert(root, AnchorInfo { owner: sender.clone(), leaf_count, anchored_at });
+
+			Self::deposit_event(Event::RootAnchored { owner: sender, root, leaf_count });
+			Ok(())
+		}
+
+		/// Remove an anchored Merkle root and refund its deposit.
+		#[pallet::call_index(16)]
+		#[pallet::weight(T::WeightInfo::remove_anchor())]
+		pub fn remove_anchor(origin: OriginFor<T>, root: hashing::Digest) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let anchor = Anchors::<T>::get(root).ok_or(Error::<T>::AnchorNotExist)?;
+			ensure!(anchor.owner == sender, Error::<T>::NotAnchorOwner);
+
+			Anchors::<T>::remove(root);
+			if let Some(deposit) = AnchorDeposits::<T>::take(root) {
+				T::Currency::unreserve(&sender, deposit);
+			}
+
+			Self::deposit_event(Event::AnchorRemoved { owner: sender, root });
 			Ok(())
 		}
 	}
 
-	
+	impl<T: Config> Pallet<T> {
+		/// Check that `signer` may act for `owner`, returning the delegate if it is not the owner itself.
+		fn ensure_can_act(
+			signer: &T::AccountId,
+			owner: &T::AccountId,
+			allowed: impl FnOnce(&DelegatePermissions) -> bool,
+		) -> Result<Option<T::AccountId>, DispatchError> {
+			if signer == owner {
+				return Ok(None)
+			}
+
+			let permissions = Delegates::<T>::get(owner, signer).ok_or(Error::<T>::NotClaimOwner)?;
+			ensure!(allowed(&permissions), Error::<T>::DelegateNotPermitted);
+			Ok(Some(signer.clone()))
+		}
+
+		/// Ask the co-owners of a shared claim to sign `action`, signing it as `proposer`.
+		fn propose_shared_action(
+			proposer: &T::AccountId,
+			claim: BoundedVec<u8, T::MaxClaimLength>,
+			action: SharedAction<T::AccountId>,
+		) -> DispatchResult {
+			let ownership = CoOwners::<T>::get(&claim).ok_or(Error::<T>::ClaimNotShared)?;
+			ensure!(ownership.owners.contains(proposer), Error::<T>::NotCoOwner);
+
+			let now = frame_system::Pallet::<T>::block_number();
+			if let Some(pending) = PendingSignatures::<T>::get(&claim) {
+				ensure!(pending.deadline <= now, Error::<T>::SigningInProgress);
+			}
+			PendingSignatures::<T>::insert(
+				&claim,
+				PendingSigning {
+					action: action.clone(),
+					signatures: BoundedVec::default(),
+					deadline: now.saturating_add(T::SigningPeriod::get()),
+				},
+			);
+
+			Self::deposit_event(Event::SharedActionProposed { proposer: proposer.clone(), claim: claim.clone(), action });
+			Self::do_co_sign_claim(proposer, claim)
+		}
+
+		/// Add the signature of `signer` to the pending action of `claim`, carrying the action out
+		/// if it reaches the threshold.
+		fn do_co_sign_claim(signer: &T::AccountId, claim: BoundedVec<u8, T::MaxClaimLength>) -> DispatchResult {
+			let ownership = CoOwners::<T>::get(&claim).ok_or(Error::<T>::ClaimNotShared)?;
+			ensure!(ownership.owners.contains(signer), Error::<T>::NotCoOwner);
+			let mut pending = PendingSignatures::<T>::get(&claim).ok_or(Error::<T>::SigningNotExist)?;
+			ensure!(
+				frame_system::Pallet::<T>::block_number() < pending.deadline,
+				Error::<T>::SigningExpired
+			);
+			ensure!(!pending.signatures.contains(signer), Error::<T>::AlreadySigned);
+
+			// Bounded by the co-owners, who can each sign once.
+			pending.signatures.try_push(signer.clone()).map_err(|_| Error::<T>::AlreadySigned)?;
+			let signatures = pending.signatures.len() as u32;
+			Self::deposit_event(Event::ClaimCoSigned { signer: signer.clone(), claim: claim.clone(), signatures });
+
+			if signatures < ownership.threshold {
+				PendingSignatures::<T>::insert(&claim, pending);
+				return Ok(())
+			}
+
+			PendingSignatures::<T>::remove(&claim);
+			let mut info = Proofs::<T>::get(&claim).ok_or(Error::<T>::ClaimNotExist)?;
+			match pending.action {
+				SharedAction::Create => {
+					if let Some(deadline) = info.expires_at.take() {
+						Self::unschedule_expiry(&claim, deadline);
+					}
+					let owner = info.owner.clone();
+					Proofs::<T>::insert(&claim, info);
+					Self::deposit_event(Event::ClaimCreated(owner, claim));
+				},
+				SharedAction::Revoke => {
+					Self::do_revoke_claim(&info.owner, &claim)?;
+					Self::deposit_event(Event::ClaimRevoked(info.owner, claim));
+				},
+				SharedAction::Transfer(recipient) => {
+					let offer = ClaimOffer { recipient: recipient.clone(), expires_at: None };
+					PendingOffers::<T>::insert(&claim, offer);
+					Self::deposit_event(Event::ClaimOffered {
+						owner: info.owner,
+						recipient,
+						claim,
+						expires_at: None,
+					});
+				},
+			}
+			Ok(())
+		}
+
+		/// Emit `DelegateActed` if the call was made by a delegate.
+		fn deposit_delegate_event(
+			owner: T::AccountId,
+			delegate: Option<T::AccountId>,
+			claim: BoundedVec<u8, T::MaxClaimLength>,
+		) {
+			if let Some(delegate) = delegate {
+				Self::deposit_event(Event::DelegateActed { owner, delegate, claim });
+			}
+		}
+
+		/// Record `claim` as owned by `owner` at the current block, with the hash algorithm if it is
+		/// a document digest.
+		fn do_create_claim(
+			owner: &T::AccountId,
+			claim: &BoundedVec<u8, T::MaxClaimLength>,
+			expires_at: Option<T::BlockNumber>,
+			algorithm: Option<HashAlgorithm>,
+		) -> DispatchResult {
+			ensure!(!Proofs::<T>::contains_key(claim), Error::<T>::ProofAlreadyExist);
+
+			let current_block = frame_system::Pallet::<T>::block_number();
+			if let Some(expires_at) = expires_at {
+				ensure!(expires_at > current_block, Error::<T>::InvalidExpiry);
+				Self::schedule_expiry(claim, expires_at)?;
+			}
+			Self::add_owned_claim(owner, claim, current_block)?;
+
+			let deposit = Self::claim_deposit(claim.len());
+			T::Currency::reserve(owner, deposit)?;
+			Deposits::<T>::insert(claim, deposit);
+
+			Proofs::<T>::insert(
+				claim,
+				ClaimInfo { owner: owner.clone(), created_at: current_block, expires_at, algorithm },
+			);
+			Ok(())
+		}
+
+		/// Remove `claim` on behalf of `owner` and refund its deposit.
+		fn do_revoke_claim(owner: &T::AccountId, claim: &BoundedVec<u8, T::MaxClaimLength>) -> DispatchResult {
+			let info = Proofs::<T>::get(claim).ok_or(Error::<T>::ClaimNotExist)?;
+			ensure!(&info.owner == owner, Error::<T>::NotClaimOwner);
+
+			Proofs::<T>::remove(claim);
+			PendingOffers::<T>::remove(claim);
+			Metadata::<T>::remove(claim);
+			CoOwners::<T>::remove(claim);
+			PendingSignatures::<T>::remove(claim);
+			Self::remove_owned_claim(owner, claim);
+			if let Some(expires_at) = info.expires_at {
+				Self::unschedule_expiry(claim, expires_at);
+			}
+			if let Some(deposit) = Deposits::<T>::take(claim) {
+				T::Currency::unreserve(owner, deposit);
+			}
+			Ok(())
+		}
+
+		/// Hand `claim` and its deposit over to `recipient` as its sole owner, dropping any pending
+		/// offer.
+		fn do_transfer_claim(
+			claim: &BoundedVec<u8, T::MaxClaimLength>,
+			mut info: ClaimInfo<T::AccountId, T::BlockNumber>,
+			recipient: &T::AccountId,
+		) -> DispatchResult {
+			Self::add_owned_claim(recipient, claim, info.created_at)?;
+			Self::remove_owned_claim(&info.owner, claim);
+			if let Some(deposit) = Deposits::<T>::get(claim) {
+				T::Currency::repatriate_reserved(&info.owner, recipient, deposit, BalanceStatus::Reserved)?;
+			}
+			PendingOffers::<T>::remove(claim);
+			CoOwners::<T>::remove(claim);
+			PendingSignatures::<T>::remove(claim);
+
+			info.owner = recipient.clone();
+			Proofs::<T>::insert(claim, info);
+			Ok(())
+		}
+
+		/// Queue `claim` to be pruned at the start of block `at`.
+		fn schedule_expiry(claim: &BoundedVec<u8, T::MaxClaimLength>, at: T::BlockNumber) -> DispatchResult {
+			ExpiringClaims::<T>::try_mutate(at, |claims| {
+				claims.try_push(claim.clone()).map_err(|_| Error::<T>::TooManyExpiries.into())
+			})
+		}
+
+		/// Take `claim` back out of the pruning queue of block `at`.
+		fn unschedule_expiry(claim: &BoundedVec<u8, T::MaxClaimLength>, at: T::BlockNumber) {
+			ExpiringClaims::<T>::mutate_exists(at, |claims| {
+				if let Some(queued) = claims {
+					queued.retain(|c| c != claim);
+					if queued.is_empty() {
+						*claims = None;
+					}
+				}
+			});
+		}
+
+		/// Remove an expired claim and refund its deposit to the owner.
+		fn expire_claim(claim: BoundedVec<u8, T::MaxClaimLength>) {
+			let info = match Proofs::<T>::take(&claim) {
+				Some(info) => info,
+				None => return,
+			};
+
+			Self::remove_owned_claim(&info.owner, &claim);
+			PendingOffers::<T>::remove(&claim);
+			Metadata::<T>::remove(&claim);
+			CoOwners::<T>::remove(&claim);
+			PendingSignatures::<T>::remove(&claim);
+			if let Some(deposit) = Deposits::<T>::take(&claim) {
+				T::Currency::unreserve(&info.owner, deposit);
+			}
+
+			Self::deposit_event(Event::ClaimExpired { owner: info.owner, claim });
+		}
+
+		/// The deposit reserved for a claim of `len` bytes.
+		pub fn claim_deposit(len: usize) -> BalanceOf<T> {
+			let len: BalanceOf<T> = (len as u32).into();
+			T::ClaimDepositBase::get().saturating_add(T::ClaimDepositPerByte::get().saturating_mul(len))
+		}
+
+		/// Index `claim` under `owner`, failing if the owner is already at `MaxClaimsPerOwner`.
+		fn add_owned_claim(
+			owner: &T::AccountId,
+			claim: &BoundedVec<u8, T::MaxClaimLength>,
+			block_number: T::BlockNumber,
+		) -> DispatchResult {
+			let count = ClaimsCount::<T>::get(owner);
+			ensure!(count < T::MaxClaimsPerOwner::get(), Error::<T>::TooManyClaims);
+
+			ClaimsByOwner::<T>::insert(owner, claim, block_number);
+			ClaimsCount::<T>::insert(owner, count + 1);
+			Ok(())
+		}
+
+		/// Drop `claim` from the index of `owner`.
+		fn remove_owned_claim(owner: &T::AccountId, claim: &BoundedVec<u8, T::MaxClaimLength>) {
+			ClaimsByOwner::<T>::remove(owner, claim);
+
+			let count = ClaimsCount::<T>::get(owner).saturating_sub(1);
+			if count == 0 {
+				ClaimsCount::<T>::remove(owner);
+			} else {
+				ClaimsCount::<T>::insert(owner, count);
+			}
+		}
+
+		/// Return at most `limit` claims of `owner` together with the block they were recorded at.
+		///
+		/// Pages are resumed by passing the last claim of the previous page as `start_after`.
+		/// Used by the `PoeApi` runtime API.
+		pub fn claims_of(
+			owner: T::AccountId,
+			start_after: Option<Vec<u8>>,
+			limit: u32,
+		) -> Vec<(Vec<u8>, T::BlockNumber)> {
+			let limit = limit.min(T::MaxClaimsPerOwner::get()) as usize;
+
+			let iter = match start_after {
+				Some(cursor) => {
+					let cursor = match BoundedVec::<u8, T::MaxClaimLength>::try_from(cursor) {
+						Ok(cursor) => cursor,
+						// A cursor that can't be a claim can't be in the index either.
+						Err(_) => return Vec::new(),
+					};
+					let raw_key = ClaimsByOwner::<T>::hashed_key_for(&owner, &cursor);
+					ClaimsByOwner::<T>::iter_prefix_from(&owner, raw_key)
+				},
+				None => ClaimsByOwner::<T>::iter_prefix(&owner),
+			};
+
+			iter.take(limit).map(|(claim, block_number)| (claim.into_inner(), block_number)).collect()
+		}
+
+		/// Return a claim together with its metadata, if it exists.
+		///
+		/// Used by the `PoeApi` runtime API.
+		pub fn claim_record(claim: Vec<u8>) -> Option<ClaimRecord<T::AccountId, T::BlockNumber>> {
+			let claim = BoundedVec::<u8, T::MaxClaimLength>::try_from(claim).ok()?;
+			let info = Proofs::<T>::get(&claim)?;
+
+			let record = match Metadata::<T>::get(&claim) {
+				Some((metadata, _)) => ClaimRecord {
+					info,
+					content_type: metadata.content_type.into_inner(),
+					label: metadata.label.into_inner(),
+					uri: metadata.uri.map(|uri| uri.into_inner()),
+					parent: metadata.parent.map(|parent| parent.into_inner()),
+				},
+				None => ClaimRecord {
+					info,
+					content_type: Vec::new(),
+					label: Vec::new(),
+					uri: None,
+					parent: None,
+				},
+			};
+			Some(record)
+		}
+
+		/// Return the co-owners of a shared claim and the signatures collected for its pending
+		/// action, or `None` if the claim is not shared.
+		///
+		/// Used by the `PoeApi` runtime API.
+		pub fn signing_status(claim: Vec<u8>) -> Option<SigningStatus<T::AccountId, T::BlockNumber>> {
+			let claim = BoundedVec::<u8, T::MaxClaimLength>::try_from(claim).ok()?;
+			let ownership = CoOwners::<T>::get(&claim)?;
+			let pending = PendingSignatures::<T>::get(&claim);
+
+			Some(SigningStatus {
+				co_owners: ownership.owners.into_inner(),
+				threshold: ownership.threshold,
+				action: pending.as_ref().map(|pending| pending.action.clone()),
+				deadline: pending.as_ref().map(|pending| pending.deadline),
+				signatures: pending.map(|pending| pending.signatures.into_inner()).unwrap_or_default(),
+			})
+		}
+
+		/// Return the anchor of `root` if `proof` shows that `leaf` is one of its leaves.
+		///
+		/// Used by the `PoeApi` runtime API.
+		pub fn verify_inclusion(
+			root: hashing::Digest,
+			leaf: hashing::Digest,
+			proof: Vec<hashing::Digest>,
+		) -> Option<AnchorInfo<T::AccountId, T::BlockNumber>> {
+			let anchor = Anchors::<T>::get(root)?;
+			if proof.len() > merkle::max_proof_length(anchor.leaf_count) ||
+				merkle::root_from_proof(&leaf, &proof) != root
+			{
+				return None
+			}
+			Some(anchor)
+		}
+
+		/// Hash `document` with `algorithm` and return the proof recorded for its digest, if any.
+		///
+		/// Only digests created with the same algorithm through `create_claim_hash` match, not raw
+		/// claims that happen to hold the same bytes. Used by the `PoeApi` runtime API.
+		pub fn verify_document(
+			algorithm: HashAlgorithm,
+			document: &[u8],
+		) -> Option<ClaimInfo<T::AccountId, T::BlockNumber>> {
+			let digest = T::ClaimHasher::hash(algorithm, document);
+			let claim = BoundedVec::<u8, T::MaxClaimLength>::try_from(digest.to_vec()).ok()?;
+			Proofs::<T>::get(&claim).filter(|info| info.algorithm == Some(algorithm))
+		}
+	}
 }
\ No newline at end of file
@@ -0,0 +1,39 @@
+//! Merkle trees of document digests, whose roots are anchored on chain by `anchor_root`.
+//!
+//! Leaves are hashed as `blake2_256(0x00 ++ leaf)` and inner nodes as
+//! `blake2_256(0x01 ++ min(a, b) ++ max(a, b))`. Sorting each pair means a proof is just the
+//! siblings from the leaf up, without left/right flags. A level with an odd number of nodes passes
+//! its last node up unchanged.
+
+use crate::hashing::{Digest, DIGEST_LENGTH};
+
+const LEAF_PREFIX: u8 = 0;
+const NODE_PREFIX: u8 = 1;
+
+/// The tree node of a leaf.
+pub fn leaf_hash(leaf: &Digest) -> Digest {
+	let mut data = [0u8; 1 + DIGEST_LENGTH];
+	data[0] = LEAF_PREFIX;
+	data[1..].copy_from_slice(leaf);
+	sp_io::hashing::blake2_256(&data)
+}
+
+/// The parent of two sibling nodes.
+pub fn node_hash(a: &Digest, b: &Digest) -> Digest {
+	let (left, right) = if a <= b { (a, b) } else { (b, a) };
+	let mut data = [0u8; 1 + 2 * DIGEST_LENGTH];
+	data[0] = NODE_PREFIX;
+	data[1..1 + DIGEST_LENGTH].copy_from_slice(left);
+	data[1 + DIGEST_LENGTH..].copy_from_slice(right);
+	sp_io::hashing::blake2_256(&data)
+}
+
+/// The root reached by folding `proof` into `leaf`.
+pub fn root_from_proof(leaf: &Digest, proof: &[Digest]) -> Digest {
+	proof.iter().fold(leaf_hash(leaf), |node, sibling| node_hash(&node, sibling))
+}
+
+/// The most siblings a proof can hold in a tree of `leaf_count` leaves.
+pub fn max_proof_length(leaf_count: u32) -> usize {
+	(u32::BITS - leaf_count.saturating_sub(1).leading_zeros()) as usize
+}
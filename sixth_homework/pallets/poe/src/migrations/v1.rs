@@ -0,0 +1,32 @@
+use frame_support::pallet_prelude::*;
+use frame_support::traits::GetStorageVersion;
+use frame_support::weights::Weight;
+
+use crate::{ClaimInfo, ClaimsByOwner, ClaimsCount, Config, Pallet, Proofs};
+
+/// Turn the `(owner, block_number)` tuples stored by version 0 into raw claims that never expire,
+/// and add every claim missing from `ClaimsByOwner` to it.
+pub fn migrate<T: Config>() -> Weight {
+	let on_chain_version = Pallet::<T>::on_chain_storage_version();
+
+	if on_chain_version != 0 {
+		return T::DbWeight::get().reads(1);
+	}
+
+	let mut translated = 0u64;
+	let mut indexed = 0u64;
+	Proofs::<T>::translate::<(T::AccountId, T::BlockNumber), _>(|claim, (owner, created_at)| {
+		translated += 1;
+		// Claims created before the index existed are not in it yet.
+		if !ClaimsByOwner::<T>::contains_key(&owner, &claim) {
+			indexed += 1;
+			ClaimsByOwner::<T>::insert(&owner, &claim, created_at);
+			ClaimsCount::<T>::mutate(&owner, |count| *count = count.saturating_add(1));
+		}
+		Some(ClaimInfo { owner, created_at, expires_at: None, algorithm: None })
+	});
+
+	StorageVersion::new(1).put::<Pallet<T>>();
+
+	T::DbWeight::get().reads_writes(2 * translated + indexed + 1, translated + 2 * indexed + 1)
+}
@@ -17,6 +17,7 @@ frame_support::construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic,
 	{
 		System: frame_system,
+		Balances: pallet_balances,
 		PoeModule: pallet_poe,
 	}
 );
@@ -39,7 +40,7 @@ impl frame_system::Config for Test {
 	type BlockHashCount = ConstU64<250>;
 	type Version = ();
 	type PalletInfo = PalletInfo;
-	type AccountData = ();
+	type AccountData = pallet_balances::AccountData<u64>;
 	type OnNewAccount = ();
 	type OnKilledAccount = ();
 	type SystemWeightInfo = ();
@@ -48,13 +49,39 @@ impl frame_system::Config for Test {
 	type MaxConsumers = frame_support::traits::ConstU32<16>;
 }
 
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type Balance = u64;
+	type RuntimeEvent = RuntimeEvent;
+	type DustRemoval = ();
+	type ExistentialDeposit = ConstU64<1>;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
 impl pallet_poe::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
-	type MaxClaimLength = ConstU32<10>;
+	type MaxClaimLength = ConstU32<32>;
+	type MaxClaimsPerOwner = ConstU32<3>;
+	type ClaimHasher = pallet_poe::SpIoHasher;
+	type Currency = Balances;
+	type ClaimDepositBase = ConstU64<10>;
+	type ClaimDepositPerByte = ConstU64<1>;
+	type MaxExpiriesPerBlock = ConstU32<2>;
+	type MaxMetadataLength = ConstU32<16>;
+	type MaxBatchSize = ConstU32<3>;
+	type MaxCoOwners = ConstU32<3>;
+	type SigningPeriod = ConstU64<10>;
 	type WeightInfo = ();
 }
 
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
-	frame_system::GenesisConfig::default().build_storage::<Test>().unwrap().into()
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> { balances: vec![(1, 1_000), (2, 1_000), (3, 1_000)] }
+		.assimilate_storage(&mut t)
+		.unwrap();
+	t.into()
 }
\ No newline at end of file
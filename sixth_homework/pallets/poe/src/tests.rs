@@ -7,7 +7,7 @@ const ACCOUNT_ID_1: u64 = 1;
 const ACCOUNT_ID_2: u64 = 2;
 const ACCOUNT_ID_3: u64 = 3;
 
-fn new_claim() -> BoundedVec<u8, ConstU32<10>> {
+fn new_claim() -> BoundedVec<u8, ConstU32<32>> {
 	return BoundedVec::try_from(vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10]).unwrap()
 }
 
@@ -22,12 +22,12 @@ new_test_ext().execute_with(|| {
 
    let bound_claim = BoundedVec::try_from(vec![0,1]).unwrap();
 
-    assert_ok!(PoeModule::create_claim(RuntimeOrigin::signed(1), bound_claim.clone()));
+    assert_ok!(PoeModule::create_claim(RuntimeOrigin::signed(1), bound_claim.clone(), None, None));
     
   
     assert_eq!(
         Proofs::<Test>::get(&bound_claim),
-        Some((1,frame_system::Pallet::<Test>::block_number())),
+        Some(ClaimInfo { owner: 1, created_at: frame_system::Pallet::<Test>::block_number(), expires_at: None, algorithm: None }),
     )
 })
 }
@@ -36,12 +36,12 @@ new_test_ext().execute_with(|| {
 fn create_claim_failed_when_claim_already_exist() {
     new_test_ext().execute_with(|| {
        // let bound_claim = BoundedVec::try_from(vec![0, 1]).unwrap();
-        let bound_claim: BoundedVec<u8, ConstU32<10>> = BoundedVec::try_from(vec![0,1]).unwrap();
+        let bound_claim: BoundedVec<u8, ConstU32<32>> = BoundedVec::try_from(vec![0,1]).unwrap();
 
-        let _ = PoeModule::create_claim(RuntimeOrigin::signed(1), bound_claim.clone());
+        let _ = PoeModule::create_claim(RuntimeOrigin::signed(1), bound_claim.clone(), None, None);
 
         assert_noop!(
-            PoeModule::create_claim(RuntimeOrigin::signed(1), bound_claim.clone()),
+            PoeModule::create_claim(RuntimeOrigin::signed(1), bound_claim.clone(), None, None),
             Error::<Test>::ProofAlreadyExist
         );
 
@@ -51,9 +51,9 @@ fn create_claim_failed_when_claim_already_exist() {
 #[test]
 fn revoke_claim_works() {
     new_test_ext().execute_with(|| {
-        let bound_claim: BoundedVec<u8, ConstU32<10>> = BoundedVec::try_from(vec![0,1]).unwrap();
+        let bound_claim: BoundedVec<u8, ConstU32<32>> = BoundedVec::try_from(vec![0,1]).unwrap();
 
-        let _ = PoeModule::create_claim(RuntimeOrigin::signed(1), bound_claim.clone());
+        let _ = PoeModule::create_claim(RuntimeOrigin::signed(1), bound_claim.clone(), None, None);
 
         assert_ok!(PoeModule::revoke_claim(RuntimeOrigin::signed(1), bound_claim.clone()));
     })
@@ -63,7 +63,7 @@ fn revoke_claim_works() {
 #[test]
 fn revoke_claim_faild_when_claim_not_exist() {
     new_test_ext().execute_with(|| {
-        let bound_claim: BoundedVec<u8, ConstU32<10>> = BoundedVec::try_from(vec![0,1]).unwrap();
+        let bound_claim: BoundedVec<u8, ConstU32<32>> = BoundedVec::try_from(vec![0,1]).unwrap();
         assert_noop!(
             PoeModule::revoke_claim(RuntimeOrigin::signed(1), bound_claim.clone()),
             Error::<Test>::ClaimNotExist
@@ -74,8 +74,8 @@ fn revoke_claim_faild_when_claim_not_exist() {
 #[test]
 fn revoke_claim_faild_with_wrong_owner() {
     new_test_ext().execute_with(|| {
-        let bound_claim: BoundedVec<u8, ConstU32<10>> = BoundedVec::try_from(vec![0,1]).unwrap();
-        let _ = PoeModule::create_claim(RuntimeOrigin::signed(1), claim.clone());
+        let bound_claim: BoundedVec<u8, ConstU32<32>> = BoundedVec::try_from(vec![0,1]).unwrap();
+        let _ = PoeModule::create_claim(RuntimeOrigin::signed(1), bound_claim.clone(), None, None);
 
         assert_noop!(
             PoeModule::revoke_claim(RuntimeOrigin::signed(2), bound_claim.clone()),
@@ -88,15 +88,29 @@ fn revoke_claim_faild_with_wrong_owner() {
 #[test]
 fn transfer_claim_works() {
 	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
 		let claim = new_claim();
 		let signer = RuntimeOrigin::signed(ACCOUNT_ID_1);
 
 		// 创建存证
-		assert_ok!(PoeModule::create_claim(signer.clone(), claim.clone()));
+		assert_ok!(PoeModule::create_claim(signer.clone(), claim.clone(), None, None));
 		// 转移存证
 		assert_ok!(PoeModule::transfer_claim(signer, ACCOUNT_ID_2, claim.clone()));
+		// 接收方接受之前存证不变
+		assert_eq!(Proofs::<Test>::get(&claim).unwrap().owner, ACCOUNT_ID_1);
+		assert_eq!(ClaimsByOwner::<Test>::get(ACCOUNT_ID_2, &claim), None);
+		assert_eq!(Balances::reserved_balance(ACCOUNT_ID_2), 0);
+		assert_eq!(
+			PendingOffers::<Test>::get(&claim),
+			Some(ClaimOffer { recipient: ACCOUNT_ID_2, expires_at: None })
+		);
+		System::assert_last_event(
+			Event::ClaimOffered { owner: ACCOUNT_ID_1, recipient: ACCOUNT_ID_2, claim: claim.clone(), expires_at: None }.into(),
+		);
 		// 检查存证
-		//assert_eq!(PoeModule::proofs(&claim), Some((ACCOUNT_ID_2, System::block_number())));
+		assert_ok!(PoeModule::accept_claim(RuntimeOrigin::signed(ACCOUNT_ID_2), claim.clone()));
+		assert_eq!(Proofs::<Test>::get(&claim).unwrap().owner, ACCOUNT_ID_2);
+		assert_eq!(PendingOffers::<Test>::get(&claim), None);
 	})
 }
 
@@ -124,14 +138,14 @@ fn transfer_claim_failed_when_not_owner() {
 		let signer_2 = RuntimeOrigin::signed(ACCOUNT_ID_2);
 
 		// 创建存证
-		assert_ok!(PoeModule::create_claim(signer, claim.clone()));
+		assert_ok!(PoeModule::create_claim(signer, claim.clone(), None, None));
 		// 转移存证
 		assert_noop!(
 			PoeModule::transfer_claim(signer_2, ACCOUNT_ID_3, claim.clone()),
 			Error::<Test>::NotClaimOwner
 		);
 		// 检查存证
-		assert_eq!(PoeModule::proofs(&claim), Some((ACCOUNT_ID_1, System::block_number())));
+		assert_eq!(PoeModule::proofs(&claim), Some(ClaimInfo { owner: ACCOUNT_ID_1, created_at: System::block_number(), expires_at: None, algorithm: None }));
 	})
 }
 
@@ -142,13 +156,911 @@ fn transfer_claim_failed_when_transfer_to_owner() {
 		let signer = RuntimeOrigin::signed(ACCOUNT_ID_1);
 
 		// 创建存证
-		assert_ok!(PoeModule::create_claim(signer.clone(), claim.clone()));
+		assert_ok!(PoeModule::create_claim(signer.clone(), claim.clone(), None, None));
 		// 转移存证
 		assert_noop!(
 			PoeModule::transfer_claim(signer, ACCOUNT_ID_1, claim.clone()),
 			Error::<Test>::TransferToOwner
 		);
 		// 检查存证
-		assert_eq!(PoeModule::proofs(&claim), Some((ACCOUNT_ID_1, System::block_number())));
+		assert_eq!(PoeModule::proofs(&claim), Some(ClaimInfo { owner: ACCOUNT_ID_1, created_at: System::block_number(), expires_at: None, algorithm: None }));
+	})
+}
+
+#[test]
+fn claims_by_owner_follows_create_revoke_and_transfer() {
+	new_test_ext().execute_with(|| {
+		let claim = new_claim();
+		let signer = RuntimeOrigin::signed(ACCOUNT_ID_1);
+
+		assert_ok!(PoeModule::create_claim(signer.clone(), claim.clone(), None, None));
+		assert_eq!(ClaimsByOwner::<Test>::get(ACCOUNT_ID_1, &claim), Some(System::block_number()));
+		assert_eq!(ClaimsCount::<Test>::get(ACCOUNT_ID_1), 1);
+
+		assert_ok!(PoeModule::transfer_claim(signer, ACCOUNT_ID_2, claim.clone()));
+		assert_eq!(ClaimsCount::<Test>::get(ACCOUNT_ID_1), 1);
+		assert_ok!(PoeModule::accept_claim(RuntimeOrigin::signed(ACCOUNT_ID_2), claim.clone()));
+		assert_eq!(ClaimsByOwner::<Test>::get(ACCOUNT_ID_1, &claim), None);
+		assert_eq!(ClaimsCount::<Test>::get(ACCOUNT_ID_1), 0);
+		assert_eq!(ClaimsByOwner::<Test>::get(ACCOUNT_ID_2, &claim), Some(System::block_number()));
+		assert_eq!(ClaimsCount::<Test>::get(ACCOUNT_ID_2), 1);
+
+		assert_ok!(PoeModule::revoke_claim(RuntimeOrigin::signed(ACCOUNT_ID_2), claim.clone()));
+		assert_eq!(ClaimsByOwner::<Test>::get(ACCOUNT_ID_2, &claim), None);
+		assert_eq!(ClaimsCount::<Test>::get(ACCOUNT_ID_2), 0);
+	})
+}
+
+#[test]
+fn create_claim_failed_when_too_many_claims() {
+	new_test_ext().execute_with(|| {
+		let signer = RuntimeOrigin::signed(ACCOUNT_ID_1);
+
+		for i in 0..3u8 {
+			assert_ok!(PoeModule::create_claim(signer.clone(), BoundedVec::try_from(vec![i]).unwrap(), None, None));
+		}
+		assert_noop!(
+			PoeModule::create_claim(signer, BoundedVec::try_from(vec![3]).unwrap(), None, None),
+			Error::<Test>::TooManyClaims
+		);
+	})
+}
+
+#[test]
+fn claims_of_returns_pages() {
+	new_test_ext().execute_with(|| {
+		let signer = RuntimeOrigin::signed(ACCOUNT_ID_1);
+
+		for i in 0..3u8 {
+			assert_ok!(PoeModule::create_claim(signer.clone(), BoundedVec::try_from(vec![i]).unwrap(), None, None));
+		}
+
+		let first = PoeModule::claims_of(ACCOUNT_ID_1, None, 2);
+		assert_eq!(first.len(), 2);
+
+		let cursor = first.last().map(|(claim, _)| claim.clone());
+		let second = PoeModule::claims_of(ACCOUNT_ID_1, cursor, 2);
+		assert_eq!(second.len(), 1);
+
+		let mut all: Vec<Vec<u8>> = first.into_iter().chain(second).map(|(claim, _)| claim).collect();
+		all.sort();
+		assert_eq!(all, vec![vec![0], vec![1], vec![2]]);
+		assert!(PoeModule::claims_of(ACCOUNT_ID_2, None, 2).is_empty());
+	})
+}
+
+#[test]
+fn create_claim_hash_works() {
+	new_test_ext().execute_with(|| {
+		let document = b"notarised contract".to_vec();
+		let digest = SpIoHasher::hash(HashAlgorithm::Sha2_256, &document);
+
+		assert_ok!(PoeModule::create_claim_hash(
+			RuntimeOrigin::signed(ACCOUNT_ID_1),
+			HashAlgorithm::Sha2_256,
+			digest,
+			None
+		));
+
+		let claim: BoundedVec<u8, ConstU32<32>> = BoundedVec::try_from(digest.to_vec()).unwrap();
+		assert_eq!(Proofs::<Test>::get(&claim), Some(ClaimInfo { owner: ACCOUNT_ID_1, created_at: System::block_number(), expires_at: None, algorithm: Some(HashAlgorithm::Sha2_256) }));
+		assert_noop!(
+			PoeModule::create_claim_hash(
+				RuntimeOrigin::signed(ACCOUNT_ID_2),
+				HashAlgorithm::Sha2_256,
+				digest,
+				None
+			),
+			Error::<Test>::ProofAlreadyExist
+		);
+	})
+}
+
+#[test]
+fn verify_document_matches_algorithm() {
+	new_test_ext().execute_with(|| {
+		let document = b"notarised contract".to_vec();
+		let digest = SpIoHasher::hash(HashAlgorithm::Keccak256, &document);
+
+		assert_ok!(PoeModule::create_claim_hash(
+			RuntimeOrigin::signed(ACCOUNT_ID_1),
+			HashAlgorithm::Keccak256,
+			digest,
+			None
+		));
+
+		assert_eq!(
+			PoeModule::verify_document(HashAlgorithm::Keccak256, &document),
+			Some(ClaimInfo { owner: ACCOUNT_ID_1, created_at: System::block_number(), expires_at: None, algorithm: Some(HashAlgorithm::Keccak256) })
+		);
+		assert_eq!(PoeModule::verify_document(HashAlgorithm::Blake2_256, &document), None);
+		assert_eq!(PoeModule::verify_document(HashAlgorithm::Keccak256, b"forged"), None);
+	})
+}
+
+#[test]
+fn verify_document_ignores_raw_claims() {
+	new_test_ext().execute_with(|| {
+		let document = b"notarised contract".to_vec();
+		let digest = SpIoHasher::hash(HashAlgorithm::Blake2_256, &document);
+		let claim: BoundedVec<u8, ConstU32<32>> = BoundedVec::try_from(digest.to_vec()).unwrap();
+
+		assert_ok!(PoeModule::create_claim(RuntimeOrigin::signed(ACCOUNT_ID_1), claim, None, None));
+
+		assert_eq!(PoeModule::verify_document(HashAlgorithm::Blake2_256, &document), None);
+	})
+}
+
+#[test]
+fn claim_deposit_is_reserved_moved_and_refunded() {
+	new_test_ext().execute_with(|| {
+		let claim = new_claim();
+		let deposit = PoeModule::claim_deposit(claim.len());
+		assert_eq!(deposit, 10 + 10);
+
+		assert_ok!(PoeModule::create_claim(RuntimeOrigin::signed(ACCOUNT_ID_1), claim.clone(), None, None));
+		assert_eq!(Balances::reserved_balance(ACCOUNT_ID_1), deposit);
+		assert_eq!(Deposits::<Test>::get(&claim), Some(deposit));
+
+		assert_ok!(PoeModule::transfer_claim(RuntimeOrigin::signed(ACCOUNT_ID_1), ACCOUNT_ID_2, claim.clone()));
+		assert_ok!(PoeModule::accept_claim(RuntimeOrigin::signed(ACCOUNT_ID_2), claim.clone()));
+		assert_eq!(Balances::reserved_balance(ACCOUNT_ID_1), 0);
+		assert_eq!(Balances::reserved_balance(ACCOUNT_ID_2), deposit);
+		assert_eq!(Balances::free_balance(ACCOUNT_ID_1), 1_000 - deposit);
+
+		assert_ok!(PoeModule::revoke_claim(RuntimeOrigin::signed(ACCOUNT_ID_2), claim.clone()));
+		assert_eq!(Balances::reserved_balance(ACCOUNT_ID_2), 0);
+		assert_eq!(Balances::free_balance(ACCOUNT_ID_2), 1_000 + deposit);
+		assert_eq!(Deposits::<Test>::get(&claim), None);
+	})
+}
+
+#[test]
+fn create_claim_failed_without_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			PoeModule::create_claim(RuntimeOrigin::signed(4), new_claim(), None, None),
+			pallet_balances::Error::<Test>::InsufficientBalance
+		);
+	})
+}
+
+#[test]
+fn create_claim_failed_with_past_expiry() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(5);
+		assert_noop!(
+			PoeModule::create_claim(RuntimeOrigin::signed(ACCOUNT_ID_1), new_claim(), Some(5), None),
+			Error::<Test>::InvalidExpiry
+		);
+	})
+}
+
+#[test]
+fn expired_claim_is_pruned_and_refunded() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let claim = new_claim();
+
+		assert_ok!(PoeModule::create_claim(RuntimeOrigin::signed(ACCOUNT_ID_1), claim.clone(), Some(3), None));
+		assert_eq!(ExpiringClaims::<Test>::get(3).into_inner(), vec![claim.clone()]);
+
+		PoeModule::on_initialize(2);
+		assert!(Proofs::<Test>::contains_key(&claim));
+
+		PoeModule::on_initialize(3);
+		assert_eq!(Proofs::<Test>::get(&claim), None);
+		assert_eq!(ClaimsCount::<Test>::get(ACCOUNT_ID_1), 0);
+		assert_eq!(Balances::reserved_balance(ACCOUNT_ID_1), 0);
+		assert!(!ExpiringClaims::<Test>::contains_key(3));
+		System::assert_last_event(
+			Event::ClaimExpired { owner: ACCOUNT_ID_1, claim }.into(),
+		);
+	})
+}
+
+#[test]
+fn too_many_claims_expiring_in_one_block() {
+	new_test_ext().execute_with(|| {
+		let signer = RuntimeOrigin::signed(ACCOUNT_ID_1);
+
+		for i in 0..2u8 {
+			assert_ok!(PoeModule::create_claim(signer.clone(), BoundedVec::try_from(vec![i]).unwrap(), Some(10), None));
+		}
+		assert_noop!(
+			PoeModule::create_claim(signer, BoundedVec::try_from(vec![2]).unwrap(), Some(10), None),
+			Error::<Test>::TooManyExpiries
+		);
+	})
+}
+
+#[test]
+fn renew_claim_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let claim = new_claim();
+		let signer = RuntimeOrigin::signed(ACCOUNT_ID_1);
+
+		assert_ok!(PoeModule::create_claim(signer.clone(), claim.clone(), Some(3), None));
+		assert_noop!(
+			PoeModule::renew_claim(signer.clone(), claim.clone(), Some(2)),
+			Error::<Test>::InvalidExpiry
+		);
+		assert_noop!(
+			PoeModule::renew_claim(RuntimeOrigin::signed(ACCOUNT_ID_2), claim.clone(), Some(5)),
+			Error::<Test>::NotClaimOwner
+		);
+
+		assert_ok!(PoeModule::renew_claim(signer.clone(), claim.clone(), Some(5)));
+		assert!(!ExpiringClaims::<Test>::contains_key(3));
+		assert_eq!(ExpiringClaims::<Test>::get(5).into_inner(), vec![claim.clone()]);
+
+		PoeModule::on_initialize(3);
+		assert!(Proofs::<Test>::contains_key(&claim));
+
+		// Dropping the expiry keeps the claim forever.
+		assert_ok!(PoeModule::renew_claim(signer.clone(), claim.clone(), None));
+		assert!(!ExpiringClaims::<Test>::contains_key(5));
+		assert_noop!(
+			PoeModule::renew_claim(signer, claim, None),
+			Error::<Test>::InvalidExpiry
+		);
+	})
+}
+
+#[test]
+fn renew_claim_sets_an_expiry_on_a_claim_kept_forever() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(3);
+		let claim = new_claim();
+		let signer = RuntimeOrigin::signed(ACCOUNT_ID_1);
+
+		assert_ok!(PoeModule::create_claim(signer.clone(), claim.clone(), None, None));
+		assert_noop!(
+			PoeModule::renew_claim(signer.clone(), claim.clone(), Some(3)),
+			Error::<Test>::InvalidExpiry
+		);
+
+		assert_ok!(PoeModule::renew_claim(signer, claim.clone(), Some(6)));
+		assert_eq!(Proofs::<Test>::get(&claim).and_then(|info| info.expires_at), Some(6));
+		assert_eq!(ExpiringClaims::<Test>::get(6).into_inner(), vec![claim.clone()]);
+
+		PoeModule::on_initialize(6);
+		assert!(!Proofs::<Test>::contains_key(&claim));
+		assert_eq!(Balances::reserved_balance(ACCOUNT_ID_1), 0);
+	})
+}
+
+#[test]
+fn revoke_claim_cancels_expiry() {
+	new_test_ext().execute_with(|| {
+		let claim = new_claim();
+		let signer = RuntimeOrigin::signed(ACCOUNT_ID_1);
+
+		assert_ok!(PoeModule::create_claim(signer.clone(), claim.clone(), Some(10), None));
+		assert_ok!(PoeModule::revoke_claim(signer, claim));
+		assert!(!ExpiringClaims::<Test>::contains_key(10));
+	})
+}
+
+#[test]
+fn migrate_to_v1_works() {
+	new_test_ext().execute_with(|| {
+		let claim = new_claim();
+		let indexed: BoundedVec<u8, ConstU32<32>> = BoundedVec::try_from(vec![1]).unwrap();
+		frame_support::storage::unhashed::put(
+			&Proofs::<Test>::hashed_key_for(&claim),
+			&(ACCOUNT_ID_1, 7u64),
+		);
+		// Claims created after the owner index was introduced are already in it.
+		frame_support::storage::unhashed::put(
+			&Proofs::<Test>::hashed_key_for(&indexed),
+			&(ACCOUNT_ID_1, 8u64),
+		);
+		ClaimsByOwner::<Test>::insert(ACCOUNT_ID_1, &indexed, 8);
+		ClaimsCount::<Test>::insert(ACCOUNT_ID_1, 1);
+
+		crate::migrations::v1::migrate::<Test>();
+
+		assert_eq!(
+			Proofs::<Test>::get(&claim),
+			Some(ClaimInfo { owner: ACCOUNT_ID_1, created_at: 7, expires_at: None, algorithm: None })
+		);
+		assert_eq!(ClaimsByOwner::<Test>::get(ACCOUNT_ID_1, &claim), Some(7));
+		assert_eq!(ClaimsByOwner::<Test>::get(ACCOUNT_ID_1, &indexed), Some(8));
+		assert_eq!(ClaimsCount::<Test>::get(ACCOUNT_ID_1), 2);
+		assert_eq!(PoeModule::on_chain_storage_version(), 1);
+	})
+}
+
+#[test]
+fn offer_and_accept_claim_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let claim = new_claim();
+		let owner = RuntimeOrigin::signed(ACCOUNT_ID_1);
+
+		assert_ok!(PoeModule::create_claim(owner.clone(), claim.clone(), None, None));
+		assert_ok!(PoeModule::offer_claim(owner, claim.clone(), ACCOUNT_ID_2, Some(5)));
+		// Ownership doesn't move until the recipient accepts.
+		assert_eq!(Proofs::<Test>::get(&claim).map(|info| info.owner), Some(ACCOUNT_ID_1));
+
+		assert_noop!(
+			PoeModule::accept_claim(RuntimeOrigin::signed(ACCOUNT_ID_3), claim.clone()),
+			Error::<Test>::NotOfferRecipient
+		);
+		assert_ok!(PoeModule::accept_claim(RuntimeOrigin::signed(ACCOUNT_ID_2), claim.clone()));
+
+		assert_eq!(Proofs::<Test>::get(&claim).map(|info| info.owner), Some(ACCOUNT_ID_2));
+		assert_eq!(Balances::reserved_balance(ACCOUNT_ID_2), PoeModule::claim_deposit(claim.len()));
+		assert!(!PendingOffers::<Test>::contains_key(&claim));
+		System::assert_last_event(
+			Event::ClaimOfferAccepted { sender: ACCOUNT_ID_1, recipient: ACCOUNT_ID_2, claim }.into(),
+		);
+	})
+}
+
+#[test]
+fn accept_claim_failed_when_offer_expired() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let claim = new_claim();
+		let owner = RuntimeOrigin::signed(ACCOUNT_ID_1);
+
+		assert_ok!(PoeModule::create_claim(owner.clone(), claim.clone(), None, None));
+		assert_ok!(PoeModule::offer_claim(owner, claim.clone(), ACCOUNT_ID_2, Some(3)));
+
+		System::set_block_number(3);
+		assert_noop!(
+			PoeModule::accept_claim(RuntimeOrigin::signed(ACCOUNT_ID_2), claim),
+			Error::<Test>::OfferExpired
+		);
+	})
+}
+
+#[test]
+fn cancel_offer_works() {
+	new_test_ext().execute_with(|| {
+		let claim = new_claim();
+		let owner = RuntimeOrigin::signed(ACCOUNT_ID_1);
+
+		assert_ok!(PoeModule::create_claim(owner.clone(), claim.clone(), None, None));
+		assert_noop!(
+			PoeModule::cancel_offer(owner.clone(), claim.clone()),
+			Error::<Test>::OfferNotExist
+		);
+		assert_ok!(PoeModule::offer_claim(owner.clone(), claim.clone(), ACCOUNT_ID_2, None));
+		assert_noop!(
+			PoeModule::cancel_offer(RuntimeOrigin::signed(ACCOUNT_ID_2), claim.clone()),
+			Error::<Test>::NotClaimOwner
+		);
+		assert_ok!(PoeModule::cancel_offer(owner, claim.clone()));
+
+		assert_noop!(
+			PoeModule::accept_claim(RuntimeOrigin::signed(ACCOUNT_ID_2), claim),
+			Error::<Test>::OfferNotExist
+		);
+	})
+}
+
+fn metadata(label: &[u8], parent: Option<BoundedVec<u8, ConstU32<32>>>) -> ClaimMetadata<Test> {
+	ClaimMetadata {
+		content_type: BoundedVec::try_from(b"text/plain".to_vec()).unwrap(),
+		label: BoundedVec::try_from(label.to_vec()).unwrap(),
+		uri: None,
+		parent,
+	}
+}
+
+#[test]
+fn update_claim_metadata_works() {
+	new_test_ext().execute_with(|| {
+		let claim = new_claim();
+		let revision: BoundedVec<u8, ConstU32<32>> = BoundedVec::try_from(vec![1, 2]).unwrap();
+		let owner = RuntimeOrigin::signed(ACCOUNT_ID_1);
+
+		assert_ok!(PoeModule::create_claim(owner.clone(), claim.clone(), None, None));
+		assert_ok!(PoeModule::create_claim(owner.clone(), revision.clone(), None, None));
+		let claim_deposit = PoeModule::claim_deposit(revision.len());
+
+		let long = metadata(b"long label", Some(claim.clone()));
+		assert_ok!(PoeModule::update_claim_metadata(owner.clone(), revision.clone(), long.clone()));
+		let long_deposit = long.encoded_size() as u64;
+		assert_eq!(Deposits::<Test>::get(&revision), Some(claim_deposit + long_deposit));
+
+		let short = metadata(b"short", Some(claim.clone()));
+		assert_ok!(PoeModule::update_claim_metadata(owner, revision.clone(), short.clone()));
+		let short_deposit = short.encoded_size() as u64;
+		assert_eq!(Deposits::<Test>::get(&revision), Some(claim_deposit + short_deposit));
+		assert_eq!(
+			Balances::reserved_balance(ACCOUNT_ID_1),
+			PoeModule::claim_deposit(claim.len()) + claim_deposit + short_deposit
+		);
+
+		let record = PoeModule::claim_record(revision.to_vec()).unwrap();
+		assert_eq!(record.label, b"short".to_vec());
+		assert_eq!(record.parent, Some(claim.to_vec()));
+	})
+}
+
+#[test]
+fn update_claim_metadata_failed_with_invalid_parent() {
+	new_test_ext().execute_with(|| {
+		let claim = new_claim();
+		let owner = RuntimeOrigin::signed(ACCOUNT_ID_1);
+
+		assert_ok!(PoeModule::create_claim(owner.clone(), claim.clone(), None, None));
+		assert_noop!(
+			PoeModule::update_claim_metadata(owner.clone(), claim.clone(), metadata(b"self", Some(claim.clone()))),
+			Error::<Test>::InvalidParent
+		);
+		assert_noop!(
+			PoeModule::update_claim_metadata(
+				owner,
+				claim.clone(),
+				metadata(b"missing", Some(BoundedVec::try_from(vec![9]).unwrap()))
+			),
+			Error::<Test>::InvalidParent
+		);
+		assert_noop!(
+			PoeModule::update_claim_metadata(RuntimeOrigin::signed(ACCOUNT_ID_2), claim, metadata(b"x", None)),
+			Error::<Test>::NotClaimOwner
+		);
+	})
+}
+
+#[test]
+fn revoke_claim_refunds_metadata_deposit() {
+	new_test_ext().execute_with(|| {
+		let claim = new_claim();
+		let owner = RuntimeOrigin::signed(ACCOUNT_ID_1);
+
+		assert_ok!(PoeModule::create_claim(owner.clone(), claim.clone(), None, None));
+		assert_ok!(PoeModule::update_claim_metadata(owner.clone(), claim.clone(), metadata(b"label", None)));
+		assert_ok!(PoeModule::revoke_claim(owner, claim.clone()));
+
+		assert_eq!(Metadata::<Test>::get(&claim), None);
+		assert_eq!(Balances::reserved_balance(ACCOUNT_ID_1), 0);
+		assert_eq!(PoeModule::claim_record(claim.to_vec()), None);
+	})
+}
+
+
+fn batch(claims: &[&[u8]]) -> BoundedVec<BoundedVec<u8, ConstU32<32>>, ConstU32<3>> {
+	let claims: Vec<_> = claims.iter().map(|claim| BoundedVec::try_from(claim.to_vec()).unwrap()).collect();
+	BoundedVec::try_from(claims).unwrap()
+}
+
+#[test]
+fn create_claims_fail_fast_rolls_back_whole_batch() {
+	new_test_ext().execute_with(|| {
+		let owner = RuntimeOrigin::signed(ACCOUNT_ID_1);
+
+		assert_noop!(
+			PoeModule::create_claims(owner, batch(&[b"a", b"b", b"a"]), true),
+			Error::<Test>::ProofAlreadyExist
+		);
+		assert_eq!(ClaimsCount::<Test>::get(ACCOUNT_ID_1), 0);
+		assert_eq!(Balances::reserved_balance(ACCOUNT_ID_1), 0);
+	})
+}
+
+#[test]
+fn create_claims_best_effort_reports_failed_items() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let owner = RuntimeOrigin::signed(ACCOUNT_ID_1);
+		let claims = batch(&[b"a", b"b", b"a"]);
+
+		assert_ok!(PoeModule::create_claims(owner, claims.clone(), false));
+		assert_eq!(ClaimsCount::<Test>::get(ACCOUNT_ID_1), 2);
+		assert!(Proofs::<Test>::contains_key(&claims[0]));
+		assert!(Proofs::<Test>::contains_key(&claims[1]));
+		System::assert_has_event(Event::ClaimCreated(ACCOUNT_ID_1, claims[1].clone()).into());
+		System::assert_last_event(
+			Event::ClaimBatchItemFailed {
+				index: 2,
+				claim: claims[2].clone(),
+				error: Error::<Test>::ProofAlreadyExist.into(),
+			}
+			.into(),
+		);
+	})
+}
+
+#[test]
+fn revoke_claims_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let owner = RuntimeOrigin::signed(ACCOUNT_ID_1);
+		let claims = batch(&[b"a", b"b", b"c"]);
+
+		assert_ok!(PoeModule::create_claims(owner.clone(), claims.clone(), true));
+		assert_noop!(
+			PoeModule::revoke_claims(RuntimeOrigin::signed(ACCOUNT_ID_2), claims.clone(), true),
+			Error::<Test>::NotClaimOwner
+		);
+
+		assert_ok!(PoeModule::revoke_claim(owner.clone(), claims[1].clone()));
+		assert_ok!(PoeModule::revoke_claims(owner, claims.clone(), false));
+		assert_eq!(ClaimsCount::<Test>::get(ACCOUNT_ID_1), 0);
+		assert_eq!(Balances::reserved_balance(ACCOUNT_ID_1), 0);
+		System::assert_has_event(
+			Event::ClaimBatchItemFailed {
+				index: 1,
+				claim: claims[1].clone(),
+				error: Error::<Test>::ClaimNotExist.into(),
+			}
+			.into(),
+		);
+		System::assert_last_event(Event::ClaimRevoked(ACCOUNT_ID_1, claims[2].clone()).into());
+	})
+}
+
+fn permissions(create: bool, revoke: bool, transfer: bool) -> DelegatePermissions {
+	DelegatePermissions { create, revoke, transfer }
+}
+
+#[test]
+fn delegate_creates_claim_on_behalf_of_owner() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let claim = new_claim();
+		let delegate = RuntimeOrigin::signed(ACCOUNT_ID_2);
+
+		assert_noop!(
+			PoeModule::create_claim(delegate.clone(), claim.clone(), None, Some(ACCOUNT_ID_1)),
+			Error::<Test>::NotClaimOwner
+		);
+
+		assert_ok!(PoeModule::add_delegate(RuntimeOrigin::signed(ACCOUNT_ID_1), ACCOUNT_ID_2, permissions(true, false, false)));
+		assert_ok!(PoeModule::create_claim(delegate, claim.clone(), None, Some(ACCOUNT_ID_1)));
+
+		assert_eq!(Proofs::<Test>::get(&claim).unwrap().owner, ACCOUNT_ID_1);
+		assert_eq!(Balances::reserved_balance(ACCOUNT_ID_1), PoeModule::claim_deposit(claim.len()));
+		assert_eq!(Balances::reserved_balance(ACCOUNT_ID_2), 0);
+		System::assert_has_event(Event::ClaimCreated(ACCOUNT_ID_1, claim.clone()).into());
+		System::assert_last_event(
+			Event::DelegateActed { owner: ACCOUNT_ID_1, delegate: ACCOUNT_ID_2, claim }.into(),
+		);
+	})
+}
+
+#[test]
+fn delegate_revokes_and_transfers_with_permission() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let claim = new_claim();
+		let other: BoundedVec<u8, ConstU32<32>> = BoundedVec::try_from(vec![1]).unwrap();
+		let owner = RuntimeOrigin::signed(ACCOUNT_ID_1);
+		let delegate = RuntimeOrigin::signed(ACCOUNT_ID_2);
+
+		assert_ok!(PoeModule::create_claim(owner.clone(), claim.clone(), None, None));
+		assert_ok!(PoeModule::create_claim(owner.clone(), other.clone(), None, None));
+		assert_ok!(PoeModule::add_delegate(owner, ACCOUNT_ID_2, permissions(false, true, true)));
+
+		assert_ok!(PoeModule::revoke_claim(delegate.clone(), claim.clone()));
+		assert_eq!(Proofs::<Test>::get(&claim), None);
+		System::assert_last_event(
+			Event::DelegateActed { owner: ACCOUNT_ID_1, delegate: ACCOUNT_ID_2, claim }.into(),
+		);
+
+		assert_ok!(PoeModule::transfer_claim(delegate, ACCOUNT_ID_3, other.clone()));
+		assert_eq!(PendingOffers::<Test>::get(&other).unwrap().recipient, ACCOUNT_ID_3);
+		System::assert_has_event(
+			Event::ClaimOffered { owner: ACCOUNT_ID_1, recipient: ACCOUNT_ID_3, claim: other.clone(), expires_at: None }.into(),
+		);
+		System::assert_last_event(
+			Event::DelegateActed { owner: ACCOUNT_ID_1, delegate: ACCOUNT_ID_2, claim: other.clone() }.into(),
+		);
+
+		assert_ok!(PoeModule::accept_claim(RuntimeOrigin::signed(ACCOUNT_ID_3), other.clone()));
+		assert_eq!(Proofs::<Test>::get(&other).unwrap().owner, ACCOUNT_ID_3);
+	})
+}
+
+#[test]
+fn delegate_failed_without_permission() {
+	new_test_ext().execute_with(|| {
+		let claim = new_claim();
+		let owner = RuntimeOrigin::signed(ACCOUNT_ID_1);
+		let delegate = RuntimeOrigin::signed(ACCOUNT_ID_2);
+
+		assert_ok!(PoeModule::create_claim(owner.clone(), claim.clone(), None, None));
+		assert_ok!(PoeModule::add_delegate(owner.clone(), ACCOUNT_ID_2, permissions(true, false, false)));
+
+		assert_noop!(
+			PoeModule::revoke_claim(delegate.clone(), claim.clone()),
+			Error::<Test>::DelegateNotPermitted
+		);
+		assert_noop!(
+			PoeModule::transfer_claim(delegate.clone(), ACCOUNT_ID_3, claim.clone()),
+			Error::<Test>::DelegateNotPermitted
+		);
+
+		assert_ok!(PoeModule::remove_delegate(owner, ACCOUNT_ID_2));
+		assert_noop!(
+			PoeModule::create_claim(delegate, BoundedVec::try_from(vec![1]).unwrap(), None, Some(ACCOUNT_ID_1)),
+			Error::<Test>::NotClaimOwner
+		);
+	})
+}
+
+#[test]
+fn add_and_remove_delegate_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let owner = RuntimeOrigin::signed(ACCOUNT_ID_1);
+
+		assert_noop!(
+			PoeModule::add_delegate(owner.clone(), ACCOUNT_ID_1, permissions(true, true, true)),
+			Error::<Test>::InvalidDelegate
+		);
+		assert_noop!(PoeModule::remove_delegate(owner.clone(), ACCOUNT_ID_2), Error::<Test>::DelegateNotExist);
+
+		assert_ok!(PoeModule::add_delegate(owner.clone(), ACCOUNT_ID_2, permissions(true, false, false)));
+		assert_ok!(PoeModule::add_delegate(owner.clone(), ACCOUNT_ID_2, permissions(false, true, false)));
+		assert_eq!(Delegates::<Test>::get(ACCOUNT_ID_1, ACCOUNT_ID_2), Some(permissions(false, true, false)));
+
+		assert_ok!(PoeModule::remove_delegate(owner, ACCOUNT_ID_2));
+		assert_eq!(Delegates::<Test>::get(ACCOUNT_ID_1, ACCOUNT_ID_2), None);
+		System::assert_last_event(Event::DelegateRemoved { owner: ACCOUNT_ID_1, delegate: ACCOUNT_ID_2 }.into());
+	})
+}
+
+fn co_owners(owners: &[u64]) -> BoundedVec<u64, ConstU32<3>> {
+	BoundedVec::try_from(owners.to_vec()).unwrap()
+}
+
+#[test]
+fn shared_claim_is_final_once_threshold_signs() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let claim = new_claim();
+		let owners = co_owners(&[ACCOUNT_ID_1, ACCOUNT_ID_2, ACCOUNT_ID_3]);
+
+		assert_ok!(PoeModule::create_shared_claim(RuntimeOrigin::signed(ACCOUNT_ID_1), claim.clone(), owners.clone(), 2));
+		assert_eq!(Proofs::<Test>::get(&claim).unwrap().expires_at, Some(11));
+		assert_eq!(
+			PoeModule::signing_status(claim.to_vec()),
+			Some(SigningStatus {
+				co_owners: owners.to_vec(),
+				threshold: 2,
+				action: Some(SharedAction::Create),
+				signatures: vec![ACCOUNT_ID_1],
+				deadline: Some(11),
+			})
+		);
+
+		assert_noop!(
+			PoeModule::co_sign_claim(RuntimeOrigin::signed(ACCOUNT_ID_1), claim.clone()),
+			Error::<Test>::AlreadySigned
+		);
+		assert_noop!(PoeModule::co_sign_claim(RuntimeOrigin::signed(4), claim.clone()), Error::<Test>::NotCoOwner);
+
+		assert_ok!(PoeModule::co_sign_claim(RuntimeOrigin::signed(ACCOUNT_ID_2), claim.clone()));
+		assert_eq!(Proofs::<Test>::get(&claim).unwrap().expires_at, None);
+		assert!(!ExpiringClaims::<Test>::contains_key(11));
+		assert_eq!(PoeModule::signing_status(claim.to_vec()).unwrap().action, None);
+		System::assert_last_event(Event::ClaimCreated(ACCOUNT_ID_1, claim.clone()).into());
+
+		assert_noop!(
+			PoeModule::co_sign_claim(RuntimeOrigin::signed(ACCOUNT_ID_3), claim),
+			Error::<Test>::SigningNotExist
+		);
+	})
+}
+
+#[test]
+fn create_shared_claim_failed_with_invalid_co_owners() {
+	new_test_ext().execute_with(|| {
+		let claim = new_claim();
+		let signer = RuntimeOrigin::signed(ACCOUNT_ID_1);
+
+		assert_noop!(
+			PoeModule::create_shared_claim(signer.clone(), claim.clone(), co_owners(&[ACCOUNT_ID_2, ACCOUNT_ID_3]), 1),
+			Error::<Test>::NotCoOwner
+		);
+		assert_noop!(
+			PoeModule::create_shared_claim(signer.clone(), claim.clone(), co_owners(&[ACCOUNT_ID_1, ACCOUNT_ID_2]), 0),
+			Error::<Test>::InvalidThreshold
+		);
+		assert_noop!(
+			PoeModule::create_shared_claim(signer.clone(), claim.clone(), co_owners(&[ACCOUNT_ID_1, ACCOUNT_ID_2]), 3),
+			Error::<Test>::InvalidThreshold
+		);
+		assert_noop!(
+			PoeModule::create_shared_claim(signer, claim, co_owners(&[ACCOUNT_ID_1, ACCOUNT_ID_2, ACCOUNT_ID_1]), 2),
+			Error::<Test>::DuplicateCoOwner
+		);
+	})
+}
+
+#[test]
+fn unsigned_shared_claim_is_pruned_after_signing_period() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let claim = new_claim();
+
+		assert_ok!(PoeModule::create_shared_claim(
+			RuntimeOrigin::signed(ACCOUNT_ID_1),
+			claim.clone(),
+			co_owners(&[ACCOUNT_ID_1, ACCOUNT_ID_2]),
+			2
+		));
+
+		System::set_block_number(11);
+		PoeModule::on_initialize(11);
+		assert_eq!(Proofs::<Test>::get(&claim), None);
+		assert_eq!(PoeModule::signing_status(claim.to_vec()), None);
+		assert_eq!(Balances::reserved_balance(ACCOUNT_ID_1), 0);
+		assert_noop!(
+			PoeModule::co_sign_claim(RuntimeOrigin::signed(ACCOUNT_ID_2), claim),
+			Error::<Test>::ClaimNotShared
+		);
+	})
+}
+
+#[test]
+fn shared_claim_revoke_needs_threshold() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let claim = new_claim();
+		let owners = co_owners(&[ACCOUNT_ID_1, ACCOUNT_ID_2, ACCOUNT_ID_3]);
+
+		assert_ok!(PoeModule::create_shared_claim(RuntimeOrigin::signed(ACCOUNT_ID_1), claim.clone(), owners, 2));
+		assert_ok!(PoeModule::co_sign_claim(RuntimeOrigin::signed(ACCOUNT_ID_3), claim.clone()));
+
+		let batch: BoundedVec<_, ConstU32<3>> = BoundedVec::try_from(vec![claim.clone()]).unwrap();
+		assert_noop!(
+			PoeModule::revoke_claims(RuntimeOrigin::signed(ACCOUNT_ID_1), batch, true),
+			Error::<Test>::SharedClaim
+		);
+		assert_noop!(
+			PoeModule::offer_claim(RuntimeOrigin::signed(ACCOUNT_ID_1), claim.clone(), ACCOUNT_ID_2, None),
+			Error::<Test>::SharedClaim
+		);
+
+		assert_ok!(PoeModule::revoke_claim(RuntimeOrigin::signed(ACCOUNT_ID_2), claim.clone()));
+		assert!(Proofs::<Test>::contains_key(&claim));
+		System::assert_last_event(
+			Event::ClaimCoSigned { signer: ACCOUNT_ID_2, claim: claim.clone(), signatures: 1 }.into(),
+		);
+
+		assert_ok!(PoeModule::co_sign_claim(RuntimeOrigin::signed(ACCOUNT_ID_3), claim.clone()));
+		assert_eq!(Proofs::<Test>::get(&claim), None);
+		assert_eq!(CoOwners::<Test>::get(&claim), None);
+		assert_eq!(Balances::reserved_balance(ACCOUNT_ID_1), 0);
+		System::assert_last_event(Event::ClaimRevoked(ACCOUNT_ID_1, claim).into());
+	})
+}
+
+#[test]
+fn shared_claim_transfer_needs_threshold() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let claim = new_claim();
+		let owners = co_owners(&[ACCOUNT_ID_1, ACCOUNT_ID_2]);
+
+		assert_ok!(PoeModule::create_shared_claim(RuntimeOrigin::signed(ACCOUNT_ID_1), claim.clone(), owners, 2));
+		assert_noop!(
+			PoeModule::transfer_claim(RuntimeOrigin::signed(ACCOUNT_ID_2), ACCOUNT_ID_3, claim.clone()),
+			Error::<Test>::SigningInProgress
+		);
+		assert_ok!(PoeModule::co_sign_claim(RuntimeOrigin::signed(ACCOUNT_ID_2), claim.clone()));
+
+		assert_ok!(PoeModule::transfer_claim(RuntimeOrigin::signed(ACCOUNT_ID_2), ACCOUNT_ID_3, claim.clone()));
+		assert_eq!(
+			PoeModule::signing_status(claim.to_vec()).unwrap().action,
+			Some(SharedAction::Transfer(ACCOUNT_ID_3))
+		);
+		assert_noop!(
+			PoeModule::revoke_claim(RuntimeOrigin::signed(ACCOUNT_ID_1), claim.clone()),
+			Error::<Test>::SigningInProgress
+		);
+
+		assert_ok!(PoeModule::co_sign_claim(RuntimeOrigin::signed(ACCOUNT_ID_1), claim.clone()));
+		assert_eq!(Proofs::<Test>::get(&claim).unwrap().owner, ACCOUNT_ID_1);
+		assert_eq!(PoeModule::signing_status(claim.to_vec()).unwrap().action, None);
+		System::assert_last_event(
+			Event::ClaimOffered { owner: ACCOUNT_ID_1, recipient: ACCOUNT_ID_3, claim: claim.clone(), expires_at: None }.into(),
+		);
+		assert_noop!(
+			PoeModule::cancel_offer(RuntimeOrigin::signed(ACCOUNT_ID_1), claim.clone()),
+			Error::<Test>::SharedClaim
+		);
+
+		assert_ok!(PoeModule::accept_claim(RuntimeOrigin::signed(ACCOUNT_ID_3), claim.clone()));
+		assert_eq!(Proofs::<Test>::get(&claim).unwrap().owner, ACCOUNT_ID_3);
+		assert_eq!(CoOwners::<Test>::get(&claim), None);
+	})
+}
+
+#[test]
+fn anchor_root_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let root = [7; 32];
+		let signer = RuntimeOrigin::signed(ACCOUNT_ID_1);
+
+		assert_noop!(PoeModule::anchor_root(signer.clone(), root, 0), Error::<Test>::EmptyAnchor);
+		assert_ok!(PoeModule::anchor_root(signer.clone(), root, 4));
+		assert_eq!(
+			Anchors::<Test>::get(root),
+			Some(AnchorInfo { owner: ACCOUNT_ID_1, leaf_count: 4, anchored_at: 1 })
+		);
+		System::assert_last_event(Event::RootAnchored { owner: ACCOUNT_ID_1, root, leaf_count: 4 }.into());
+
+		assert_noop!(PoeModule::anchor_root(signer, root, 4), Error::<Test>::RootAlreadyAnchored);
+	})
+}
+
+#[test]
+fn remove_anchor_refunds_the_deposit() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let root = [7; 32];
+		let signer = RuntimeOrigin::signed(ACCOUNT_ID_1);
+
+		assert_ok!(PoeModule::anchor_root(signer.clone(), root, 4));
+		assert_eq!(Balances::reserved_balance(ACCOUNT_ID_1), 10);
+		assert_eq!(AnchorDeposits::<Test>::get(root), Some(10));
+
+		assert_noop!(
+			PoeModule::remove_anchor(RuntimeOrigin::signed(ACCOUNT_ID_2), root),
+			Error::<Test>::NotAnchorOwner
+		);
+		assert_ok!(PoeModule::remove_anchor(signer.clone(), root));
+		System::assert_last_event(Event::AnchorRemoved { owner: ACCOUNT_ID_1, root }.into());
+		assert_eq!(Anchors::<Test>::get(root), None);
+		assert_eq!(AnchorDeposits::<Test>::get(root), None);
+		assert_eq!(Balances::reserved_balance(ACCOUNT_ID_1), 0);
+
+		assert_noop!(PoeModule::remove_anchor(signer, root), Error::<Test>::AnchorNotExist);
+	})
+}
+
+#[test]
+fn verify_inclusion_works() {
+	new_test_ext().execute_with(|| {
+		let (a, b, c) = ([1; 32], [2; 32], [3; 32]);
+		let ab = merkle::node_hash(&merkle::leaf_hash(&a), &merkle::leaf_hash(&b));
+		let root = merkle::node_hash(&ab, &merkle::leaf_hash(&c));
+
+		assert_eq!(PoeModule::verify_inclusion(root, c, vec![ab]), None);
+		assert_ok!(PoeModule::anchor_root(RuntimeOrigin::signed(ACCOUNT_ID_1), root, 3));
+
+		let anchor = Anchors::<Test>::get(root);
+		assert_eq!(PoeModule::verify_inclusion(root, a, vec![merkle::leaf_hash(&b), merkle::leaf_hash(&c)]), anchor);
+		assert_eq!(PoeModule::verify_inclusion(root, c, vec![ab]), anchor);
+		assert_eq!(PoeModule::verify_inclusion(root, [4; 32], vec![ab]), None);
+		assert_eq!(
+			PoeModule::verify_inclusion(root, a, vec![merkle::leaf_hash(&b), merkle::leaf_hash(&c), [0; 32]]),
+			None
+		);
 	})
 }
+
+#[test]
+fn original_calls_keep_their_indices() {
+	let claim = new_claim();
+
+	let create = Call::<Test>::create_claim { claim: claim.clone(), expires_at: None, on_behalf_of: None };
+	let revoke = Call::<Test>::revoke_claim { claim: claim.clone() };
+	let transfer = Call::<Test>::transfer_claim { recipient: ACCOUNT_ID_2, claim };
+
+	assert_eq!(create.encode()[0], 0);
+	assert_eq!(revoke.encode()[0], 1);
+	assert_eq!(transfer.encode()[0], 2);
+}
+
+#[test]
+fn batch_calls_are_indexed_after_the_single_claim_calls() {
+	let claims = BoundedVec::try_from(vec![new_claim()]).unwrap();
+
+	let create = Call::<Test>::create_claims { claims: claims.clone(), fail_fast: true };
+	let revoke = Call::<Test>::revoke_claims { claims, fail_fast: true };
+
+	assert_eq!(create.encode()[0], 9);
+	assert_eq!(revoke.encode()[0], 10);
+}
@@ -1,34 +1,14 @@
-
-//! Autogenerated weights for pallet_poe
+//! Weights for pallet_poe.
 //!
-//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
-//! DATE: 2023-07-06, STEPS: `20`, REPEAT: `10`, LOW RANGE: `[]`, HIGH RANGE: `[]`
-//! WORST CASE MAP SIZE: `1000000`
-//! HOSTNAME: `xzy-virtual-machine`, CPU: `12th Gen Intel(R) Core(TM) i3-12100F`
-//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 1024
-
-// Executed Command:
-// ./target/release/node-template
-// benchmark
-// pallet
-// --chain
-// dev
-// --execution
-// wasm
-// --wasm-execution
-// compiled
-// --pallet
-// pallet_poe
-// --extrinsic
-// *
-// --steps
-// 20
-// --repeat
-// 10
-// --output
-// ./pallets/poe/src/weights.rs
-// --template
-// .maintain/frame-weight-template.hbs
+//! These are hand-written upper bounds, not benchmark results. Each call is charged a generous
+//! fixed execution time, one database read or write for every storage item it can touch on its
+//! most expensive path, and a proof size covering the largest encoding of those items. Replace
+//! this file with the output of the `pallet_poe` benchmarks once they have been run on reference
+//! hardware:
+//!
+//! ./target/release/node-template benchmark pallet --chain dev --execution wasm
+//!     --wasm-execution compiled --pallet pallet_poe --extrinsic '*' --steps 50 --repeat 20
+//!     --output ./pallets/poe/src/weights.rs --template .maintain/frame-weight-template.hbs
 
 #![cfg_attr(rustfmt, rustfmt_skip)]
 #![allow(unused_parens)]
@@ -40,99 +20,324 @@ use sp_std::marker::PhantomData;
 /// Weight functions needed for pallet_poe.
 pub trait WeightInfo {
 	fn create_claim(d: u32, ) -> Weight;
+	fn create_claim_hash() -> Weight;
 	fn revoke_claim(d: u32, ) -> Weight;
 	fn transfer_claim(d: u32, ) -> Weight;
+	fn renew_claim(d: u32, ) -> Weight;
+	fn expire_claims(n: u32, ) -> Weight;
+	fn offer_claim(d: u32, ) -> Weight;
+	fn accept_claim(d: u32, ) -> Weight;
+	fn cancel_offer(d: u32, ) -> Weight;
+	fn update_claim_metadata(d: u32, m: u32, ) -> Weight;
+	fn create_claims(n: u32, b: u32, ) -> Weight;
+	fn revoke_claims(n: u32, b: u32, ) -> Weight;
+	fn add_delegate() -> Weight;
+	fn remove_delegate() -> Weight;
+	fn create_shared_claim(d: u32, n: u32, ) -> Weight;
+	fn co_sign_claim(d: u32, n: u32, ) -> Weight;
+	fn anchor_root() -> Weight;
+	fn remove_anchor() -> Weight;
 }
 
 /// Weights for pallet_poe using the Substrate node and recommended hardware.
 pub struct SubstrateWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
-	/// Storage: PalletPoe Proofs (r:1 w:1)
-	/// Proof: PalletPoe Proofs (max_values: None, max_size: Some(566), added: 3041, mode: MaxEncodedLen)
-	/// The range of component `d` is `[0, 512]`.
+	/// Reads: Proofs, Delegates, ClaimsCount, ExpiringClaims, System Account.
+	/// Writes: Proofs, ClaimsByOwner, ClaimsCount, Deposits, ExpiringClaims, System Account.
 	fn create_claim(d: u32, ) -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `42`
-		//  Estimated: `4031`
-		// Minimum execution time: 12_329_000 picoseconds.
-		Weight::from_parts(16_967_325, 4031)
-			// Standard Error: 2_337
-			.saturating_add(Weight::from_parts(200, 0).saturating_mul(d.into()))
-			.saturating_add(T::DbWeight::get().reads(1_u64))
-			.saturating_add(T::DbWeight::get().writes(1_u64))
+		Weight::from_parts(100_000_000, 45_000)
+			.saturating_add(Weight::from_parts(50_000, 0).saturating_mul(d.into()))
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+	/// Reads: Proofs, ClaimsCount, ExpiringClaims, System Account.
+	/// Writes: Proofs, ClaimsByOwner, ClaimsCount, Deposits, ExpiringClaims, System Account.
+	fn create_claim_hash() -> Weight {
+		Weight::from_parts(101_600_000, 45_000)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
 	}
-	/// Storage: PalletPoe Proofs (r:1 w:1)
-	/// Proof: PalletPoe Proofs (max_values: None, max_size: Some(566), added: 3041, mode: MaxEncodedLen)
-	/// The range of component `d` is `[0, 512]`.
+	/// Reads: Proofs, CoOwners, Delegates, ClaimsCount, Deposits, ExpiringClaims, System Account.
+	/// Writes: Proofs, ClaimsByOwner, ClaimsCount, Deposits, ExpiringClaims, PendingOffers, Metadata, CoOwners, PendingSignatures, System Account.
 	fn revoke_claim(d: u32, ) -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `123 + d * (1 ±0)`
-		//  Estimated: `4031`
-		// Minimum execution time: 12_507_000 picoseconds.
-		Weight::from_parts(15_560_894, 4031)
-			// Standard Error: 2_351
-			.saturating_add(Weight::from_parts(14_598, 0).saturating_mul(d.into()))
+		Weight::from_parts(120_000_000, 50_000)
+			.saturating_add(Weight::from_parts(50_000, 0).saturating_mul(d.into()))
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(10_u64))
+	}
+	/// Reads: Proofs, CoOwners, Delegates, PendingSignatures.
+	/// Writes: PendingOffers, PendingSignatures.
+	fn transfer_claim(d: u32, ) -> Weight {
+		Weight::from_parts(80_000_000, 15_000)
+			.saturating_add(Weight::from_parts(50_000, 0).saturating_mul(d.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Reads: Proofs, CoOwners, ExpiringClaims (old and new block).
+	/// Writes: Proofs, ExpiringClaims (old and new block).
+	fn renew_claim(d: u32, ) -> Weight {
+		Weight::from_parts(100_000_000, 65_000)
+			.saturating_add(Weight::from_parts(50_000, 0).saturating_mul(d.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Reads: ExpiringClaims, and per claim Proofs, ClaimsCount, Deposits, System Account.
+	/// Writes: ExpiringClaims, and per claim Proofs, ClaimsByOwner, ClaimsCount, Deposits, PendingOffers, Metadata, CoOwners, PendingSignatures, System Account.
+	fn expire_claims(n: u32, ) -> Weight {
+		Weight::from_parts(20_000_000, 30_000)
+			.saturating_add(Weight::from_parts(100_000_000, 0).saturating_mul(n.into()))
 			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().reads((4_u64).saturating_mul(n.into())))
 			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((9_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 16_000).saturating_mul(n.into()))
 	}
-	/// Storage: PalletPoe Proofs (r:1 w:1)
-	/// Proof: PalletPoe Proofs (max_values: None, max_size: Some(566), added: 3041, mode: MaxEncodedLen)
-	/// The range of component `d` is `[0, 512]`.
-	fn transfer_claim(d: u32, ) -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `123 + d * (1 ±0)`
-		//  Estimated: `4031`
-		// Minimum execution time: 14_141_000 picoseconds.
-		Weight::from_parts(15_255_407, 4031)
-			// Standard Error: 3_990
-			.saturating_add(Weight::from_parts(28_998, 0).saturating_mul(d.into()))
+	/// Reads: Proofs, CoOwners.
+	/// Writes: PendingOffers.
+	fn offer_claim(d: u32, ) -> Weight {
+		Weight::from_parts(60_000_000, 8_000)
+			.saturating_add(Weight::from_parts(50_000, 0).saturating_mul(d.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Reads: PendingOffers, Proofs, ClaimsCount (both owners), Deposits, System Account (both owners).
+	/// Writes: Proofs, PendingOffers, CoOwners, PendingSignatures, ClaimsByOwner (both owners), ClaimsCount (both owners), System Account (both owners).
+	fn accept_claim(d: u32, ) -> Weight {
+		Weight::from_parts(150_000_000, 25_000)
+			.saturating_add(Weight::from_parts(50_000, 0).saturating_mul(d.into()))
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(10_u64))
+	}
+	/// Reads: Proofs, CoOwners, PendingOffers.
+	/// Writes: PendingOffers.
+	fn cancel_offer(d: u32, ) -> Weight {
+		Weight::from_parts(60_000_000, 11_000)
+			.saturating_add(Weight::from_parts(50_000, 0).saturating_mul(d.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Reads: Proofs (claim and parent), Metadata, Deposits, System Account.
+	/// Writes: Metadata, Deposits, System Account.
+	fn update_claim_metadata(d: u32, m: u32, ) -> Weight {
+		Weight::from_parts(120_000_000, 20_000)
+			.saturating_add(Weight::from_parts(50_000, 0).saturating_mul(d.into()))
+			.saturating_add(Weight::from_parts(20_000, 0).saturating_mul(m.into()))
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Reads: per claim Proofs, ClaimsCount, System Account.
+	/// Writes: per claim Proofs, ClaimsByOwner, ClaimsCount, Deposits, System Account.
+	fn create_claims(n: u32, b: u32, ) -> Weight {
+		Weight::from_parts(20_000_000, 4_000)
+			.saturating_add(Weight::from_parts(100_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(50_000, 0).saturating_mul(b.into()))
+			.saturating_add(T::DbWeight::get().reads((3_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes((5_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 12_000).saturating_mul(n.into()))
+	}
+	/// Reads: per claim Proofs, CoOwners, ClaimsCount, Deposits, ExpiringClaims, System Account.
+	/// Writes: per claim Proofs, ClaimsByOwner, ClaimsCount, Deposits, ExpiringClaims, PendingOffers, Metadata, CoOwners, PendingSignatures, System Account.
+	fn revoke_claims(n: u32, b: u32, ) -> Weight {
+		Weight::from_parts(20_000_000, 4_000)
+			.saturating_add(Weight::from_parts(120_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(50_000, 0).saturating_mul(b.into()))
+			.saturating_add(T::DbWeight::get().reads((6_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes((10_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 45_000).saturating_mul(n.into()))
+	}
+	/// Writes: Delegates.
+	fn add_delegate() -> Weight {
+		Weight::from_parts(40_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Reads: Delegates.
+	/// Writes: Delegates.
+	fn remove_delegate() -> Weight {
+		Weight::from_parts(40_000_000, 4_000)
 			.saturating_add(T::DbWeight::get().reads(1_u64))
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
+	/// Reads: Proofs, ClaimsCount, ExpiringClaims, CoOwners, PendingSignatures, System Account.
+	/// Writes: Proofs, ClaimsByOwner, ClaimsCount, Deposits, ExpiringClaims, CoOwners, PendingSignatures, System Account.
+	fn create_shared_claim(d: u32, n: u32, ) -> Weight {
+		Weight::from_parts(150_000_000, 50_000)
+			.saturating_add(Weight::from_parts(50_000, 0).saturating_mul(d.into()))
+			.saturating_add(Weight::from_parts(2_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(9_u64))
+	}
+	/// Reads: CoOwners, PendingSignatures, Proofs, and the claim storage touched by a final revoke or transfer.
+	/// Writes: PendingSignatures, and the claim storage touched by a final revoke or transfer.
+	fn co_sign_claim(d: u32, n: u32, ) -> Weight {
+		Weight::from_parts(180_000_000, 55_000)
+			.saturating_add(Weight::from_parts(50_000, 0).saturating_mul(d.into()))
+			.saturating_add(Weight::from_parts(2_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(10_u64))
+			.saturating_add(T::DbWeight::get().writes(12_u64))
+	}
+	/// Reads: Anchors, System Account.
+	/// Writes: Anchors, AnchorDeposits, System Account.
+	fn anchor_root() -> Weight {
+		Weight::from_parts(60_000_000, 7_000)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Reads: Anchors, AnchorDeposits, System Account.
+	/// Writes: Anchors, AnchorDeposits, System Account.
+	fn remove_anchor() -> Weight {
+		Weight::from_parts(60_000_000, 10_000)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
 }
 
 // For backwards compatibility and tests
 impl WeightInfo for () {
-	/// Storage: PalletPoe Proofs (r:1 w:1)
-	/// Proof: PalletPoe Proofs (max_values: None, max_size: Some(566), added: 3041, mode: MaxEncodedLen)
-	/// The range of component `d` is `[0, 512]`.
+	/// Reads: Proofs, Delegates, ClaimsCount, ExpiringClaims, System Account.
+	/// Writes: Proofs, ClaimsByOwner, ClaimsCount, Deposits, ExpiringClaims, System Account.
 	fn create_claim(d: u32, ) -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `42`
-		//  Estimated: `4031`
-		// Minimum execution time: 12_329_000 picoseconds.
-		Weight::from_parts(16_967_325, 4031)
-			// Standard Error: 2_337
-			.saturating_add(Weight::from_parts(200, 0).saturating_mul(d.into()))
-			.saturating_add(RocksDbWeight::get().reads(1_u64))
-			.saturating_add(RocksDbWeight::get().writes(1_u64))
+		Weight::from_parts(100_000_000, 45_000)
+			.saturating_add(Weight::from_parts(50_000, 0).saturating_mul(d.into()))
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
 	}
-	/// Storage: PalletPoe Proofs (r:1 w:1)
-	/// Proof: PalletPoe Proofs (max_values: None, max_size: Some(566), added: 3041, mode: MaxEncodedLen)
-	/// The range of component `d` is `[0, 512]`.
+	/// Reads: Proofs, ClaimsCount, ExpiringClaims, System Account.
+	/// Writes: Proofs, ClaimsByOwner, ClaimsCount, Deposits, ExpiringClaims, System Account.
+	fn create_claim_hash() -> Weight {
+		Weight::from_parts(101_600_000, 45_000)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+	/// Reads: Proofs, CoOwners, Delegates, ClaimsCount, Deposits, ExpiringClaims, System Account.
+	/// Writes: Proofs, ClaimsByOwner, ClaimsCount, Deposits, ExpiringClaims, PendingOffers, Metadata, CoOwners, PendingSignatures, System Account.
 	fn revoke_claim(d: u32, ) -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `123 + d * (1 ±0)`
-		//  Estimated: `4031`
-		// Minimum execution time: 12_507_000 picoseconds.
-		Weight::from_parts(15_560_894, 4031)
-			// Standard Error: 2_351
-			.saturating_add(Weight::from_parts(14_598, 0).saturating_mul(d.into()))
+		Weight::from_parts(120_000_000, 50_000)
+			.saturating_add(Weight::from_parts(50_000, 0).saturating_mul(d.into()))
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().writes(10_u64))
+	}
+	/// Reads: Proofs, CoOwners, Delegates, PendingSignatures.
+	/// Writes: PendingOffers, PendingSignatures.
+	fn transfer_claim(d: u32, ) -> Weight {
+		Weight::from_parts(80_000_000, 15_000)
+			.saturating_add(Weight::from_parts(50_000, 0).saturating_mul(d.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Reads: Proofs, CoOwners, ExpiringClaims (old and new block).
+	/// Writes: Proofs, ExpiringClaims (old and new block).
+	fn renew_claim(d: u32, ) -> Weight {
+		Weight::from_parts(100_000_000, 65_000)
+			.saturating_add(Weight::from_parts(50_000, 0).saturating_mul(d.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Reads: ExpiringClaims, and per claim Proofs, ClaimsCount, Deposits, System Account.
+	/// Writes: ExpiringClaims, and per claim Proofs, ClaimsByOwner, ClaimsCount, Deposits, PendingOffers, Metadata, CoOwners, PendingSignatures, System Account.
+	fn expire_claims(n: u32, ) -> Weight {
+		Weight::from_parts(20_000_000, 30_000)
+			.saturating_add(Weight::from_parts(100_000_000, 0).saturating_mul(n.into()))
 			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().reads((4_u64).saturating_mul(n.into())))
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((9_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 16_000).saturating_mul(n.into()))
 	}
-	/// Storage: PalletPoe Proofs (r:1 w:1)
-	/// Proof: PalletPoe Proofs (max_values: None, max_size: Some(566), added: 3041, mode: MaxEncodedLen)
-	/// The range of component `d` is `[0, 512]`.
-	fn transfer_claim(d: u32, ) -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `123 + d * (1 ±0)`
-		//  Estimated: `4031`
-		// Minimum execution time: 14_141_000 picoseconds.
-		Weight::from_parts(15_255_407, 4031)
-			// Standard Error: 3_990
-			.saturating_add(Weight::from_parts(28_998, 0).saturating_mul(d.into()))
+	/// Reads: Proofs, CoOwners.
+	/// Writes: PendingOffers.
+	fn offer_claim(d: u32, ) -> Weight {
+		Weight::from_parts(60_000_000, 8_000)
+			.saturating_add(Weight::from_parts(50_000, 0).saturating_mul(d.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Reads: PendingOffers, Proofs, ClaimsCount (both owners), Deposits, System Account (both owners).
+	/// Writes: Proofs, PendingOffers, CoOwners, PendingSignatures, ClaimsByOwner (both owners), ClaimsCount (both owners), System Account (both owners).
+	fn accept_claim(d: u32, ) -> Weight {
+		Weight::from_parts(150_000_000, 25_000)
+			.saturating_add(Weight::from_parts(50_000, 0).saturating_mul(d.into()))
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().writes(10_u64))
+	}
+	/// Reads: Proofs, CoOwners, PendingOffers.
+	/// Writes: PendingOffers.
+	fn cancel_offer(d: u32, ) -> Weight {
+		Weight::from_parts(60_000_000, 11_000)
+			.saturating_add(Weight::from_parts(50_000, 0).saturating_mul(d.into()))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Reads: Proofs (claim and parent), Metadata, Deposits, System Account.
+	/// Writes: Metadata, Deposits, System Account.
+	fn update_claim_metadata(d: u32, m: u32, ) -> Weight {
+		Weight::from_parts(120_000_000, 20_000)
+			.saturating_add(Weight::from_parts(50_000, 0).saturating_mul(d.into()))
+			.saturating_add(Weight::from_parts(20_000, 0).saturating_mul(m.into()))
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Reads: per claim Proofs, ClaimsCount, System Account.
+	/// Writes: per claim Proofs, ClaimsByOwner, ClaimsCount, Deposits, System Account.
+	fn create_claims(n: u32, b: u32, ) -> Weight {
+		Weight::from_parts(20_000_000, 4_000)
+			.saturating_add(Weight::from_parts(100_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(50_000, 0).saturating_mul(b.into()))
+			.saturating_add(RocksDbWeight::get().reads((3_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes((5_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 12_000).saturating_mul(n.into()))
+	}
+	/// Reads: per claim Proofs, CoOwners, ClaimsCount, Deposits, ExpiringClaims, System Account.
+	/// Writes: per claim Proofs, ClaimsByOwner, ClaimsCount, Deposits, ExpiringClaims, PendingOffers, Metadata, CoOwners, PendingSignatures, System Account.
+	fn revoke_claims(n: u32, b: u32, ) -> Weight {
+		Weight::from_parts(20_000_000, 4_000)
+			.saturating_add(Weight::from_parts(120_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(50_000, 0).saturating_mul(b.into()))
+			.saturating_add(RocksDbWeight::get().reads((6_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes((10_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 45_000).saturating_mul(n.into()))
+	}
+	/// Writes: Delegates.
+	fn add_delegate() -> Weight {
+		Weight::from_parts(40_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Reads: Delegates.
+	/// Writes: Delegates.
+	fn remove_delegate() -> Weight {
+		Weight::from_parts(40_000_000, 4_000)
 			.saturating_add(RocksDbWeight::get().reads(1_u64))
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
-}
\ No newline at end of file
+	/// Reads: Proofs, ClaimsCount, ExpiringClaims, CoOwners, PendingSignatures, System Account.
+	/// Writes: Proofs, ClaimsByOwner, ClaimsCount, Deposits, ExpiringClaims, CoOwners, PendingSignatures, System Account.
+	fn create_shared_claim(d: u32, n: u32, ) -> Weight {
+		Weight::from_parts(150_000_000, 50_000)
+			.saturating_add(Weight::from_parts(50_000, 0).saturating_mul(d.into()))
+			.saturating_add(Weight::from_parts(2_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().writes(9_u64))
+	}
+	/// Reads: CoOwners, PendingSignatures, Proofs, and the claim storage touched by a final revoke or transfer.
+	/// Writes: PendingSignatures, and the claim storage touched by a final revoke or transfer.
+	fn co_sign_claim(d: u32, n: u32, ) -> Weight {
+		Weight::from_parts(180_000_000, 55_000)
+			.saturating_add(Weight::from_parts(50_000, 0).saturating_mul(d.into()))
+			.saturating_add(Weight::from_parts(2_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(10_u64))
+			.saturating_add(RocksDbWeight::get().writes(12_u64))
+	}
+	/// Reads: Anchors, System Account.
+	/// Writes: Anchors, AnchorDeposits, System Account.
+	fn anchor_root() -> Weight {
+		Weight::from_parts(60_000_000, 7_000)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Reads: Anchors, AnchorDeposits, System Account.
+	/// Writes: Anchors, AnchorDeposits, System Account.
+	fn remove_anchor() -> Weight {
+		Weight::from_parts(60_000_000, 10_000)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+}
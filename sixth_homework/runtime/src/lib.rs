@@ -116,10 +116,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	//   `spec_version`, and `authoring_version` are the same between Wasm and native.
 	// This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
 	//   the compatible custom types.
-	spec_version: 102,
+	spec_version: 113,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 4,
 	state_version: 1,
 };
 
@@ -309,6 +309,16 @@ impl pallet_kuaidi100::Config for Runtime {
 impl pallet_poe::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type MaxClaimLength = ConstU32<512>;
+	type MaxClaimsPerOwner = ConstU32<10_000>;
+	type ClaimHasher = pallet_poe::SpIoHasher;
+	type Currency = Balances;
+	type ClaimDepositBase = ConstU128<{ EXISTENTIAL_DEPOSIT * 10 }>;
+	type ClaimDepositPerByte = ConstU128<{ EXISTENTIAL_DEPOSIT / 10 }>;
+	type MaxExpiriesPerBlock = ConstU32<50>;
+	type MaxMetadataLength = ConstU32<256>;
+	type MaxBatchSize = ConstU32<100>;
+	type MaxCoOwners = ConstU32<16>;
+	type SigningPeriod = ConstU32<{ 7 * DAYS }>;
 	type WeightInfo = pallet_poe::weights::SubstrateWeight<Runtime>;
 }
 
@@ -590,6 +600,35 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_poe_runtime_api::PoeApi<Block, AccountId, BlockNumber> for Runtime {
+		fn claims_of(owner: AccountId, start_after: Option<Vec<u8>>, limit: u32) -> Vec<(Vec<u8>, BlockNumber)> {
+			PalletPoe::claims_of(owner, start_after, limit)
+		}
+
+		fn verify_document(
+			algorithm: pallet_poe::HashAlgorithm,
+			document: Vec<u8>,
+		) -> Option<pallet_poe::ClaimInfo<AccountId, BlockNumber>> {
+			PalletPoe::verify_document(algorithm, &document)
+		}
+
+		fn claim(claim: Vec<u8>) -> Option<pallet_poe::ClaimRecord<AccountId, BlockNumber>> {
+			PalletPoe::claim_record(claim)
+		}
+
+		fn signing_status(claim: Vec<u8>) -> Option<pallet_poe::SigningStatus<AccountId, BlockNumber>> {
+			PalletPoe::signing_status(claim)
+		}
+
+		fn verify_inclusion(
+			root: pallet_poe::hashing::Digest,
+			leaf: pallet_poe::hashing::Digest,
+			proof: Vec<pallet_poe::hashing::Digest>,
+		) -> Option<pallet_poe::AnchorInfo<AccountId, BlockNumber>> {
+			PalletPoe::verify_inclusion(root, leaf, proof)
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn benchmark_metadata(extra: bool) -> (
@@ -1,6 +1,6 @@
 use node_template_runtime::{
-	AccountId, AuraConfig, BalancesConfig, GenesisConfig, GrandpaConfig, Signature, SudoConfig,
-	SystemConfig, WASM_BINARY,
+	AccountId, AuraConfig, BalancesConfig, GenesisConfig, GrandpaConfig, KittiesModuleConfig,
+	OracleConfig, Signature, SudoConfig, SystemConfig, WASM_BINARY,
 };
 use sc_service::ChainType;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
@@ -152,5 +152,19 @@ fn testnet_genesis(
 			key: Some(root_key),
 		},
 		transaction_payment: Default::default(),
+		kitties_module: KittiesModuleConfig {
+			// The off-chain worker of a node started with `--dev` signs with Alice's key.
+			oracle_authorities: vec![get_account_id_from_seed::<sr25519::Public>("Alice")],
+		},
+		oracle: OracleConfig {
+			// The off-chain worker of a node started with `--dev` signs with Alice's key.
+			authorities: vec![get_account_id_from_seed::<sr25519::Public>("Alice")],
+			feeds: vec![(
+				b"BTCUSDT".to_vec(),
+				b"https://data.binance.com/api/v3/avgPrice?symbol=BTCUSDT".to_vec(),
+				b"price".to_vec(),
+				0,
+			)],
+		},
 	}
 }
@@ -87,6 +87,12 @@ pub fn new_partial(
 			Some("//Alice"),
 		)
 		.expect("Creating key with account Alice should succeed.");
+		sp_keystore::Keystore::sr25519_generate_new(
+			&*keystore,
+			node_template_runtime::pallet_oracle::KEY_TYPE,
+			Some("//Alice"),
+		)
+		.expect("Creating key with account Alice should succeed.");
 	}
 
 	let telemetry = telemetry.map(|(worker, telemetry)| {
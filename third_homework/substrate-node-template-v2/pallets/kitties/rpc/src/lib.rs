@@ -0,0 +1,111 @@
+//! RPC interface for the kitties pallet.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use codec::Codec;
+use jsonrpsee::{
+	core::RpcResult,
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::Block as BlockT;
+
+pub use pallet_kitties_runtime_api::{KittiesApi as KittiesRuntimeApi, KittyId};
+
+#[rpc(client, server)]
+pub trait KittiesApi<BlockHash> {
+	/// Walk the ancestry of `kitty_id` up to `depth` generations, returning each kitty with its
+	/// parents.
+	#[method(name = "kitties_ancestors")]
+	fn ancestors(
+		&self,
+		kitty_id: KittyId,
+		depth: u32,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<(KittyId, (KittyId, KittyId))>>;
+
+	/// List the descendants of `kitty_id` up to `depth` generations, returning each kitty with
+	/// its parents.
+	#[method(name = "kitties_descendants")]
+	fn descendants(
+		&self,
+		kitty_id: KittyId,
+		depth: u32,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<(KittyId, (KittyId, KittyId))>>;
+}
+
+/// Provides RPC methods to query the kitties pallet.
+pub struct Kitties<C, Block, AccountId> {
+	client: Arc<C>,
+	_marker: PhantomData<(Block, AccountId)>,
+}
+
+impl<C, Block, AccountId> Kitties<C, Block, AccountId> {
+	/// Create new `Kitties` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+impl<C, Block, AccountId> KittiesApiServer<<Block as BlockT>::Hash> for Kitties<C, Block, AccountId>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: KittiesRuntimeApi<Block, AccountId>,
+	AccountId: Codec + Send + Sync + 'static,
+{
+	fn ancestors(
+		&self,
+		kitty_id: KittyId,
+		depth: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<(KittyId, (KittyId, KittyId))>> {
+		let api = self.client.runtime_api();
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		api.ancestors(at_hash, kitty_id, depth).map_err(|e| {
+			CallError::Custom(ErrorObject::owned(
+				Error::RuntimeError.into(),
+				"Unable to query ancestors.",
+				Some(e.to_string()),
+			))
+			.into()
+		})
+	}
+
+	fn descendants(
+		&self,
+		kitty_id: KittyId,
+		depth: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<(KittyId, (KittyId, KittyId))>> {
+		let api = self.client.runtime_api();
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		api.descendants(at_hash, kitty_id, depth).map_err(|e| {
+			CallError::Custom(ErrorObject::owned(
+				Error::RuntimeError.into(),
+				"Unable to query descendants.",
+				Some(e.to_string()),
+			))
+			.into()
+		})
+	}
+}
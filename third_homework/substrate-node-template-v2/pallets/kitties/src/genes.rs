@@ -0,0 +1,76 @@
+//! Traits of a kitty, decoded from its DNA.
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+pub enum Colour {
+	Black,
+	White,
+	Ginger,
+	Grey,
+	Cream,
+	Blue,
+	Lilac,
+	Cinnamon,
+}
+
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+pub enum Pattern {
+	Solid,
+	Tabby,
+	Spotted,
+	Bicolour,
+	Tortoiseshell,
+	Pointed,
+}
+
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+pub enum EyeShape {
+	Round,
+	Almond,
+	Slanted,
+	Sleepy,
+}
+
+/// The traits expressed by a kitty's DNA.
+///
+/// Each trait is read from its own DNA byte, so breeding passes traits on along with the bytes.
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+pub struct Genes {
+	pub colour: Colour,
+	pub pattern: Pattern,
+	pub eye_shape: EyeShape,
+}
+
+impl Genes {
+	pub fn from_dna(dna: &[u8; 16]) -> Self {
+		const COLOURS: [Colour; 8] = [
+			Colour::Black,
+			Colour::White,
+			Colour::Ginger,
+			Colour::Grey,
+			Colour::Cream,
+			Colour::Blue,
+			Colour::Lilac,
+			Colour::Cinnamon,
+		];
+		const PATTERNS: [Pattern; 6] = [
+			Pattern::Solid,
+			Pattern::Tabby,
+			Pattern::Spotted,
+			Pattern::Bicolour,
+			Pattern::Tortoiseshell,
+			Pattern::Pointed,
+		];
+		const EYE_SHAPES: [EyeShape; 4] =
+			[EyeShape::Round, EyeShape::Almond, EyeShape::Slanted, EyeShape::Sleepy];
+
+		Genes {
+			colour: COLOURS[dna[0] as usize % COLOURS.len()],
+			pattern: PATTERNS[dna[1] as usize % PATTERNS.len()],
+			eye_shape: EYE_SHAPES[dna[2] as usize % EYE_SHAPES.len()],
+		}
+	}
+}
@@ -0,0 +1,75 @@
+//! Implementations of the `nonfungibles` traits, with every kitty an item of a single collection.
+
+use super::*;
+use frame_support::{
+	dispatch::DispatchResult,
+	traits::tokens::nonfungibles::{Inspect, Mutate, Transfer},
+};
+use sp_std::vec::Vec;
+
+/// Attribute key for the DNA, as its 16 raw bytes.
+pub const DNA_KEY: &[u8] = b"dna";
+/// Attribute key for the name, as UTF-8.
+pub const NAME_KEY: &[u8] = b"name";
+/// Attribute key for the generation, as a SCALE-encoded `u32`.
+pub const GENERATION_KEY: &[u8] = b"generation";
+
+impl<T: Config> Inspect<T::AccountId> for Pallet<T> {
+	type ItemId = KittyId;
+	type CollectionId = ();
+
+	fn owner(_collection: &Self::CollectionId, item: &Self::ItemId) -> Option<T::AccountId> {
+		Self::kitty_owner(item)
+	}
+
+	fn attribute(
+		_collection: &Self::CollectionId,
+		item: &Self::ItemId,
+		key: &[u8],
+	) -> Option<Vec<u8>> {
+		let kitty = Self::kitties(item)?;
+		match key {
+			DNA_KEY => Some(kitty.dna.to_vec()),
+			NAME_KEY => Some(kitty.name.to_vec()),
+			GENERATION_KEY => Some(kitty.generation.encode()),
+			_ => None,
+		}
+	}
+
+	/// Kitties being auctioned or held as gifts cannot be transferred.
+	fn can_transfer(_collection: &Self::CollectionId, item: &Self::ItemId) -> bool {
+		Kitties::<T>::contains_key(item) &&
+			!Auctions::<T>::contains_key(item) &&
+			!Gifts::<T>::contains_key(item)
+	}
+}
+
+impl<T: Config> Transfer<T::AccountId> for Pallet<T> {
+	fn transfer(
+		_collection: &Self::CollectionId,
+		item: &Self::ItemId,
+		destination: &T::AccountId,
+	) -> DispatchResult {
+		let owner = Self::kitty_owner(item).ok_or(Error::<T>::InvalidKittyId)?;
+		Self::do_transfer(owner, destination.clone(), *item)
+	}
+}
+
+impl<T: Config> Mutate<T::AccountId> for Pallet<T> {
+	/// Mint the next kitty for free. `item` must be `NextKittyId`.
+	fn mint_into(
+		_collection: &Self::CollectionId,
+		item: &Self::ItemId,
+		who: &T::AccountId,
+	) -> DispatchResult {
+		Self::do_mint(who, *item)
+	}
+
+	fn burn(
+		_collection: &Self::CollectionId,
+		item: &Self::ItemId,
+		maybe_check_owner: Option<&T::AccountId>,
+	) -> DispatchResult {
+		Self::do_burn(*item, maybe_check_owner)
+	}
+}
@@ -3,9 +3,14 @@
 /// Edit this file to define custom logic or remove it if it is not needed.
 /// Learn more about FRAME and the core library of Substrate FRAME pallets:
 /// <https://docs.substrate.io/reference/frame-pallets/>
+pub use genes::Genes;
+pub use price::BtcPriceFeed;
 pub use pallet::*;
 
+pub mod genes;
+mod impl_nonfungibles;
 mod migrations;
+pub mod price;
 
 #[cfg(test)]
 mod mock;
@@ -14,12 +19,12 @@ mod tests;
 
 use codec::{Decode, Encode};
 use frame_system::offchain::{
-	AppCrypto, CreateSignedTransaction, SendUnsignedTransaction, SignedPayload, Signer,
-	SigningTypes,
+	AppCrypto, CreateSignedTransaction, SendSignedTransaction, SendUnsignedTransaction,
+	SignedPayload, Signer, SigningTypes, SubmitTransaction,
 };
 use sp_runtime::{
 	transaction_validity::{InvalidTransaction, TransactionValidity, ValidTransaction},
-	RuntimeDebug,
+	RuntimeAppPublic, RuntimeDebug,
 };
 
 use sp_core::crypto::KeyTypeId;
@@ -60,26 +65,116 @@ pub mod pallet {
 	use frame_support::pallet_prelude::*;
 	use frame_system::pallet_prelude::{BlockNumberFor, *};
 
-	use frame_support::traits::{Currency, ExistenceRequirement, Randomness, StorageVersion};
+	use frame_support::traits::{
+		BalanceStatus, Currency, ExistenceRequirement, Randomness, ReservableCurrency,
+		StorageVersion,
+	};
+	use frame_support::storage::with_storage_layer;
 	use frame_support::PalletId;
 	use sp_io::hashing::blake2_128;
+	use sp_std::collections::btree_set::BTreeSet;
 
 	use serde::{Deserialize, Deserializer};
 	use sp_runtime::offchain::storage::StorageValueRef;
 	use sp_runtime::offchain::{http, Duration};
-	use sp_runtime::traits::AccountIdConversion;
+	use sp_runtime::traits::{
+		AccountIdConversion, CheckedDiv, Hash, IdentifyAccount, One, Saturating, Zero,
+	};
+	use sp_runtime::{FixedPointNumber, FixedU128, SaturatedConversion};
 
 	pub type KittyId = u32;
+
+	/// The most generations `ancestors` and `descendants` walk.
+	pub const MAX_LINEAGE_DEPTH: u32 = 16;
 	pub type BalanceOf<T> =
 		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 
+	pub type KittyName<T> = BoundedVec<u8, <T as Config>::MaxNameLength>;
+
 	#[derive(
-		Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq, Default, TypeInfo, MaxEncodedLen,
+		Encode,
+		Decode,
+		CloneNoBound,
+		RuntimeDebugNoBound,
+		PartialEqNoBound,
+		EqNoBound,
+		TypeInfo,
+		MaxEncodedLen,
 	)]
+	#[scale_info(skip_type_params(T))]
+	#[codec(mel_bound())]
 	// pub struct Kitty(pub [u8; 16]);
-	pub struct Kitty {
+	pub struct Kitty<T: Config> {
 		pub dna: [u8; 16],
-		pub name: [u8; 8],
+		/// Non-empty UTF-8.
+		pub name: KittyName<T>,
+		/// 0 for created kitties, one more than the older parent for bred ones.
+		pub generation: u32,
+		/// The kitty cannot breed before this block.
+		pub breeding_cooldown_until: T::BlockNumber,
+	}
+
+	/// What happens to the price paid for a new kitty.
+	#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+	pub enum PriceMode {
+		/// The price is paid to the pallet account.
+		Payment,
+		/// The price is reserved against the owner and released when the kitty is burned.
+		Deposit,
+	}
+
+	/// How the off-chain worker submits the prices it fetches.
+	#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+	pub enum SubmissionMode {
+		/// Signed transactions from every local oracle authority key, which pay fees.
+		Signed,
+		/// Unsigned transactions carrying a payload signed by an oracle authority.
+		UnsignedWithSignedPayload,
+		/// Unsigned transactions that nothing authenticates, at most one per round. The pool
+		/// only takes them from the local off-chain worker and does not gossip them, so only
+		/// block authors get theirs included.
+		Unsigned,
+	}
+
+	pub type AuctionOf<T> = Auction<
+		<T as frame_system::Config>::AccountId,
+		BalanceOf<T>,
+		<T as frame_system::Config>::BlockNumber,
+	>;
+
+	/// An English auction of a kitty. The kitty stays with the seller until it is settled.
+	#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+	pub struct Auction<AccountId, Balance, BlockNumber> {
+		pub seller: AccountId,
+		/// The lowest bid that can win.
+		pub reserve: Balance,
+		/// The auction is settled at the start of this block.
+		pub end: BlockNumber,
+		/// The highest bidder and their bid, which is reserved from them.
+		pub highest_bid: Option<(AccountId, Balance)>,
+	}
+
+	pub type GiftOf<T> = Gift<
+		<T as frame_system::Config>::AccountId,
+		<T as frame_system::Config>::Hash,
+		<T as frame_system::Config>::BlockNumber,
+	>;
+
+	/// A kitty held by the pallet account until the recipient reveals the claim code behind
+	/// `secret_hash`.
+	#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+	pub struct Gift<AccountId, Hash, BlockNumber> {
+		pub sender: AccountId,
+		/// `T::Hashing` of the claim code and the recipient's account.
+		pub secret_hash: Hash,
+		/// The gift can no longer be claimed from this block, only reclaimed by the sender.
+		pub expiry: BlockNumber,
+	}
+
+	impl<T: Config> Kitty<T> {
+		pub fn genes(&self) -> Genes {
+			Genes::from_dna(&self.dna)
+		}
 	}
 
 	#[derive(Deserialize, Encode, Decode, Clone, PartialEq, Eq, scale_info::TypeInfo)]
@@ -109,24 +204,41 @@ pub mod pallet {
 		}
 	}
 
+	/// A price signed by an oracle authority, and the block its off-chain worker fetched it in.
 	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
-	pub struct Payload<Public> {
-		price: Vec<u8>,
-		public: Public,
+	pub struct Payload<Public, BlockNumber> {
+		pub price: Vec<u8>,
+		pub block_number: BlockNumber,
+		pub public: Public,
 	}
 
-	impl<T: SigningTypes> SignedPayload<T> for Payload<T::Public> {
+	impl<T: SigningTypes> SignedPayload<T> for Payload<T::Public, T::BlockNumber> {
 		fn public(&self) -> T::Public {
 			self.public.clone()
 		}
 	}
 
 	#[derive(Debug, Deserialize, Encode, Decode, Default)]
-	struct IndexingData(Vec<u8>, [u8; 8]);
+	struct IndexingData(Vec<u8>, Vec<u8>);
+
+	/// What the off-chain worker remembers about an account it signs price transactions with.
+	#[derive(Encode, Decode, Default, RuntimeDebug)]
+	struct SignerState<Index, BlockNumber> {
+		/// The account nonce once the last transaction the pool accepted is included, and the
+		/// block it was sent in.
+		pending: Option<(Index, BlockNumber)>,
+		/// How many transactions in a row the pool rejected.
+		failures: u32,
+		/// After a rejection, the block before which the account does not send again.
+		retry_at: BlockNumber,
+	}
 
 	const ONCHAIN_TX_KEY: &[u8] = b"ocw-kitties::storage::tx";
+	const SIGNER_STATE_KEY: &[u8] = b"ocw-kitties::signer/";
+	/// A signer that keeps being rejected waits at most `2^MAX_BACKOFF_EXPONENT` rounds.
+	const MAX_BACKOFF_EXPONENT: u32 = 5;
 
-	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(7);
 
 	#[pallet::pallet]
 	#[pallet::storage_version(STORAGE_VERSION)]
@@ -138,9 +250,69 @@ pub mod pallet {
 		/// Because this pallet emits events, it depends on the runtime's definition of an event.
 		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
 		type Randomness: Randomness<Self::Hash, Self::BlockNumber>;
-		type Currency: Currency<Self::AccountId>;
+		type Currency: ReservableCurrency<Self::AccountId>;
+		/// The price of a kitty when there is no fresh BTC price.
 		#[pallet::constant]
 		type KittyPrice: Get<BalanceOf<Self>>;
+		/// The price of a kitty in USD, converted to BTC at `LatestBtcPrice` and from BTC to
+		/// native units at `NativeTokensPerBtc`.
+		#[pallet::constant]
+		type KittyPriceUsd: Get<FixedU128>;
+		/// How many whole native tokens one BTC is worth. The native token has no market of its
+		/// own, so it is valued against BTC at this fixed rate.
+		#[pallet::constant]
+		type NativeTokensPerBtc: Get<FixedU128>;
+		/// The number of native units in one whole token.
+		#[pallet::constant]
+		type NativeUnit: Get<BalanceOf<Self>>;
+		/// An aggregated BTC price, such as an oracle pallet's, preferred over `LatestBtcPrice`.
+		type BtcPriceFeed: BtcPriceFeed<Self::BlockNumber>;
+		/// How long a BTC price is used for before falling back to `KittyPrice`.
+		#[pallet::constant]
+		type PriceStaleAfter: Get<Self::BlockNumber>;
+		/// The lowest BTC price an oracle report may carry.
+		#[pallet::constant]
+		type MinBtcPrice: Get<FixedU128>;
+		/// The highest BTC price an oracle report may carry.
+		#[pallet::constant]
+		type MaxBtcPrice: Get<FixedU128>;
+		/// Whether the kitty price is paid to the pallet or reserved as a deposit.
+		#[pallet::constant]
+		type PriceMode: Get<PriceMode>;
+		/// The price kitties were paid for before storage version 5, which its migration refunds
+		/// and reserves as their deposit. It must stay the price charged before that upgrade.
+		#[pallet::constant]
+		type LegacyKittyPrice: Get<BalanceOf<Self>>;
+		/// How long both parents and the child must wait before breeding again.
+		#[pallet::constant]
+		type BreedingCooldownPeriod: Get<Self::BlockNumber>;
+		/// The maximum number of kitties a single account can own.
+		#[pallet::constant]
+		type MaxKittiesOwned: Get<u32>;
+		/// The longest a kitty name can be, in bytes.
+		#[pallet::constant]
+		type MaxNameLength: Get<u32>;
+		/// Whether two kitties can share a name. `KittyByName` is only kept when they cannot.
+		#[pallet::constant]
+		type UniqueNames: Get<bool>;
+		/// The longest an auction can run.
+		#[pallet::constant]
+		type MaxAuctionDuration: Get<Self::BlockNumber>;
+		/// A bid this close to the end pushes the end back to this long after the bid.
+		#[pallet::constant]
+		type AuctionExtension: Get<Self::BlockNumber>;
+		/// The maximum number of auctions that can end in the same block.
+		#[pallet::constant]
+		type MaxAuctionsPerBlock: Get<u32>;
+		/// The most accounts `OracleAuthorities` can hold.
+		#[pallet::constant]
+		type MaxOracleAuthorities: Get<u32>;
+		/// Each oracle authority can report one price per round of this many blocks.
+		#[pallet::constant]
+		type OracleSubmissionInterval: Get<Self::BlockNumber>;
+		/// How the off-chain worker submits prices. Only that kind of submission is accepted.
+		#[pallet::constant]
+		type SubmissionMode: Get<SubmissionMode>;
 		type PalletId: Get<PalletId>;
 		/// The identifier type for an offchain worker.
 		type AuthorityId: AppCrypto<Self::Public, Self::Signature>;
@@ -154,20 +326,134 @@ pub mod pallet {
 
 	#[pallet::storage]
 	#[pallet::getter(fn kitties)]
-	pub type Kitties<T: Config> = StorageMap<_, Blake2_128Concat, KittyId, Kitty>;
+	pub type Kitties<T: Config> = StorageMap<_, Blake2_128Concat, KittyId, Kitty<T>>;
 
 	#[pallet::storage]
 	#[pallet::getter(fn kitty_owner)]
 	pub type KittyOwner<T: Config> = StorageMap<_, Blake2_128Concat, KittyId, T::AccountId>;
 
+	/// The kitties owned by each account, kept in sync with `KittyOwner`.
+	#[pallet::storage]
+	#[pallet::getter(fn owned_kitties)]
+	pub type OwnedKitties<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<KittyId, T::MaxKittiesOwned>,
+		ValueQuery,
+	>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn kitty_on_sale)]
-	pub type KittyOnSale<T: Config> = StorageMap<_, Blake2_128Concat, KittyId, ()>;
+	/// The asking price of every listed kitty.
+	pub type KittyOnSale<T: Config> = StorageMap<_, Blake2_128Concat, KittyId, BalanceOf<T>>;
 
 	#[pallet::storage]
 	#[pallet::getter(fn kitty_parents)]
 	pub type KittyParents<T: Config> = StorageMap<_, Blake2_128Concat, KittyId, (KittyId, KittyId)>;
 
+	/// The account allowed to transfer each kitty on its owner's behalf.
+	#[pallet::storage]
+	#[pallet::getter(fn kitty_approval)]
+	pub type KittyApprovals<T: Config> = StorageMap<_, Blake2_128Concat, KittyId, T::AccountId>;
+
+	/// Operators allowed to transfer every kitty of an owner, keyed by owner then operator.
+	#[pallet::storage]
+	pub type OperatorApprovals<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Blake2_128Concat, T::AccountId, ()>;
+
+	/// The USD price of one BTC reported by the off-chain worker, and the block it was reported
+	/// in.
+	#[pallet::storage]
+	#[pallet::storage_prefix = "LatestPrice"]
+	#[pallet::getter(fn latest_btc_price)]
+	pub type LatestBtcPrice<T: Config> = StorageValue<_, (FixedU128, T::BlockNumber)>;
+
+	/// The accounts allowed to report `LatestBtcPrice` from the off-chain worker.
+	#[pallet::storage]
+	#[pallet::getter(fn oracle_authorities)]
+	pub type OracleAuthorities<T: Config> =
+		StorageValue<_, BoundedVec<T::AccountId, T::MaxOracleAuthorities>, ValueQuery>;
+
+	/// The round of the last price accepted from each oracle authority.
+	#[pallet::storage]
+	#[pallet::getter(fn last_oracle_round)]
+	pub type LastOracleRound<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, T::BlockNumber>;
+
+	/// The round of the last price accepted in a pure unsigned transaction.
+	#[pallet::storage]
+	pub type LastUnsignedRound<T: Config> = StorageValue<_, T::BlockNumber>;
+
+	/// The fee for breeding with each kitty offered as a stud.
+	#[pallet::storage]
+	#[pallet::getter(fn stud_fee)]
+	pub type StudFees<T: Config> = StorageMap<_, Blake2_128Concat, KittyId, BalanceOf<T>>;
+
+	/// Kitties held in escrow by the pallet account as gifts.
+	#[pallet::storage]
+	#[pallet::getter(fn gifts)]
+	pub type Gifts<T: Config> = StorageMap<_, Blake2_128Concat, KittyId, GiftOf<T>>;
+
+	/// The gifted kitty for each claim code hash.
+	#[pallet::storage]
+	pub type GiftCodes<T: Config> = StorageMap<_, Blake2_128Concat, T::Hash, KittyId>;
+
+	/// The kitty holding each name, kept only when `UniqueNames` is set.
+	#[pallet::storage]
+	#[pallet::getter(fn kitty_by_name)]
+	pub type KittyByName<T: Config> = StorageMap<_, Blake2_128Concat, KittyName<T>, KittyId>;
+
+	/// The deposit reserved against the owner of each kitty in `PriceMode::Deposit`.
+	#[pallet::storage]
+	#[pallet::getter(fn kitty_deposit)]
+	pub type KittyDeposit<T: Config> = StorageMap<_, Blake2_128Concat, KittyId, BalanceOf<T>>;
+
+	/// The next kitty the version 5 migration gives a deposit to, while it is still running.
+	#[pallet::storage]
+	pub type DepositMigrationCursor<T: Config> = StorageValue<_, KittyId>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn auctions)]
+	pub type Auctions<T: Config> = StorageMap<_, Blake2_128Concat, KittyId, AuctionOf<T>>;
+
+	/// The auctions to settle at the start of each block.
+	#[pallet::storage]
+	pub type AuctionsEnding<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::BlockNumber,
+		BoundedVec<KittyId, T::MaxAuctionsPerBlock>,
+		ValueQuery,
+	>;
+
+	/// Reverse index of `KittyParents`, keyed by parent then child.
+	#[pallet::storage]
+	pub type KittyChildren<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, KittyId, Blake2_128Concat, KittyId, ()>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		pub oracle_authorities: Vec<T::AccountId>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self { oracle_authorities: Vec::new() }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			let authorities =
+				BoundedVec::<_, T::MaxOracleAuthorities>::try_from(self.oracle_authorities.clone())
+					.expect("too many genesis oracle authorities");
+			OracleAuthorities::<T>::put(authorities);
+		}
+	}
+
 	// Pallets use events to inform users when important changes are made.
 	// https://docs.substrate.io/main-docs/build/events-errors/
 	#[pallet::event]
@@ -178,12 +464,12 @@ pub mod pallet {
 		KittyCreated {
 			who: T::AccountId,
 			kitty_id: KittyId,
-			kitty: Kitty,
+			kitty: Kitty<T>,
 		},
 		KittyBred {
 			who: T::AccountId,
 			kitty_id: KittyId,
-			kitty: Kitty,
+			kitty: Kitty<T>,
 		},
 		KittyTransferred {
 			who: T::AccountId,
@@ -193,10 +479,118 @@ pub mod pallet {
 		KittyOnSale {
 			who: T::AccountId,
 			kitty_id: KittyId,
+			price: BalanceOf<T>,
+		},
+		/// The owner changed the asking price of a listed kitty.
+		KittyPriceUpdated {
+			who: T::AccountId,
+			kitty_id: KittyId,
+			price: BalanceOf<T>,
+		},
+		/// A listing was withdrawn, by the owner or by a transfer.
+		KittySaleCancelled {
+			who: T::AccountId,
+			kitty_id: KittyId,
 		},
 		KittyBought {
 			who: T::AccountId,
+			seller: T::AccountId,
+			kitty_id: KittyId,
+			price: BalanceOf<T>,
+		},
+		/// `operator` may now transfer the kitty, or nobody may if it is `None`.
+		Approval {
+			owner: T::AccountId,
+			kitty_id: KittyId,
+			operator: Option<T::AccountId>,
+		},
+		/// `operator` may now transfer every kitty of `owner`, or no longer may.
+		ApprovalForAll {
+			owner: T::AccountId,
+			operator: T::AccountId,
+			approved: bool,
+		},
+		/// The off-chain worker reported a new `LatestBtcPrice`.
+		PriceReported {
+			price: FixedU128,
+		},
+		OracleAuthorityAdded {
+			who: T::AccountId,
+		},
+		OracleAuthorityRemoved {
+			who: T::AccountId,
+		},
+		/// Anyone may now breed with the kitty for `fee`.
+		StudOffered {
+			who: T::AccountId,
+			kitty_id: KittyId,
+			fee: BalanceOf<T>,
+		},
+		StudWithdrawn {
+			who: T::AccountId,
+			kitty_id: KittyId,
+		},
+		/// `who` paid `fee` to `owner` to breed with the stud `kitty_id`.
+		StudFeePaid {
+			who: T::AccountId,
+			owner: T::AccountId,
+			kitty_id: KittyId,
+			fee: BalanceOf<T>,
+		},
+		/// A kitty was put in escrow, claimable until `expiry`.
+		GiftCreated {
+			who: T::AccountId,
+			kitty_id: KittyId,
+			expiry: T::BlockNumber,
+		},
+		GiftClaimed {
+			who: T::AccountId,
+			kitty_id: KittyId,
+		},
+		/// An expired gift was taken back by its sender, who gave it to `recipient`.
+		GiftReclaimed {
+			who: T::AccountId,
+			kitty_id: KittyId,
+			recipient: T::AccountId,
+		},
+		KittyRenamed {
+			who: T::AccountId,
+			kitty_id: KittyId,
+			name: KittyName<T>,
+		},
+		/// A kitty was destroyed and its deposit, if any, released to the owner.
+		KittyBurned {
+			who: T::AccountId,
+			kitty_id: KittyId,
+			deposit: BalanceOf<T>,
+		},
+		AuctionStarted {
+			who: T::AccountId,
+			kitty_id: KittyId,
+			reserve: BalanceOf<T>,
+			end: T::BlockNumber,
+		},
+		/// A bid was placed. `end` is later than before if the bid extended the auction.
+		BidPlaced {
+			who: T::AccountId,
+			kitty_id: KittyId,
+			amount: BalanceOf<T>,
+			end: T::BlockNumber,
+		},
+		/// An auction ended. `winner` is `None` if the kitty went back to the seller.
+		AuctionSettled {
+			kitty_id: KittyId,
+			seller: T::AccountId,
+			winner: Option<T::AccountId>,
+			price: Option<BalanceOf<T>>,
+		},
+		/// The highest bid of an auction could not be settled. The bid was refunded and the kitty
+		/// went back to the seller.
+		AuctionSettlementFailed {
 			kitty_id: KittyId,
+			bidder: T::AccountId,
+			amount: BalanceOf<T>,
+			error: DispatchError,
 		},
 	}
 
@@ -210,12 +604,85 @@ pub mod pallet {
 		NoOwner,
 		AlreadyOwned,
 		NotOnSale,
+		/// A parent is still recovering from its last breeding.
+		BreedingCooldown,
+		/// The kitties are siblings, or one is the parent of the other.
+		InbreedingNotAllowed,
+		/// The account already owns `MaxKittiesOwned` kitties.
+		TooManyKitties,
+		/// The asking price is above the buyer's `max_price`.
+		PriceTooHigh,
+		/// The caller is neither the owner nor approved for the kitty.
+		NotApproved,
+		/// An owner cannot approve themselves.
+		ApproveToOwner,
+		/// The reported price is not a positive decimal number.
+		InvalidPrice,
+		/// The account is not in `OracleAuthorities`.
+		NotOracleAuthority,
+		/// The account is already in `OracleAuthorities`.
+		AlreadyOracleAuthority,
+		/// `OracleAuthorities` already holds `MaxOracleAuthorities` accounts.
+		TooManyOracleAuthorities,
+		/// The oracle authority already reported a price in this round.
+		OracleRateLimited,
+		/// Prices are not submitted this way under the configured `SubmissionMode`.
+		WrongSubmissionMode,
+		/// The kitty is not offered as a stud.
+		NotAStud,
+		/// The kitty is held in escrow as a gift.
+		KittyGifted,
+		/// Another gift already uses this claim code.
+		GiftCodeTaken,
+		/// No gift matches the claim code, or the kitty is not a gift.
+		GiftNotFound,
+		/// The expiry is not in the future.
+		InvalidGiftExpiry,
+		/// The gift has expired and can only be reclaimed.
+		GiftExpired,
+		/// The gift can still be claimed.
+		GiftNotExpired,
+		/// The name is empty or not valid UTF-8.
+		InvalidName,
+		/// Another kitty already has this name.
+		NameTaken,
+		/// The kitty is being auctioned.
+		KittyInAuction,
+		/// The kitty is not being auctioned.
+		NotInAuction,
+		/// The duration is zero or longer than `MaxAuctionDuration`.
+		InvalidAuctionDuration,
+		/// The bid is below the reserve or not above the highest bid.
+		BidTooLow,
+		/// `MaxAuctionsPerBlock` auctions already end in that block.
+		TooManyAuctions,
+		/// The winning bid is no longer fully reserved.
+		BidNotReserved,
+		/// The kitty's deposit is no longer fully reserved by its owner.
+		DepositNotReserved,
 	}
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			let ending = AuctionsEnding::<T>::take(now);
+			for kitty_id in ending.iter() {
+				Self::settle_auction(*kitty_id);
+			}
+
+			let settled = ending.len() as u64;
+			T::DbWeight::get()
+				.reads_writes(1 + 3 * settled, 1 + 6 * settled)
+				.saturating_add(migrations::v5::step::<T>())
+		}
+
 		fn on_runtime_upgrade() -> Weight {
 			migrations::v2::migrate::<T>()
+				.saturating_add(migrations::v3::migrate::<T>())
+				.saturating_add(migrations::v4::migrate::<T>())
+				.saturating_add(migrations::v5::migrate::<T>())
+				.saturating_add(migrations::v6::migrate::<T>())
+				.saturating_add(migrations::v7::migrate::<T>())
 		}
 
 		fn offchain_worker(block_number: T::BlockNumber) {
@@ -231,31 +698,11 @@ pub mod pallet {
 				Ok(info) => {
 					log::info!("OCW ==> BTC Info: {:?}", info);
 
-					// Retrieve the signer to sign the payload
-					let signer = Signer::<T, T::AuthorityId>::any_account();
-					if let Some((_, res)) = signer.send_unsigned_transaction(
-						// this line is to prepare and return payload
-						|acct| Payload { price: info.price.clone(), public: acct.public.clone() },
-						|payload, signature| Call::unsigned_extrinsic_with_signed_payload {
-							payload,
-							signature,
-						},
-					) {
-						match res {
-							Ok(()) => {
-								log::info!(
-									"OCW ==> unsigned tx with signed payload successfully sent."
-								);
-							},
-							Err(()) => {
-								log::error!(
-									"OCW ==> sending unsigned tx with signed payload failed."
-								);
-							},
-						};
-					} else {
-						// The case of `None`: no account is available for sending
-						log::error!("OCW ==> No local account available");
+					match T::SubmissionMode::get() {
+						SubmissionMode::Signed => Self::send_signed(block_number, info.price),
+						SubmissionMode::UnsignedWithSignedPayload =>
+							Self::send_unsigned_with_signed_payload(block_number, info.price),
+						SubmissionMode::Unsigned => Self::send_unsigned(block_number, info.price),
 					}
 				},
 				Err(e) => {
@@ -274,29 +721,30 @@ pub mod pallet {
 		/// storage and emits an event. This function must be dispatched by a signed extrinsic.
 		#[pallet::call_index(0)]
 		#[pallet::weight(10_000)]
-		pub fn create(origin: OriginFor<T>, name: [u8; 8]) -> DispatchResult {
+		pub fn create(origin: OriginFor<T>, name: KittyName<T>) -> DispatchResult {
 			// Check that the extrinsic was signed and get the signer.
 			// This function will return an error if the extrinsic is not signed.
 			// https://docs.substrate.io/main-docs/build/origins/
 			let who = ensure_signed(origin)?;
 
+			Self::ensure_valid_name(&name)?;
 			let kitty_id = Self::get_next_id()?;
-			let kitty = Kitty { dna: Self::random_value(&who), name };
+			let kitty = Kitty {
+				dna: Self::random_value(&who),
+				name,
+				generation: 0,
+				breeding_cooldown_until: Zero::zero(),
+			};
 
-			let price = T::KittyPrice::get();
-			T::Currency::transfer(
-				&who,
-				&Self::get_account_id(),
-				price,
-				ExistenceRequirement::KeepAlive,
-			)?;
-			// T::Currency::reserve(&who, price)?;
+			Self::charge_kitty_price(&who, kitty_id)?;
 
-			Kitties::<T>::insert(kitty_id, kitty);
+			Self::index_name(&kitty.name, kitty_id);
+			Kitties::<T>::insert(kitty_id, kitty.clone());
 			KittyOwner::<T>::insert(kitty_id, &who);
+			Self::add_owned_kitty(&who, kitty_id)?;
 
 			let key = Self::derived_key(frame_system::Pallet::<T>::block_number());
-			let data = IndexingData(b"submit_name".to_vec(), name);
+			let data = IndexingData(b"submit_name".to_vec(), kitty.name.to_vec());
 			sp_io::offchain_index::set(&key, &data.encode());
 
 			// Emit an event.
@@ -305,13 +753,14 @@ pub mod pallet {
 			Ok(())
 		}
 
+		/// Breed a new kitty from two kitties owned by the caller.
 		#[pallet::call_index(1)]
 		#[pallet::weight(10_000)]
 		pub fn breed(
 			origin: OriginFor<T>,
 			kitty_id_1: KittyId,
 			kitty_id_2: KittyId,
-			name: [u8; 8],
+			name: KittyName<T>,
 		) -> DispatchResult {
 			// Check that the extrinsic was signed and get the signer.
 			// This function will return an error if the extrinsic is not signed.
@@ -319,40 +768,10 @@ pub mod pallet {
 			let who = ensure_signed(origin)?;
 
 			ensure!(kitty_id_1 != kitty_id_2, Error::<T>::SameKittyId);
+			Self::ensure_owner(&who, kitty_id_1)?;
+			Self::ensure_owner(&who, kitty_id_2)?;
 
-			ensure!(Kitties::<T>::contains_key(kitty_id_1), Error::<T>::InvalidKittyId);
-			ensure!(Kitties::<T>::contains_key(kitty_id_2), Error::<T>::InvalidKittyId);
-
-			let kitty_id = Self::get_next_id()?;
-
-			let _kitty_1 = Self::kitties(kitty_id_1).ok_or(Error::<T>::InvalidKittyId)?;
-			let _kitty_2 = Self::kitties(kitty_id_2).ok_or(Error::<T>::InvalidKittyId)?;
-
-			let _selector = Self::random_value(&who);
-			let dna = [0u8; 16];
-			// for i in 0..kitty_1.0.len() {
-			// 	data[i] = (kitty_1.0[i] & selector[i]) | (kitty_2.0[i] & !selector[i]);
-			// }
-
-			let kitty = Kitty { dna, name };
-
-			let price = T::KittyPrice::get();
-			T::Currency::transfer(
-				&who,
-				&Self::get_account_id(),
-				price,
-				ExistenceRequirement::KeepAlive,
-			)?;
-			// T::Currency::reserve(&who, price)?;
-
-			Kitties::<T>::insert(kitty_id, kitty);
-			KittyOwner::<T>::insert(kitty_id, &who);
-			KittyParents::<T>::insert(kitty_id, (kitty_id_1, kitty_id_2));
-
-			// Emit an event.
-			Self::deposit_event(Event::KittyBred { who, kitty_id, kitty });
-			// Return a successful DispatchResultWithPostInfo
-			Ok(())
+			Self::do_breed(who, kitty_id_1, kitty_id_2, name)
 		}
 
 		#[pallet::call_index(2)]
@@ -363,38 +782,39 @@ pub mod pallet {
 			kitty_id: KittyId,
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
-			ensure!(Kitties::<T>::contains_key(kitty_id), Error::<T>::InvalidKittyId);
-
-			let owner = Self::kitty_owner(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
-			ensure!(owner == who, Error::<T>::NotOwner);
-
-			KittyOwner::<T>::insert(kitty_id, &recipient);
 
-			// Emit an event.
-			Self::deposit_event(Event::KittyTransferred { who, recipient, kitty_id });
-			// Return a successful DispatchResultWithPostInfo
-			Ok(())
+			Self::do_transfer(who, recipient, kitty_id)
 		}
 
 		#[pallet::call_index(3)]
 		#[pallet::weight(10_000)]
-		pub fn sale(origin: OriginFor<T>, kitty_id: KittyId) -> DispatchResult {
+		pub fn sale(
+			origin: OriginFor<T>,
+			kitty_id: KittyId,
+			price: BalanceOf<T>,
+		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 
 			Self::kitties(kitty_id).ok_or::<DispatchError>(Error::<T>::InvalidKittyId.into())?;
 
 			ensure!(Self::kitty_owner(kitty_id) == Some(who.clone()), Error::<T>::NotOwner);
 			ensure!(Self::kitty_on_sale(kitty_id).is_none(), Error::<T>::AlreadyOnSale);
+			ensure!(!Auctions::<T>::contains_key(kitty_id), Error::<T>::KittyInAuction);
 
-			KittyOnSale::<T>::insert(kitty_id, ());
-			Self::deposit_event(Event::KittyOnSale { who, kitty_id });
+			KittyOnSale::<T>::insert(kitty_id, price);
+			Self::deposit_event(Event::KittyOnSale { who, kitty_id, price });
 
 			Ok(())
 		}
 
+		/// Buy a listed kitty at its asking price, failing if that is above `max_price`.
 		#[pallet::call_index(4)]
 		#[pallet::weight(10_000)]
-		pub fn buy(origin: OriginFor<T>, kitty_id: KittyId) -> DispatchResult {
+		pub fn buy(
+			origin: OriginFor<T>,
+			kitty_id: KittyId,
+			max_price: BalanceOf<T>,
+		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 
 			Self::kitties(kitty_id).ok_or::<DispatchError>(Error::<T>::InvalidKittyId.into())?;
@@ -402,122 +822,1175 @@ pub mod pallet {
 			let owner =
 				Self::kitty_owner(kitty_id).ok_or::<DispatchError>(Error::<T>::NoOwner.into())?;
 			ensure!(owner != who, Error::<T>::AlreadyOwned);
-			ensure!(Self::kitty_on_sale(kitty_id).is_some(), Error::<T>::NotOnSale);
+			let price = Self::kitty_on_sale(kitty_id).ok_or(Error::<T>::NotOnSale)?;
+			ensure!(price <= max_price, Error::<T>::PriceTooHigh);
 
-			let price = T::KittyPrice::get();
 			// T::Currency::reserve(&who, price)?;
 			// T::Currency::unreserve(&owner, price);
 			T::Currency::transfer(&who, &owner, price, ExistenceRequirement::KeepAlive)?;
+			Self::move_deposit(kitty_id, &owner, &who)?;
 
 			KittyOwner::<T>::insert(kitty_id, &who);
+			KittyApprovals::<T>::remove(kitty_id);
+			StudFees::<T>::remove(kitty_id);
+			Self::remove_owned_kitty(&owner, kitty_id);
+			Self::add_owned_kitty(&who, kitty_id)?;
 			KittyOnSale::<T>::remove(kitty_id);
 
-			Self::deposit_event(Event::KittyBought { who, kitty_id });
+			Self::deposit_event(Event::KittyBought { who, seller: owner, kitty_id, price });
 
 			Ok(())
 		}
 
+		/// Store the price fetched by the off-chain worker of an oracle authority as
+		/// `LatestBtcPrice`. The signature is checked in `validate_unsigned`.
 		#[pallet::call_index(5)]
-		#[pallet::weight(0)]
+		#[pallet::weight(10_000)]
 		pub fn unsigned_extrinsic_with_signed_payload(
 			origin: OriginFor<T>,
-			payload: Payload<T::Public>,
+			payload: Payload<T::Public, T::BlockNumber>,
 			_signature: T::Signature,
 		) -> DispatchResult {
 			ensure_none(origin)?;
+			ensure!(
+				T::SubmissionMode::get() == SubmissionMode::UnsignedWithSignedPayload,
+				Error::<T>::WrongSubmissionMode
+			);
 
 			log::info!("OCW ==> in call unsigned_extrinsic_with_signed_payload: {:?}", payload);
-			// Return a successful DispatchResultWithPostInfo
-			Ok(())
+			let who = payload.public.clone().into_account();
+			Self::do_report_price(&who, payload.block_number, &payload.price)
 		}
-	}
 
-	impl<T: Config> Pallet<T> {
-		fn get_next_id() -> Result<KittyId, DispatchError> {
-			NextKittyId::<T>::try_mutate(|next_id| -> Result<KittyId, DispatchError> {
-				let current_id = *next_id;
-				*next_id = next_id
-					.checked_add(1)
-					.ok_or::<DispatchError>(Error::<T>::InvalidKittyId.into())?;
-				Ok(current_id)
-			})
-		}
+		#[pallet::call_index(6)]
+		#[pallet::weight(10_000)]
+		pub fn update_price(
+			origin: OriginFor<T>,
+			kitty_id: KittyId,
+			price: BalanceOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
 
-		fn random_value(sender: &T::AccountId) -> [u8; 16] {
-			let payload = (
-				T::Randomness::random_seed(),
-				&sender,
-				<frame_system::Pallet<T>>::extrinsic_index(),
-			);
+			ensure!(Self::kitty_owner(kitty_id) == Some(who.clone()), Error::<T>::NotOwner);
+			KittyOnSale::<T>::try_mutate(kitty_id, |listing| -> DispatchResult {
+				let current = listing.as_mut().ok_or(Error::<T>::NotOnSale)?;
+				*current = price;
+				Ok(())
+			})?;
 
-			payload.using_encoded(blake2_128)
-		}
+			Self::deposit_event(Event::KittyPriceUpdated { who, kitty_id, price });
 
-		fn get_account_id() -> T::AccountId {
-			T::PalletId::get().into_account_truncating()
+			Ok(())
 		}
-	}
 
-	impl<T: Config> Pallet<T> {
-		fn derived_key(block_number: T::BlockNumber) -> Vec<u8> {
-			block_number.using_encoded(|encoded_bn| {
-				ONCHAIN_TX_KEY
-					.clone()
-					.into_iter()
-					.chain(b"/".into_iter())
-					.chain(encoded_bn)
-					.copied()
-					.collect::<Vec<u8>>()
-			})
+		#[pallet::call_index(7)]
+		#[pallet::weight(10_000)]
+		pub fn cancel_sale(origin: OriginFor<T>, kitty_id: KittyId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(Self::kitty_owner(kitty_id) == Some(who.clone()), Error::<T>::NotOwner);
+			KittyOnSale::<T>::take(kitty_id).ok_or(Error::<T>::NotOnSale)?;
+
+			Self::deposit_event(Event::KittySaleCancelled { who, kitty_id });
+
+			Ok(())
 		}
-	}
 
-	impl<T: Config> Pallet<T> {
-		fn fetch_btc_info() -> Result<BtcInfo, http::Error> {
-			// prepare for send request
-			let deadline = sp_io::offchain::timestamp().add(Duration::from_millis(8_000));
-			let request =
-				http::Request::get("https://data.binance.com/api/v3/avgPrice?symbol=BTCUSDT");
-			let pending = request.deadline(deadline).send().map_err(|_| http::Error::IoError)?;
-			let response =
-				pending.try_wait(deadline).map_err(|_| http::Error::DeadlineReached)??;
-			if response.code != 200 {
-				log::warn!("Unexpected status code: {}", response.code);
-				return Err(http::Error::Unknown);
-			}
+		/// Auction a kitty for `duration` blocks. Bids below `reserve` are rejected.
+		#[pallet::call_index(8)]
+		#[pallet::weight(10_000)]
+		pub fn start_auction(
+			origin: OriginFor<T>,
+			kitty_id: KittyId,
+			reserve: BalanceOf<T>,
+			duration: T::BlockNumber,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
 
-			let body = response.body().collect::<Vec<u8>>();
+			ensure!(Kitties::<T>::contains_key(kitty_id), Error::<T>::InvalidKittyId);
+			ensure!(Self::kitty_owner(kitty_id) == Some(who.clone()), Error::<T>::NotOwner);
+			ensure!(Self::kitty_on_sale(kitty_id).is_none(), Error::<T>::AlreadyOnSale);
+			ensure!(!Auctions::<T>::contains_key(kitty_id), Error::<T>::KittyInAuction);
+			ensure!(
+				!duration.is_zero() && duration <= T::MaxAuctionDuration::get(),
+				Error::<T>::InvalidAuctionDuration
+			);
 
-			let body_str = sp_std::str::from_utf8(&body).map_err(|_| {
-				log::warn!("No UTF8 body");
-				http::Error::Unknown
-			})?;
+			let end = frame_system::Pallet::<T>::block_number().saturating_add(duration);
+			AuctionsEnding::<T>::try_append(end, kitty_id)
+				.map_err(|_| Error::<T>::TooManyAuctions)?;
+			Auctions::<T>::insert(
+				kitty_id,
+				Auction { seller: who.clone(), reserve, end, highest_bid: None },
+			);
 
-			// parse the response str
-			let btc_info: BtcInfo =
-				serde_json::from_str(body_str).map_err(|_| http::Error::Unknown)?;
+			Self::deposit_event(Event::AuctionStarted { who, kitty_id, reserve, end });
 
-			Ok(btc_info)
+			Ok(())
 		}
-	}
-
-	#[pallet::validate_unsigned]
-	impl<T: Config> ValidateUnsigned for Pallet<T> {
-		type Call = Call<T>;
 
-		/// Validate unsigned call to this module.
+		/// Bid on an auctioned kitty. `amount` is reserved and the previous bid is refunded.
 		///
-		/// By default unsigned transactions are disallowed, but implementing the validator
-		/// here we make sure that some particular calls (the ones produced by offchain worker)
+		/// A bid within `AuctionExtension` of the end pushes the end back to
+		/// `AuctionExtension` after the bid.
+		#[pallet::call_index(9)]
+		#[pallet::weight(10_000)]
+		pub fn bid(
+			origin: OriginFor<T>,
+			kitty_id: KittyId,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let mut auction = Self::auctions(kitty_id).ok_or(Error::<T>::NotInAuction)?;
+			ensure!(auction.seller != who, Error::<T>::AlreadyOwned);
+			ensure!(amount >= auction.reserve, Error::<T>::BidTooLow);
+			if let Some((_, highest)) = &auction.highest_bid {
+				ensure!(amount > *highest, Error::<T>::BidTooLow);
+			}
+
+			T::Currency::reserve(&who, amount)?;
+			if let Some((bidder, highest)) = auction.highest_bid.take() {
+				T::Currency::unreserve(&bidder, highest);
+			}
+			auction.highest_bid = Some((who.clone(), amount));
+
+			let now = frame_system::Pallet::<T>::block_number();
+			let extended_end = now.saturating_add(T::AuctionExtension::get());
+			if extended_end > auction.end {
+				AuctionsEnding::<T>::mutate(auction.end, |ending| {
+					ending.retain(|id| *id != kitty_id)
+				});
+				AuctionsEnding::<T>::try_append(extended_end, kitty_id)
+					.map_err(|_| Error::<T>::TooManyAuctions)?;
+				auction.end = extended_end;
+			}
+
+			let end = auction.end;
+			Auctions::<T>::insert(kitty_id, auction);
+
+			Self::deposit_event(Event::BidPlaced { who, kitty_id, amount, end });
+
+			Ok(())
+		}
+
+		/// Destroy a kitty, releasing its deposit to the owner.
+		#[pallet::call_index(10)]
+		#[pallet::weight(10_000)]
+		pub fn burn(origin: OriginFor<T>, kitty_id: KittyId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			Self::do_burn(kitty_id, Some(&who))
+		}
+
+		#[pallet::call_index(11)]
+		#[pallet::weight(10_000)]
+		pub fn rename(
+			origin: OriginFor<T>,
+			kitty_id: KittyId,
+			name: KittyName<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(Self::kitty_owner(kitty_id) == Some(who.clone()), Error::<T>::NotOwner);
+			Self::ensure_valid_name(&name)?;
+
+			Kitties::<T>::try_mutate(kitty_id, |kitty| -> DispatchResult {
+				let kitty = kitty.as_mut().ok_or(Error::<T>::InvalidKittyId)?;
+				Self::unindex_name(&kitty.name, kitty_id);
+				Self::index_name(&name, kitty_id);
+				kitty.name = name.clone();
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::KittyRenamed { who, kitty_id, name });
+
+			Ok(())
+		}
+
+		/// Let `operator` transfer `kitty_id`, or nobody if it is `None`. The approval is cleared
+		/// when the kitty changes hands.
+		///
+		/// Callable by the owner or one of their operators.
+		#[pallet::call_index(12)]
+		#[pallet::weight(10_000)]
+		pub fn approve(
+			origin: OriginFor<T>,
+			kitty_id: KittyId,
+			operator: Option<T::AccountId>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let owner = Self::kitty_owner(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
+			ensure!(
+				owner == who || Self::is_approved_for_all(&owner, &who),
+				Error::<T>::NotApproved
+			);
+			ensure!(operator.as_ref() != Some(&owner), Error::<T>::ApproveToOwner);
+
+			match &operator {
+				Some(operator) => KittyApprovals::<T>::insert(kitty_id, operator),
+				None => KittyApprovals::<T>::remove(kitty_id),
+			}
+
+			Self::deposit_event(Event::Approval { owner, kitty_id, operator });
+
+			Ok(())
+		}
+
+		/// Let `operator` transfer and approve every kitty of the caller, now and later.
+		#[pallet::call_index(13)]
+		#[pallet::weight(10_000)]
+		pub fn set_approval_for_all(
+			origin: OriginFor<T>,
+			operator: T::AccountId,
+			approved: bool,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(operator != who, Error::<T>::ApproveToOwner);
+
+			if approved {
+				OperatorApprovals::<T>::insert(&who, &operator, ());
+			} else {
+				OperatorApprovals::<T>::remove(&who, &operator);
+			}
+
+			Self::deposit_event(Event::ApprovalForAll { owner: who, operator, approved });
+
+			Ok(())
+		}
+
+		/// Transfer `kitty_id` from `from` to `to`. Callable by the owner, the account approved
+		/// for the kitty, or an operator of the owner.
+		#[pallet::call_index(14)]
+		#[pallet::weight(10_000)]
+		pub fn transfer_from(
+			origin: OriginFor<T>,
+			from: T::AccountId,
+			to: T::AccountId,
+			kitty_id: KittyId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(
+				who == from ||
+					Self::kitty_approval(kitty_id) == Some(who.clone()) ||
+					Self::is_approved_for_all(&from, &who),
+				Error::<T>::NotApproved
+			);
+
+			Self::do_transfer(from, to, kitty_id)
+		}
+
+		/// Put a kitty in the custody of the pallet account, for the recipient to claim before
+		/// `expiry`.
+		///
+		/// `secret_hash` is `T::Hashing` of `(secret, recipient)`. The secret becomes public once
+		/// `claim_gift` is submitted, so binding it to the recipient keeps anyone who copies it
+		/// from the transaction pool from claiming the kitty first.
+		#[pallet::call_index(15)]
+		#[pallet::weight(10_000)]
+		pub fn create_gift(
+			origin: OriginFor<T>,
+			kitty_id: KittyId,
+			secret_hash: T::Hash,
+			expiry: T::BlockNumber,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(Kitties::<T>::contains_key(kitty_id), Error::<T>::InvalidKittyId);
+			ensure!(Self::kitty_owner(kitty_id) == Some(who.clone()), Error::<T>::NotOwner);
+			ensure!(!Auctions::<T>::contains_key(kitty_id), Error::<T>::KittyInAuction);
+			ensure!(
+				expiry > frame_system::Pallet::<T>::block_number(),
+				Error::<T>::InvalidGiftExpiry
+			);
+			ensure!(!GiftCodes::<T>::contains_key(secret_hash), Error::<T>::GiftCodeTaken);
+
+			// The deposit stays reserved against the sender while the kitty is in escrow.
+			KittyOwner::<T>::insert(kitty_id, Self::get_account_id());
+			KittyApprovals::<T>::remove(kitty_id);
+			StudFees::<T>::remove(kitty_id);
+			KittyOnSale::<T>::remove(kitty_id);
+			Self::remove_owned_kitty(&who, kitty_id);
+			GiftCodes::<T>::insert(secret_hash, kitty_id);
+			Gifts::<T>::insert(kitty_id, Gift { sender: who.clone(), secret_hash, expiry });
+
+			Self::deposit_event(Event::GiftCreated { who, kitty_id, expiry });
+
+			Ok(())
+		}
+
+		/// Take the gift whose claim code is `secret` and whose recipient is the caller.
+		#[pallet::call_index(16)]
+		#[pallet::weight(10_000)]
+		pub fn claim_gift(origin: OriginFor<T>, secret: [u8; 32]) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let secret_hash = T::Hashing::hash_of(&(secret, &who));
+			let kitty_id = GiftCodes::<T>::take(secret_hash).ok_or(Error::<T>::GiftNotFound)?;
+			let gift = Gifts::<T>::take(kitty_id).ok_or(Error::<T>::GiftNotFound)?;
+			ensure!(
+				frame_system::Pallet::<T>::block_number() < gift.expiry,
+				Error::<T>::GiftExpired
+			);
+
+			Self::move_deposit(kitty_id, &gift.sender, &who)?;
+			KittyOwner::<T>::insert(kitty_id, &who);
+			Self::add_owned_kitty(&who, kitty_id)?;
+
+			Self::deposit_event(Event::GiftClaimed { who, kitty_id });
+
+			Ok(())
+		}
+
+		/// Take back an expired gift, to the sender or to `recipient` if given, e.g. when the
+		/// sender already owns `MaxKittiesOwned` kitties.
+		#[pallet::call_index(17)]
+		#[pallet::weight(10_000)]
+		pub fn reclaim_gift(
+			origin: OriginFor<T>,
+			kitty_id: KittyId,
+			recipient: Option<T::AccountId>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let gift = Self::gifts(kitty_id).ok_or(Error::<T>::GiftNotFound)?;
+			ensure!(gift.sender == who, Error::<T>::NotOwner);
+			ensure!(
+				frame_system::Pallet::<T>::block_number() >= gift.expiry,
+				Error::<T>::GiftNotExpired
+			);
+
+			let recipient = recipient.unwrap_or_else(|| who.clone());
+			Gifts::<T>::remove(kitty_id);
+			GiftCodes::<T>::remove(gift.secret_hash);
+			Self::move_deposit(kitty_id, &who, &recipient)?;
+			KittyOwner::<T>::insert(kitty_id, &recipient);
+			Self::add_owned_kitty(&recipient, kitty_id)?;
+
+			Self::deposit_event(Event::GiftReclaimed { who, kitty_id, recipient });
+
+			Ok(())
+		}
+
+		/// Let anyone breed with `kitty_id` for `fee`, paid to its owner. Offering a stud again
+		/// replaces its fee.
+		#[pallet::call_index(18)]
+		#[pallet::weight(10_000)]
+		pub fn offer_stud(
+			origin: OriginFor<T>,
+			kitty_id: KittyId,
+			fee: BalanceOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			Self::ensure_owner(&who, kitty_id)?;
+
+			StudFees::<T>::insert(kitty_id, fee);
+			Self::deposit_event(Event::StudOffered { who, kitty_id, fee });
+
+			Ok(())
+		}
+
+		#[pallet::call_index(19)]
+		#[pallet::weight(10_000)]
+		pub fn withdraw_stud(origin: OriginFor<T>, kitty_id: KittyId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			Self::ensure_owner(&who, kitty_id)?;
+			StudFees::<T>::take(kitty_id).ok_or(Error::<T>::NotAStud)?;
+
+			Self::deposit_event(Event::StudWithdrawn { who, kitty_id });
+
+			Ok(())
+		}
+
+		/// Breed the caller's `kitty_id` with someone else's stud, paying the stud fee to its
+		/// owner. Fails if the fee is above `max_fee`. The child goes to the caller.
+		#[pallet::call_index(20)]
+		#[pallet::weight(10_000)]
+		pub fn breed_with_stud(
+			origin: OriginFor<T>,
+			kitty_id: KittyId,
+			stud_id: KittyId,
+			max_fee: BalanceOf<T>,
+			name: KittyName<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			Self::ensure_owner(&who, kitty_id)?;
+			let owner = Self::kitty_owner(stud_id).ok_or(Error::<T>::InvalidKittyId)?;
+			ensure!(owner != who, Error::<T>::AlreadyOwned);
+			let fee = Self::stud_fee(stud_id).ok_or(Error::<T>::NotAStud)?;
+			ensure!(fee <= max_fee, Error::<T>::PriceTooHigh);
+
+			T::Currency::transfer(&who, &owner, fee, ExistenceRequirement::KeepAlive)?;
+			Self::deposit_event(Event::StudFeePaid {
+				who: who.clone(),
+				owner,
+				kitty_id: stud_id,
+				fee,
+			});
+
+			Self::do_breed(who, kitty_id, stud_id, name)
+		}
+
+		/// Accept prices reported by the off-chain worker holding the key of `who`.
+		#[pallet::call_index(21)]
+		#[pallet::weight(10_000)]
+		pub fn add_oracle_authority(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+			ensure_root(origin)?;
+
+			OracleAuthorities::<T>::try_mutate(|authorities| -> DispatchResult {
+				ensure!(!authorities.contains(&who), Error::<T>::AlreadyOracleAuthority);
+				authorities
+					.try_push(who.clone())
+					.map_err(|_| Error::<T>::TooManyOracleAuthorities)?;
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::OracleAuthorityAdded { who });
+
+			Ok(())
+		}
+
+		#[pallet::call_index(22)]
+		#[pallet::weight(10_000)]
+		pub fn remove_oracle_authority(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+			ensure_root(origin)?;
+
+			OracleAuthorities::<T>::try_mutate(|authorities| -> DispatchResult {
+				let index = authorities
+					.iter()
+					.position(|authority| *authority == who)
+					.ok_or(Error::<T>::NotOracleAuthority)?;
+				authorities.remove(index);
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::OracleAuthorityRemoved { who });
+
+			Ok(())
+		}
+
+		/// Store a price reported by an oracle authority in a signed transaction as
+		/// `LatestBtcPrice`.
+		#[pallet::call_index(23)]
+		#[pallet::weight(10_000)]
+		pub fn submit_price(origin: OriginFor<T>, price: Vec<u8>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(
+				T::SubmissionMode::get() == SubmissionMode::Signed,
+				Error::<T>::WrongSubmissionMode
+			);
+
+			Self::do_report_price(&who, frame_system::Pallet::<T>::block_number(), &price)
+		}
+
+		/// Store a price fetched by the off-chain worker of a block author in the round of
+		/// `block_number` as `LatestBtcPrice`. The first one of each round wins.
+		#[pallet::call_index(24)]
+		#[pallet::weight(10_000)]
+		pub fn submit_price_unsigned(
+			origin: OriginFor<T>,
+			price: Vec<u8>,
+			block_number: T::BlockNumber,
+		) -> DispatchResult {
+			ensure_none(origin)?;
+			ensure!(
+				T::SubmissionMode::get() == SubmissionMode::Unsigned,
+				Error::<T>::WrongSubmissionMode
+			);
+			ensure!(Self::can_report_unsigned(block_number), Error::<T>::OracleRateLimited);
+
+			let price = Self::parse_price(&price).ok_or(Error::<T>::InvalidPrice)?;
+			LastUnsignedRound::<T>::put(Self::oracle_round(block_number));
+			Self::set_latest_btc_price(price);
+
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		fn get_next_id() -> Result<KittyId, DispatchError> {
+			NextKittyId::<T>::try_mutate(|next_id| -> Result<KittyId, DispatchError> {
+				let current_id = *next_id;
+				*next_id = next_id
+					.checked_add(1)
+					.ok_or::<DispatchError>(Error::<T>::InvalidKittyId.into())?;
+				Ok(current_id)
+			})
+		}
+
+		fn random_value(sender: &T::AccountId) -> [u8; 16] {
+			let payload = (
+				T::Randomness::random_seed(),
+				&sender,
+				<frame_system::Pallet<T>>::extrinsic_index(),
+			);
+
+			payload.using_encoded(blake2_128)
+		}
+
+		fn get_account_id() -> T::AccountId {
+			T::PalletId::get().into_account_truncating()
+		}
+
+		/// Index `kitty_id` under `owner`, failing if the owner is already at `MaxKittiesOwned`.
+		fn add_owned_kitty(owner: &T::AccountId, kitty_id: KittyId) -> DispatchResult {
+			OwnedKitties::<T>::try_mutate(owner, |kitties| {
+				kitties.try_push(kitty_id).map_err(|_| Error::<T>::TooManyKitties.into())
+			})
+		}
+
+		/// Drop `kitty_id` from the index of `owner`.
+		fn remove_owned_kitty(owner: &T::AccountId, kitty_id: KittyId) {
+			OwnedKitties::<T>::mutate_exists(owner, |kitties| {
+				if let Some(owned) = kitties {
+					owned.retain(|id| *id != kitty_id);
+					if owned.is_empty() {
+						*kitties = None;
+					}
+				}
+			});
+		}
+
+		/// Give `who` the new kitty `kitty_id`, which must be the next id, without charging
+		/// the kitty price. It is named after its id; if another kitty already holds that name,
+		/// the new one keeps it without being indexed in `KittyByName`.
+		pub(crate) fn do_mint(who: &T::AccountId, kitty_id: KittyId) -> DispatchResult {
+			ensure!(kitty_id == Self::next_kitty_id(), Error::<T>::InvalidKittyId);
+			let name = Self::minted_name(kitty_id);
+			ensure!(!name.is_empty(), Error::<T>::InvalidName);
+			Self::get_next_id()?;
+
+			let kitty = Kitty {
+				dna: Self::random_value(who),
+				name,
+				generation: 0,
+				breeding_cooldown_until: Zero::zero(),
+			};
+			if !KittyByName::<T>::contains_key(&kitty.name) {
+				Self::index_name(&kitty.name, kitty_id);
+			}
+			Kitties::<T>::insert(kitty_id, kitty.clone());
+			KittyOwner::<T>::insert(kitty_id, who);
+			Self::add_owned_kitty(who, kitty_id)?;
+
+			Self::deposit_event(Event::KittyCreated { who: who.clone(), kitty_id, kitty });
+			Ok(())
+		}
+
+		/// `Kitty #<kitty_id>`, cut to `MaxNameLength`.
+		pub(crate) fn minted_name(kitty_id: KittyId) -> KittyName<T> {
+			let mut name = b"Kitty #".to_vec();
+			let digits_start = name.len();
+			let mut rest = kitty_id;
+			loop {
+				name.insert(digits_start, b'0' + (rest % 10) as u8);
+				rest /= 10;
+				if rest == 0 {
+					break
+				}
+			}
+			BoundedVec::truncate_from(name)
+		}
+
+		/// Destroy `kitty_id`, releasing its deposit to the owner. Fails if the kitty is being
+		/// auctioned, or if `maybe_check_owner` is given and does not own it.
+		///
+		/// Its own lineage entries go with it; its children still name it in `KittyParents`.
+		pub(crate) fn do_burn(
+			kitty_id: KittyId,
+			maybe_check_owner: Option<&T::AccountId>,
+		) -> DispatchResult {
+			let kitty = Self::kitties(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
+			let owner = Self::kitty_owner(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
+			if let Some(check_owner) = maybe_check_owner {
+				ensure!(owner == *check_owner, Error::<T>::NotOwner);
+			}
+			ensure!(!Auctions::<T>::contains_key(kitty_id), Error::<T>::KittyInAuction);
+			ensure!(!Gifts::<T>::contains_key(kitty_id), Error::<T>::KittyGifted);
+
+			Self::unindex_name(&kitty.name, kitty_id);
+			Kitties::<T>::remove(kitty_id);
+			KittyOwner::<T>::remove(kitty_id);
+			KittyApprovals::<T>::remove(kitty_id);
+			StudFees::<T>::remove(kitty_id);
+			KittyOnSale::<T>::remove(kitty_id);
+			if let Some((parent_1, parent_2)) = KittyParents::<T>::take(kitty_id) {
+				KittyChildren::<T>::remove(parent_1, kitty_id);
+				KittyChildren::<T>::remove(parent_2, kitty_id);
+			}
+			let _ = KittyChildren::<T>::clear_prefix(kitty_id, u32::MAX, None);
+			Self::remove_owned_kitty(&owner, kitty_id);
+			let deposit = KittyDeposit::<T>::take(kitty_id).unwrap_or_else(Zero::zero);
+			T::Currency::unreserve(&owner, deposit);
+
+			Self::deposit_event(Event::KittyBurned { who: owner, kitty_id, deposit });
+
+			Ok(())
+		}
+
+		fn ensure_owner(who: &T::AccountId, kitty_id: KittyId) -> DispatchResult {
+			let owner = Self::kitty_owner(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
+			ensure!(owner == *who, Error::<T>::NotOwner);
+			Ok(())
+		}
+
+		/// Breed a child of `kitty_id_1` and `kitty_id_2` for `who`, who pays the kitty price.
+		/// Callers are responsible for checking that `who` may use both parents.
+		fn do_breed(
+			who: T::AccountId,
+			kitty_id_1: KittyId,
+			kitty_id_2: KittyId,
+			name: KittyName<T>,
+		) -> DispatchResult {
+			ensure!(kitty_id_1 != kitty_id_2, Error::<T>::SameKittyId);
+			ensure!(!Self::are_related(kitty_id_1, kitty_id_2), Error::<T>::InbreedingNotAllowed);
+			Self::ensure_valid_name(&name)?;
+
+			let kitty_id = Self::get_next_id()?;
+
+			let mut kitty_1 = Self::kitties(kitty_id_1).ok_or(Error::<T>::InvalidKittyId)?;
+			let mut kitty_2 = Self::kitties(kitty_id_2).ok_or(Error::<T>::InvalidKittyId)?;
+
+			let now = frame_system::Pallet::<T>::block_number();
+			ensure!(
+				kitty_1.breeding_cooldown_until <= now && kitty_2.breeding_cooldown_until <= now,
+				Error::<T>::BreedingCooldown
+			);
+
+			let selector = Self::random_value(&who);
+			let mut dna = [0u8; 16];
+			for (i, gene) in dna.iter_mut().enumerate() {
+				*gene = (kitty_1.dna[i] & selector[i]) | (kitty_2.dna[i] & !selector[i]);
+			}
+
+			let cooldown_until = now.saturating_add(T::BreedingCooldownPeriod::get());
+			let kitty = Kitty {
+				dna,
+				name,
+				generation: kitty_1.generation.max(kitty_2.generation).saturating_add(1),
+				breeding_cooldown_until: cooldown_until,
+			};
+			kitty_1.breeding_cooldown_until = cooldown_until;
+			kitty_2.breeding_cooldown_until = cooldown_until;
+			Kitties::<T>::insert(kitty_id_1, kitty_1);
+			Kitties::<T>::insert(kitty_id_2, kitty_2);
+
+			Self::charge_kitty_price(&who, kitty_id)?;
+
+			Self::index_name(&kitty.name, kitty_id);
+			Kitties::<T>::insert(kitty_id, kitty.clone());
+			KittyOwner::<T>::insert(kitty_id, &who);
+			Self::add_owned_kitty(&who, kitty_id)?;
+			KittyParents::<T>::insert(kitty_id, (kitty_id_1, kitty_id_2));
+			KittyChildren::<T>::insert(kitty_id_1, kitty_id, ());
+			KittyChildren::<T>::insert(kitty_id_2, kitty_id, ());
+
+			Self::deposit_event(Event::KittyBred { who, kitty_id, kitty });
+			Ok(())
+		}
+
+		/// Move `kitty_id` from `from`, which must own it, to `recipient`. Any listing and
+		/// approval of the kitty are cleared.
+		pub(crate) fn do_transfer(
+			from: T::AccountId,
+			recipient: T::AccountId,
+			kitty_id: KittyId,
+		) -> DispatchResult {
+			ensure!(Kitties::<T>::contains_key(kitty_id), Error::<T>::InvalidKittyId);
+
+			let owner = Self::kitty_owner(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
+			ensure!(owner == from, Error::<T>::NotOwner);
+			ensure!(!Auctions::<T>::contains_key(kitty_id), Error::<T>::KittyInAuction);
+			ensure!(!Gifts::<T>::contains_key(kitty_id), Error::<T>::KittyGifted);
+
+			Self::move_deposit(kitty_id, &from, &recipient)?;
+			KittyOwner::<T>::insert(kitty_id, &recipient);
+			KittyApprovals::<T>::remove(kitty_id);
+			StudFees::<T>::remove(kitty_id);
+			Self::remove_owned_kitty(&from, kitty_id);
+			Self::add_owned_kitty(&recipient, kitty_id)?;
+			if KittyOnSale::<T>::take(kitty_id).is_some() {
+				Self::deposit_event(Event::KittySaleCancelled { who: from.clone(), kitty_id });
+			}
+
+			// Emit an event.
+			Self::deposit_event(Event::KittyTransferred { who: from, recipient, kitty_id });
+			// Return a successful DispatchResultWithPostInfo
+			Ok(())
+		}
+
+		/// Whether `operator` may transfer every kitty of `owner`.
+		pub fn is_approved_for_all(owner: &T::AccountId, operator: &T::AccountId) -> bool {
+			OperatorApprovals::<T>::contains_key(owner, operator)
+		}
+
+		/// Check that `name` is non-empty UTF-8 and, with `UniqueNames`, not taken.
+		fn ensure_valid_name(name: &KittyName<T>) -> DispatchResult {
+			ensure!(
+				!name.is_empty() && sp_std::str::from_utf8(name).is_ok(),
+				Error::<T>::InvalidName
+			);
+			ensure!(
+				!T::UniqueNames::get() || !KittyByName::<T>::contains_key(name),
+				Error::<T>::NameTaken
+			);
+			Ok(())
+		}
+
+		/// Record that `kitty_id` holds `name`, if names are unique.
+		pub(crate) fn index_name(name: &KittyName<T>, kitty_id: KittyId) {
+			if T::UniqueNames::get() {
+				KittyByName::<T>::insert(name, kitty_id);
+			}
+		}
+
+		/// Release `name` if `kitty_id` holds it.
+		fn unindex_name(name: &KittyName<T>, kitty_id: KittyId) {
+			if T::UniqueNames::get() && KittyByName::<T>::get(name) == Some(kitty_id) {
+				KittyByName::<T>::remove(name);
+			}
+		}
+
+		/// The price of a new kitty: `KittyPriceUsd` at the `BtcPriceFeed` price, or else at
+		/// `LatestBtcPrice`, and `NativeTokensPerBtc`. `KittyPrice` if neither price is younger
+		/// than `PriceStaleAfter`.
+		pub fn kitty_price() -> BalanceOf<T> {
+			let now = frame_system::Pallet::<T>::block_number();
+			let fresh = |(_, at): &(FixedU128, T::BlockNumber)| {
+				now.saturating_sub(*at) <= T::PriceStaleAfter::get()
+			};
+			T::BtcPriceFeed::btc_price()
+				.filter(fresh)
+				.or_else(|| Self::latest_btc_price().filter(fresh))
+				.and_then(|(price, _)| T::KittyPriceUsd::get().checked_div(&price))
+				.map(|btc| btc.saturating_mul(T::NativeTokensPerBtc::get()))
+				.map(|tokens| {
+					let unit: u128 = T::NativeUnit::get().saturated_into();
+					tokens.saturating_mul_int(unit).saturated_into()
+				})
+				.unwrap_or_else(T::KittyPrice::get)
+		}
+
+		/// The oracle round `block_number` falls in.
+		fn oracle_round(block_number: T::BlockNumber) -> T::BlockNumber {
+			block_number / T::OracleSubmissionInterval::get().max(One::one())
+		}
+
+		/// Whether `who` has not reported a price in the round of `block_number` or a later one.
+		fn can_report(who: &T::AccountId, block_number: T::BlockNumber) -> bool {
+			Self::last_oracle_round(who)
+				.map_or(true, |last| Self::oracle_round(block_number) > last)
+		}
+
+		/// Whether no pure unsigned price was accepted in the round of `block_number` or a later
+		/// one.
+		fn can_report_unsigned(block_number: T::BlockNumber) -> bool {
+			LastUnsignedRound::<T>::get()
+				.map_or(true, |last| Self::oracle_round(block_number) > last)
+		}
+
+		/// Store the price `raw` reported by the oracle authority `who` for the round of
+		/// `block_number`.
+		fn do_report_price(
+			who: &T::AccountId,
+			block_number: T::BlockNumber,
+			raw: &[u8],
+		) -> DispatchResult {
+			ensure!(Self::oracle_authorities().contains(who), Error::<T>::NotOracleAuthority);
+			ensure!(Self::can_report(who, block_number), Error::<T>::OracleRateLimited);
+			let price = Self::parse_price(raw).ok_or(Error::<T>::InvalidPrice)?;
+
+			LastOracleRound::<T>::insert(who, Self::oracle_round(block_number));
+			Self::set_latest_btc_price(price);
+
+			Ok(())
+		}
+
+		fn set_latest_btc_price(price: FixedU128) {
+			LatestBtcPrice::<T>::put((price, frame_system::Pallet::<T>::block_number()));
+			Self::deposit_event(Event::PriceReported { price });
+		}
+
+		/// A reported price, if it is within `MinBtcPrice` and `MaxBtcPrice` and so usable as
+		/// `LatestBtcPrice`.
+		fn parse_price(raw: &[u8]) -> Option<FixedU128> {
+			pallet_oracle::source::parse_decimal(raw).filter(|price| {
+				!price.is_zero() &&
+					*price >= T::MinBtcPrice::get() &&
+					*price <= T::MaxBtcPrice::get()
+			})
+		}
+
+		/// Take `kitty_price` from `who` for the new kitty `kitty_id`, as `PriceMode` says.
+		fn charge_kitty_price(who: &T::AccountId, kitty_id: KittyId) -> DispatchResult {
+			let price = Self::kitty_price();
+			match T::PriceMode::get() {
+				PriceMode::Payment => T::Currency::transfer(
+					who,
+					&Self::get_account_id(),
+					price,
+					ExistenceRequirement::KeepAlive,
+				),
+				PriceMode::Deposit => {
+					T::Currency::reserve(who, price)?;
+					KittyDeposit::<T>::insert(kitty_id, price);
+					Ok(())
+				},
+			}
+		}
+
+		/// Move the deposit of `kitty_id`, if it has one, from the reserve of `from` to that of
+		/// `to`. Fails unless all of it moves.
+		fn move_deposit(
+			kitty_id: KittyId,
+			from: &T::AccountId,
+			to: &T::AccountId,
+		) -> DispatchResult {
+			if let Some(deposit) = Self::kitty_deposit(kitty_id) {
+				let unmoved =
+					T::Currency::repatriate_reserved(from, to, deposit, BalanceStatus::Reserved)?;
+				ensure!(unmoved.is_zero(), Error::<T>::DepositNotReserved);
+			}
+			Ok(())
+		}
+
+		/// Pay the reserved `amount` of `bidder` to `seller` and hand `kitty_id` and its deposit to
+		/// `bidder`.
+		fn sell_auctioned_kitty(
+			kitty_id: KittyId,
+			seller: &T::AccountId,
+			bidder: &T::AccountId,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			Self::add_owned_kitty(bidder, kitty_id)?;
+			let unpaid =
+				T::Currency::repatriate_reserved(bidder, seller, amount, BalanceStatus::Free)?;
+			ensure!(unpaid.is_zero(), Error::<T>::BidNotReserved);
+			Self::move_deposit(kitty_id, seller, bidder)?;
+
+			KittyOwner::<T>::insert(kitty_id, bidder);
+			KittyApprovals::<T>::remove(kitty_id);
+			StudFees::<T>::remove(kitty_id);
+			Self::remove_owned_kitty(seller, kitty_id);
+			Ok(())
+		}
+
+		/// End the auction of `kitty_id`, paying the seller and handing the kitty to the highest
+		/// bidder if there is one. If the sale fails, the bid is refunded, the kitty stays with the
+		/// seller and `AuctionSettlementFailed` is emitted.
+		fn settle_auction(kitty_id: KittyId) {
+			let auction = match Auctions::<T>::take(kitty_id) {
+				Some(auction) => auction,
+				None => return,
+			};
+
+			let winner = auction.highest_bid.and_then(|(bidder, amount)| {
+				let sold = with_storage_layer(|| {
+					Self::sell_auctioned_kitty(kitty_id, &auction.seller, &bidder, amount)
+				});
+				match sold {
+					Ok(()) => Some((bidder, amount)),
+					Err(error) => {
+						T::Currency::unreserve(&bidder, amount);
+						Self::deposit_event(Event::AuctionSettlementFailed {
+							kitty_id,
+							bidder,
+							amount,
+							error,
+						});
+						None
+					},
+				}
+			});
+
+			let (winner, price) = match winner {
+				Some((bidder, amount)) => (Some(bidder), Some(amount)),
+				None => (None, None),
+			};
+			Self::deposit_event(Event::AuctionSettled {
+				kitty_id,
+				seller: auction.seller,
+				winner,
+				price,
+			});
+		}
+
+		/// Whether `kitty_id_1` and `kitty_id_2` share a parent, or one is a parent of the other.
+		fn are_related(kitty_id_1: KittyId, kitty_id_2: KittyId) -> bool {
+			let parents_1 = Self::kitty_parents(kitty_id_1);
+			let parents_2 = Self::kitty_parents(kitty_id_2);
+			let is_parent_of = |parents: Option<(KittyId, KittyId)>, kitty_id| {
+				parents.map_or(false, |(a, b)| a == kitty_id || b == kitty_id)
+			};
+
+			is_parent_of(parents_1, kitty_id_2) ||
+				is_parent_of(parents_2, kitty_id_1) ||
+				parents_2.map_or(false, |(a, b)| {
+					is_parent_of(parents_1, a) || is_parent_of(parents_1, b)
+				})
+		}
+
+		/// Return the ancestry of `kitty_id` up to `depth` generations back, as each bred kitty
+		/// with its parents. `kitty_id` itself comes first if it was bred.
+		///
+		/// `depth` is capped at `MAX_LINEAGE_DEPTH`. Used by the `KittiesApi` runtime API.
+		pub fn ancestors(kitty_id: KittyId, depth: u32) -> Vec<(KittyId, (KittyId, KittyId))> {
+			if depth == 0 {
+				return Vec::new()
+			}
+
+			Self::walk_lineage(kitty_id, depth - 1, |kitty_id| {
+				Self::kitty_parents(kitty_id).into_iter().flat_map(|(a, b)| [a, b]).collect()
+			})
+			.into_iter()
+			.filter_map(|kitty_id| Self::kitty_parents(kitty_id).map(|parents| (kitty_id, parents)))
+			.collect()
+		}
+
+		/// Return the descendants of `kitty_id` up to `depth` generations down, each with its
+		/// parents.
+		///
+		/// `depth` is capped at `MAX_LINEAGE_DEPTH`. Used by the `KittiesApi` runtime API.
+		pub fn descendants(kitty_id: KittyId, depth: u32) -> Vec<(KittyId, (KittyId, KittyId))> {
+			Self::walk_lineage(kitty_id, depth, |kitty_id| {
+				KittyChildren::<T>::iter_key_prefix(kitty_id).collect()
+			})
+			.into_iter()
+			.filter(|id| *id != kitty_id)
+			.filter_map(|kitty_id| Self::kitty_parents(kitty_id).map(|parents| (kitty_id, parents)))
+			.collect()
+		}
+
+		/// Breadth-first walk from `start` following `next`, visiting each kitty once.
+		fn walk_lineage(
+			start: KittyId,
+			depth: u32,
+			next: impl Fn(KittyId) -> Vec<KittyId>,
+		) -> Vec<KittyId> {
+			let mut visited = BTreeSet::from([start]);
+			let mut order = sp_std::vec![start];
+			let mut frontier = sp_std::vec![start];
+
+			for _ in 0..depth.min(MAX_LINEAGE_DEPTH) {
+				let mut reached = Vec::new();
+				for kitty_id in frontier {
+					for relative in next(kitty_id) {
+						if visited.insert(relative) {
+							reached.push(relative);
+						}
+					}
+				}
+				if reached.is_empty() {
+					break
+				}
+				order.extend(reached.iter().copied());
+				frontier = reached;
+			}
+			order
+		}
+
+		/// Return at most `limit` kitties of `owner`, skipping the first `start`.
+		///
+		/// Used by the `KittiesApi` runtime API.
+		pub fn kitties_of(owner: T::AccountId, start: u32, limit: u32) -> Vec<KittyId> {
+			Self::owned_kitties(owner)
+				.into_iter()
+				.skip(start as usize)
+				.take(limit as usize)
+				.collect()
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		fn derived_key(block_number: T::BlockNumber) -> Vec<u8> {
+			block_number.using_encoded(|encoded_bn| {
+				ONCHAIN_TX_KEY
+					.clone()
+					.into_iter()
+					.chain(b"/".into_iter())
+					.chain(encoded_bn)
+					.copied()
+					.collect::<Vec<u8>>()
+			})
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Send `price` in a signed transaction from every local oracle authority key that is
+		/// not waiting on an earlier one.
+		fn send_signed(block_number: T::BlockNumber, price: Vec<u8>) {
+			let signer = Signer::<T, T::AuthorityId>::all_accounts()
+				.with_filter(Self::ready_signers(block_number));
+			if !signer.can_sign() {
+				log::error!("OCW ==> No local account ready to sign");
+				return
+			}
+
+			let results =
+				signer.send_signed_transaction(|_acct| Call::submit_price { price: price.clone() });
+			for (acct, res) in &results {
+				let mut state = Self::signer_state(&acct.id);
+				match res {
+					Ok(()) => {
+						log::info!("OCW ==> signed tx from {:?} successfully sent.", acct.id);
+						// `send_signed_transaction` bumped the nonce in the worker's own copy of
+						// the state, to what it is once the transaction is included.
+						let nonce = frame_system::Pallet::<T>::account_nonce(&acct.id);
+						state = SignerState {
+							pending: Some((nonce, block_number)),
+							..Default::default()
+						};
+					},
+					Err(()) => {
+						state.failures = state.failures.saturating_add(1);
+						state.retry_at = block_number.saturating_add(Self::backoff(state.failures));
+						log::error!(
+							"OCW ==> sending signed tx from {:?} failed, {} times in a row. \
+							Retrying at block {:?}.",
+							acct.id,
+							state.failures,
+							state.retry_at
+						);
+					},
+				}
+				StorageValueRef::persistent(&Self::signer_key(&acct.id)).set(&state);
+			}
+		}
+
+		/// The local oracle authority keys whose accounts can report in the round of
+		/// `block_number` and have no transaction still waiting in the pool.
+		fn ready_signers(block_number: T::BlockNumber) -> Vec<T::Public> {
+			<T::AuthorityId as AppCrypto<T::Public, T::Signature>>::RuntimeAppPublic::all()
+				.into_iter()
+				.map(|key| -> T::Public {
+					<T::AuthorityId as AppCrypto<T::Public, T::Signature>>::GenericPublic::from(key)
+						.into()
+				})
+				.filter(|public: &T::Public| {
+					let who = public.clone().into_account();
+					Self::oracle_authorities().contains(&who) &&
+						Self::can_report(&who, block_number) &&
+						!Self::is_waiting(&who, block_number)
+				})
+				.collect()
+		}
+
+		/// Whether `who` is backing off after a rejected transaction, or the last transaction
+		/// it sent may still be in the pool. That one is not once the account nonce has reached
+		/// the one it leaves behind, or once a round has gone by and the pool dropped it.
+		fn is_waiting(who: &T::AccountId, block_number: T::BlockNumber) -> bool {
+			let state = Self::signer_state(who);
+			block_number < state.retry_at ||
+				state.pending.map_or(false, |(nonce, sent)| {
+					frame_system::Pallet::<T>::account_nonce(who) < nonce &&
+						block_number < sent.saturating_add(T::OracleSubmissionInterval::get())
+				})
+		}
+
+		/// How long an account waits after `failures` rejections in a row: one round, doubling
+		/// with each further rejection up to `2^MAX_BACKOFF_EXPONENT` rounds.
+		fn backoff(failures: u32) -> T::BlockNumber {
+			let rounds = 1u32 << failures.saturating_sub(1).min(MAX_BACKOFF_EXPONENT);
+			T::OracleSubmissionInterval::get().saturating_mul(rounds.into())
+		}
+
+		fn signer_key(who: &T::AccountId) -> Vec<u8> {
+			who.using_encoded(|encoded| [SIGNER_STATE_KEY, encoded].concat())
+		}
+
+		fn signer_state(who: &T::AccountId) -> SignerState<T::Index, T::BlockNumber> {
+			StorageValueRef::persistent(&Self::signer_key(who))
+				.get()
+				.ok()
+				.flatten()
+				.unwrap_or_default()
+		}
+
+		fn send_unsigned_with_signed_payload(block_number: T::BlockNumber, price: Vec<u8>) {
+			// Retrieve the signer to sign the payload
+			let signer = Signer::<T, T::AuthorityId>::any_account();
+			if let Some((_, res)) = signer.send_unsigned_transaction(
+				// this line is to prepare and return payload
+				|acct| Payload { price: price.clone(), block_number, public: acct.public.clone() },
+				|payload, signature| Call::unsigned_extrinsic_with_signed_payload {
+					payload,
+					signature,
+				},
+			) {
+				match res {
+					Ok(()) => {
+						log::info!("OCW ==> unsigned tx with signed payload successfully sent.");
+					},
+					Err(()) => {
+						log::error!("OCW ==> sending unsigned tx with signed payload failed.");
+					},
+				};
+			} else {
+				// The case of `None`: no account is available for sending
+				log::error!("OCW ==> No local account available");
+			}
+		}
+
+		fn send_unsigned(block_number: T::BlockNumber, price: Vec<u8>) {
+			let call = Call::submit_price_unsigned { price, block_number };
+			match SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(call.into()) {
+				Ok(()) => log::info!("OCW ==> unsigned tx successfully sent."),
+				Err(()) => log::error!("OCW ==> sending unsigned tx failed."),
+			}
+		}
+
+		fn fetch_btc_info() -> Result<BtcInfo, http::Error> {
+			// prepare for send request
+			let deadline = sp_io::offchain::timestamp().add(Duration::from_millis(8_000));
+			let request =
+				http::Request::get("https://data.binance.com/api/v3/avgPrice?symbol=BTCUSDT");
+			let pending = request.deadline(deadline).send().map_err(|_| http::Error::IoError)?;
+			let response =
+				pending.try_wait(deadline).map_err(|_| http::Error::DeadlineReached)??;
+			if response.code != 200 {
+				log::warn!("Unexpected status code: {}", response.code);
+				return Err(http::Error::Unknown);
+			}
+
+			let body = response.body().collect::<Vec<u8>>();
+
+			let body_str = sp_std::str::from_utf8(&body).map_err(|_| {
+				log::warn!("No UTF8 body");
+				http::Error::Unknown
+			})?;
+
+			// parse the response str
+			let btc_info: BtcInfo =
+				serde_json::from_str(body_str).map_err(|_| http::Error::Unknown)?;
+
+			Ok(btc_info)
+		}
+	}
+
+	#[pallet::validate_unsigned]
+	impl<T: Config> ValidateUnsigned for Pallet<T> {
+		type Call = Call<T>;
+
+		/// Validate unsigned call to this module.
+		///
+		/// By default unsigned transactions are disallowed, but implementing the validator
+		/// here we make sure that some particular calls (the ones produced by offchain worker)
 		/// are being whitelisted and marked as valid.
-		fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+		fn validate_unsigned(source: TransactionSource, call: &Self::Call) -> TransactionValidity {
 			const UNSIGNED_TXS_PRIORITY: u64 = 100;
-			let valid_tx = |provide| {
+			let interval = T::OracleSubmissionInterval::get();
+			let valid_tx = |provide: Vec<u8>, propagate: bool| {
 				ValidTransaction::with_tag_prefix("my-pallet")
 					.priority(UNSIGNED_TXS_PRIORITY) // please define `UNSIGNED_TXS_PRIORITY` before this line
 					.and_provides([&provide])
-					.longevity(3)
-					.propagate(true)
+					.longevity(interval.saturated_into::<u64>().max(1))
+					.propagate(propagate)
 					.build()
 			};
 
@@ -526,12 +1999,56 @@ pub mod pallet {
 			// 	_ => InvalidTransaction::Call.into(),
 			// }
 
+			let now = frame_system::Pallet::<T>::block_number();
 			match call {
 				Call::unsigned_extrinsic_with_signed_payload { ref payload, ref signature } => {
+					if T::SubmissionMode::get() != SubmissionMode::UnsignedWithSignedPayload {
+						return InvalidTransaction::Call.into();
+					}
 					if !SignedPayload::<T>::verify::<T::AuthorityId>(payload, signature.clone()) {
 						return InvalidTransaction::BadProof.into();
 					}
-					valid_tx(b"unsigned_extrinsic_with_signed_payload".to_vec())
+					let who = payload.public.clone().into_account();
+					if !Self::oracle_authorities().contains(&who) {
+						return InvalidTransaction::BadSigner.into();
+					}
+					if Self::parse_price(&payload.price).is_none() {
+						return InvalidTransaction::Call.into();
+					}
+
+					if payload.block_number > now {
+						return InvalidTransaction::Future.into();
+					}
+					// Old payloads are rejected so that nobody can replay an outdated price.
+					if now - payload.block_number > interval ||
+						!Self::can_report(&who, payload.block_number)
+					{
+						return InvalidTransaction::Stale.into();
+					}
+
+					// One transaction per authority and round, even before any is included.
+					valid_tx((who, Self::oracle_round(payload.block_number)).encode(), true)
+				},
+				Call::submit_price_unsigned { ref price, block_number } => {
+					if T::SubmissionMode::get() != SubmissionMode::Unsigned ||
+						Self::parse_price(price).is_none()
+					{
+						return InvalidTransaction::Call.into();
+					}
+					// Nothing authenticates the price, so only the local off-chain worker may put
+					// it in the pool, and only the block author it runs for includes it.
+					if !matches!(source, TransactionSource::Local | TransactionSource::InBlock) {
+						return InvalidTransaction::BadSigner.into();
+					}
+					if *block_number > now {
+						return InvalidTransaction::Future.into();
+					}
+					if now - *block_number > interval || !Self::can_report_unsigned(*block_number) {
+						return InvalidTransaction::Stale.into();
+					}
+
+					// The first transaction of each round wins.
+					valid_tx(Self::oracle_round(*block_number).encode(), false)
 				},
 				_ => InvalidTransaction::Call.into(),
 			}
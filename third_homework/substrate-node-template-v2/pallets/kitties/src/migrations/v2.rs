@@ -6,7 +6,7 @@ use frame_support::weights::Weight;
 use frame_support::migration::storage_key_iter;
 use frame_support::Blake2_128Concat;
 
-use crate::{Config, Kitties, Kitty, KittyId, Pallet};
+use crate::{Config, KittyId, Pallet};
 
 #[derive(
 	Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq, Default, TypeInfo, MaxEncodedLen,
@@ -21,23 +21,30 @@ pub struct V1Kitty {
 	pub name: [u8; 4],
 }
 
+/// The kitty layout written by this migration.
+#[derive(
+	Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq, Default, TypeInfo, MaxEncodedLen,
+)]
+pub struct V2Kitty {
+	pub dna: [u8; 16],
+	pub name: [u8; 8],
+}
+
+#[frame_support::storage_alias]
+type Kitties<T: Config> = StorageMap<Pallet<T>, Blake2_128Concat, KittyId, V2Kitty>;
+
+/// Give every kitty an 8-byte name, from the layout of version 0 or 1.
 pub fn migrate<T: Config>() -> Weight {
 	let on_chain_version = Pallet::<T>::on_chain_storage_version();
-	let current_version = Pallet::<T>::current_storage_version();
 
-	if current_version > 1 {
-		return Weight::zero();
+	if on_chain_version >= 2 {
+		return T::DbWeight::get().reads(1);
 	}
 
-	if on_chain_version == 0 {
-		return v0_to_v2::<T>();
-	}
+	let weight = if on_chain_version == 0 { v0_to_v2::<T>() } else { v1_to_v2::<T>() };
+	StorageVersion::new(2).put::<Pallet<T>>();
 
-	if on_chain_version == 1 {
-		return v1_to_v2::<T>();
-	}
-
-	Weight::zero()
+	weight.saturating_add(T::DbWeight::get().reads_writes(1, 1))
 }
 
 pub fn v0_to_v2<T: Config>() -> Weight {
@@ -47,7 +54,7 @@ pub fn v0_to_v2<T: Config>() -> Weight {
 	for (index, kitty) in
 		storage_key_iter::<KittyId, OldKitty, Blake2_128Concat>(module, item).drain()
 	{
-		let new_kitty = Kitty { dna: kitty.0, name: *b"abcd0000" };
+		let new_kitty = V2Kitty { dna: kitty.0, name: *b"abcd0000" };
 		Kitties::<T>::insert(index, new_kitty);
 	}
 
@@ -63,7 +70,7 @@ pub fn v1_to_v2<T: Config>() -> Weight {
 	{
 		let name: [u8; 8] = [kitty.name, kitty.name].concat().try_into().unwrap();
 
-		let new_kitty = Kitty { dna: kitty.dna, name };
+		let new_kitty = V2Kitty { dna: kitty.dna, name };
 		Kitties::<T>::insert(index, new_kitty);
 	}
 
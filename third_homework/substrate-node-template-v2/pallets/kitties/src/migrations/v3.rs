@@ -0,0 +1,76 @@
+use frame_support::pallet_prelude::*;
+use frame_support::traits::GetStorageVersion;
+use frame_support::weights::Weight;
+use sp_runtime::traits::Zero;
+
+use super::v2::V2Kitty;
+use crate::{Config, KittyChildren, KittyId, KittyParents, NextKittyId, Pallet};
+
+/// The kitty layout written by this migration.
+#[derive(
+	Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq, Default, TypeInfo, MaxEncodedLen,
+)]
+pub struct V3Kitty<BlockNumber> {
+	pub dna: [u8; 16],
+	pub name: [u8; 8],
+	pub generation: u32,
+	pub breeding_cooldown_until: BlockNumber,
+}
+
+#[frame_support::storage_alias]
+type Kitties<T: Config> = StorageMap<
+	Pallet<T>,
+	Blake2_128Concat,
+	KittyId,
+	V3Kitty<<T as frame_system::Config>::BlockNumber>,
+>;
+
+/// Give every kitty a generation and an expired breeding cooldown, and index every bred kitty in
+/// `KittyChildren`.
+///
+/// Generations are rebuilt from `KittyParents`. A child's id is always larger than its parents',
+/// so walking the ids in order sees every parent before its children.
+pub fn migrate<T: Config>() -> Weight {
+	let on_chain_version = Pallet::<T>::on_chain_storage_version();
+
+	if on_chain_version >= 3 {
+		return T::DbWeight::get().reads(1);
+	}
+
+	let mut translated = 0u64;
+	Kitties::<T>::translate::<V2Kitty, _>(|_kitty_id, kitty| {
+		translated += 1;
+		Some(V3Kitty {
+			dna: kitty.dna,
+			name: kitty.name,
+			generation: 0,
+			breeding_cooldown_until: Zero::zero(),
+		})
+	});
+
+	let next_kitty_id = NextKittyId::<T>::get();
+	let mut bred = 0u64;
+	for kitty_id in 0..next_kitty_id {
+		let (parent_1, parent_2) = match KittyParents::<T>::get(kitty_id) {
+			Some(parents) => parents,
+			None => continue,
+		};
+		let generation = |id: KittyId| Kitties::<T>::get(id).map_or(0, |kitty| kitty.generation);
+		let child_generation = generation(parent_1).max(generation(parent_2)).saturating_add(1);
+		Kitties::<T>::mutate(kitty_id, |kitty| {
+			if let Some(kitty) = kitty {
+				kitty.generation = child_generation;
+			}
+		});
+		KittyChildren::<T>::insert(parent_1, kitty_id, ());
+		KittyChildren::<T>::insert(parent_2, kitty_id, ());
+		bred += 1;
+	}
+
+	StorageVersion::new(3).put::<Pallet<T>>();
+
+	T::DbWeight::get().reads_writes(
+		translated + next_kitty_id as u64 + 4 * bred + 2,
+		translated + 3 * bred + 1,
+	)
+}
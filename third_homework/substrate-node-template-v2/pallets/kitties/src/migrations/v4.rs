@@ -0,0 +1,27 @@
+use frame_support::pallet_prelude::*;
+use frame_support::traits::GetStorageVersion;
+use frame_support::weights::Weight;
+
+use crate::{Config, KittyOnSale, Pallet};
+
+/// Give every listing an asking price.
+///
+/// Listings used to store `()` and sell at `KittyPrice`, so that is the price they keep.
+pub fn migrate<T: Config>() -> Weight {
+	let on_chain_version = Pallet::<T>::on_chain_storage_version();
+
+	if on_chain_version >= 4 {
+		return T::DbWeight::get().reads(1);
+	}
+
+	let price = T::KittyPrice::get();
+	let mut translated = 0u64;
+	KittyOnSale::<T>::translate::<(), _>(|_kitty_id, ()| {
+		translated += 1;
+		Some(price)
+	});
+
+	StorageVersion::new(4).put::<Pallet<T>>();
+
+	T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+}
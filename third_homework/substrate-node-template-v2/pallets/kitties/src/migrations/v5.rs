@@ -0,0 +1,79 @@
+use frame_support::pallet_prelude::*;
+use frame_support::traits::{
+	Currency, ExistenceRequirement, GetStorageVersion, ReservableCurrency,
+};
+use frame_support::weights::Weight;
+use sp_runtime::traits::AccountIdConversion;
+
+use crate::{
+	Config, DepositMigrationCursor, KittyDeposit, KittyId, KittyOwner, NextKittyId, Pallet,
+	PriceMode,
+};
+
+/// The number of kitties `step` gives a deposit to in one block.
+pub const KITTIES_PER_BLOCK: KittyId = 50;
+
+/// In `PriceMode::Deposit`, start handing the `LegacyKittyPrice` the pallet account was paid for
+/// every kitty back to its owner and reserving it as the kitty's deposit.
+///
+/// The kitties themselves are migrated by `step` from `on_initialize`, `KITTIES_PER_BLOCK` at a
+/// time.
+pub fn migrate<T: Config>() -> Weight {
+	let on_chain_version = Pallet::<T>::on_chain_storage_version();
+
+	if on_chain_version >= 5 {
+		return T::DbWeight::get().reads(1);
+	}
+
+	if T::PriceMode::get() == PriceMode::Deposit {
+		DepositMigrationCursor::<T>::put(0);
+	}
+
+	StorageVersion::new(5).put::<Pallet<T>>();
+
+	T::DbWeight::get().reads_writes(1, 2)
+}
+
+/// Give the next `KITTIES_PER_BLOCK` kitties their deposit while the version 5 migration is
+/// running.
+///
+/// Kitties the pallet account holds in escrow or can no longer pay back for are left without a
+/// deposit.
+pub fn step<T: Config>() -> Weight {
+	let cursor = match DepositMigrationCursor::<T>::get() {
+		Some(cursor) => cursor,
+		None => return T::DbWeight::get().reads(1),
+	};
+
+	let pallet_account: T::AccountId = T::PalletId::get().into_account_truncating();
+	let price = T::LegacyKittyPrice::get();
+	let next_kitty_id = NextKittyId::<T>::get();
+	let end = next_kitty_id.min(cursor.saturating_add(KITTIES_PER_BLOCK));
+
+	let mut reads = 2u64;
+	let mut writes = 1u64;
+	for kitty_id in cursor..end {
+		reads += 2;
+		let owner = match KittyOwner::<T>::get(kitty_id) {
+			Some(owner) if owner != pallet_account => owner,
+			_ => continue,
+		};
+		if KittyDeposit::<T>::contains_key(kitty_id) {
+			continue
+		}
+		let refunded =
+			T::Currency::transfer(&pallet_account, &owner, price, ExistenceRequirement::AllowDeath);
+		if refunded.is_ok() && T::Currency::reserve(&owner, price).is_ok() {
+			KittyDeposit::<T>::insert(kitty_id, price);
+			writes += 3;
+		}
+	}
+
+	if end < next_kitty_id {
+		DepositMigrationCursor::<T>::put(end);
+	} else {
+		DepositMigrationCursor::<T>::kill();
+	}
+
+	T::DbWeight::get().reads_writes(reads, writes)
+}
@@ -0,0 +1,70 @@
+use frame_support::pallet_prelude::*;
+use frame_support::traits::GetStorageVersion;
+use frame_support::weights::Weight;
+use sp_std::vec::Vec;
+
+use super::v3::V3Kitty;
+use crate::{Config, Kitties, Kitty, KittyId, KittyName, Pallet};
+
+/// Store kitty names as UTF-8 `BoundedVec`s instead of padded `[u8; 8]`s, indexing them in
+/// `KittyByName` if `UniqueNames` is set.
+///
+/// When two kitties end up with the same name, only the first one migrated is indexed.
+pub fn migrate<T: Config>() -> Weight {
+	let on_chain_version = Pallet::<T>::on_chain_storage_version();
+
+	if on_chain_version >= 6 {
+		return T::DbWeight::get().reads(1);
+	}
+
+	let mut renamed = Vec::new();
+	Kitties::<T>::translate::<V3Kitty<T::BlockNumber>, _>(|kitty_id, kitty| {
+		let name = name_from_bytes::<T>(kitty_id, kitty.name);
+		renamed.push((name.clone(), kitty_id));
+		Some(Kitty {
+			dna: kitty.dna,
+			name,
+			generation: kitty.generation,
+			breeding_cooldown_until: kitty.breeding_cooldown_until,
+		})
+	});
+
+	let mut indexed = 0u64;
+	if T::UniqueNames::get() {
+		for (name, kitty_id) in renamed.iter() {
+			if !name.is_empty() && Pallet::<T>::kitty_by_name(name).is_none() {
+				Pallet::<T>::index_name(name, *kitty_id);
+				indexed += 1;
+			}
+		}
+	}
+
+	StorageVersion::new(6).put::<Pallet<T>>();
+
+	let translated = renamed.len() as u64;
+	T::DbWeight::get().reads_writes(2 * translated + 1, translated + indexed + 1)
+}
+
+/// Recover the name a kitty was given from its `[u8; 8]` form.
+///
+/// `v1_to_v2` stored 4-byte names twice, so a name whose halves match keeps one of them. Trailing
+/// zero bytes are then dropped, as is anything from the first invalid UTF-8 byte on. A kitty left
+/// without a name is called `Kitty #<kitty_id>`.
+fn name_from_bytes<T: Config>(kitty_id: KittyId, name: [u8; 8]) -> KittyName<T> {
+	let mut name = &name[..];
+	if name[..4] == name[4..] {
+		name = &name[..4];
+	}
+	while let [rest @ .., 0] = name {
+		name = rest;
+	}
+	let name = &name[..name.len().min(T::MaxNameLength::get() as usize)];
+	let valid = match sp_std::str::from_utf8(name) {
+		Ok(_) => name.len(),
+		Err(e) => e.valid_up_to(),
+	};
+	if valid == 0 {
+		return Pallet::<T>::minted_name(kitty_id)
+	}
+	BoundedVec::truncate_from(name[..valid].to_vec())
+}
@@ -0,0 +1,44 @@
+use frame_support::pallet_prelude::*;
+use frame_support::traits::GetStorageVersion;
+use frame_support::weights::Weight;
+use sp_runtime::traits::AccountIdConversion;
+
+use crate::{Config, KittyOwner, OwnedKitties, Pallet};
+
+/// Add every kitty in `KittyOwner` to its owner's `OwnedKitties`, which kitties created before the
+/// index existed are missing from.
+///
+/// Kitties held in escrow by the pallet account are not indexed, nor are an owner's kitties beyond
+/// `MaxKittiesOwned`.
+pub fn migrate<T: Config>() -> Weight {
+	let on_chain_version = Pallet::<T>::on_chain_storage_version();
+
+	if on_chain_version >= 7 {
+		return T::DbWeight::get().reads(1);
+	}
+
+	let pallet_account: T::AccountId = T::PalletId::get().into_account_truncating();
+	let mut reads = 1u64;
+	let mut writes = 1u64;
+	for (kitty_id, owner) in KittyOwner::<T>::iter() {
+		reads += 2;
+		if owner == pallet_account {
+			continue
+		}
+		let indexed = OwnedKitties::<T>::try_mutate(&owner, |kitties| {
+			if kitties.contains(&kitty_id) {
+				return Ok(false)
+			}
+			kitties.try_push(kitty_id).map(|_| true)
+		});
+		match indexed {
+			Ok(true) => writes += 1,
+			Ok(false) => {},
+			Err(_) => log::warn!("kitty {} of {:?} does not fit in OwnedKitties", kitty_id, owner),
+		}
+	}
+
+	StorageVersion::new(7).put::<Pallet<T>>();
+
+	T::DbWeight::get().reads_writes(reads, writes)
+}
@@ -1,21 +1,28 @@
 use crate as pallet_kitties;
+use crate::{PriceMode, SubmissionMode};
 use frame_support::{
 	parameter_types,
 	traits::{ConstU16, ConstU64},
 	PalletId,
 };
 
+use frame_system::offchain::{
+	AppCrypto, CreateSignedTransaction, SendTransactionTypes, SigningTypes,
+};
 use pallet_balances;
 use pallet_insecure_randomness_collective_flip;
 use sp_core::{ConstU128, ConstU32, H256};
 use sp_runtime::{
-	testing::Header,
-	traits::{BlakeTwo256, IdentityLookup},
+	testing::{Header, TestSignature, TestXt, UintAuthorityId},
+	traits::{BlakeTwo256, Extrinsic as ExtrinsicT, IdentityLookup},
+	FixedU128,
 };
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
 
+pub type Extrinsic = TestXt<RuntimeCall, ()>;
+
 // Configure a mock runtime to test the pallet.
 frame_support::construct_runtime!(
 	pub enum Test where
@@ -57,12 +64,63 @@ impl frame_system::Config for Test {
 	type MaxConsumers = frame_support::traits::ConstU32<16>;
 }
 
+impl SigningTypes for Test {
+	type Public = UintAuthorityId;
+	type Signature = TestSignature;
+}
+
+impl<LocalCall> SendTransactionTypes<LocalCall> for Test
+where
+	RuntimeCall: From<LocalCall>,
+{
+	type OverarchingCall = RuntimeCall;
+	type Extrinsic = Extrinsic;
+}
+
+impl<LocalCall> CreateSignedTransaction<LocalCall> for Test
+where
+	RuntimeCall: From<LocalCall>,
+{
+	fn create_transaction<C: AppCrypto<Self::Public, Self::Signature>>(
+		call: RuntimeCall,
+		_public: UintAuthorityId,
+		_account: u64,
+		nonce: u64,
+	) -> Option<(RuntimeCall, <Extrinsic as ExtrinsicT>::SignaturePayload)> {
+		Some((call, (nonce, ())))
+	}
+}
+
+/// Off-chain worker keys of the mock runtime, whose account is their number.
+pub struct TestAuthId;
+
+impl AppCrypto<UintAuthorityId, TestSignature> for TestAuthId {
+	type RuntimeAppPublic = UintAuthorityId;
+	type GenericPublic = UintAuthorityId;
+	type GenericSignature = TestSignature;
+}
+
 pub const EXISTENTIAL_DEPOSIT: u128 = 500;
 pub type Balance = u128;
 
 parameter_types! {
 	pub  KittyPrice: Balance = EXISTENTIAL_DEPOSIT * 100;
+	pub const LegacyKittyPrice: Balance = EXISTENTIAL_DEPOSIT * 100;
 	pub const KittyPalletId: PalletId = PalletId(*b"py/kitty");
+	pub static KittyPriceMode: PriceMode = PriceMode::Payment;
+	pub const KittyPriceUsd: FixedU128 = FixedU128::from_u32(10);
+	pub const NativeTokensPerBtc: FixedU128 = FixedU128::from_u32(2);
+	pub const MinBtcPrice: FixedU128 = FixedU128::from_u32(1);
+	pub const MaxBtcPrice: FixedU128 = FixedU128::from_u32(1_000_000);
+	pub static UniqueKittyNames: bool = false;
+	pub static OcwSubmissionMode: SubmissionMode = SubmissionMode::UnsignedWithSignedPayload;
+	pub static OracleBtcPrice: Option<(FixedU128, u64)> = None;
+}
+
+impl pallet_kitties::BtcPriceFeed<u64> for OracleBtcPrice {
+	fn btc_price() -> Option<(FixedU128, u64)> {
+		Self::get()
+	}
 }
 
 impl pallet_kitties::Config for Test {
@@ -70,7 +128,27 @@ impl pallet_kitties::Config for Test {
 	type Randomness = Randomness;
 	type Currency = Balances;
 	type KittyPrice = KittyPrice;
+	type PriceMode = KittyPriceMode;
+	type LegacyKittyPrice = LegacyKittyPrice;
+	type KittyPriceUsd = KittyPriceUsd;
+	type NativeTokensPerBtc = NativeTokensPerBtc;
+	type NativeUnit = ConstU128<1_000>;
+	type BtcPriceFeed = OracleBtcPrice;
+	type PriceStaleAfter = ConstU64<10>;
+	type MinBtcPrice = MinBtcPrice;
+	type MaxBtcPrice = MaxBtcPrice;
+	type MaxKittiesOwned = ConstU32<3>;
+	type MaxNameLength = ConstU32<16>;
+	type UniqueNames = UniqueKittyNames;
+	type BreedingCooldownPeriod = ConstU64<5>;
+	type MaxAuctionDuration = ConstU64<100>;
+	type AuctionExtension = ConstU64<3>;
+	type MaxAuctionsPerBlock = ConstU32<2>;
+	type MaxOracleAuthorities = ConstU32<2>;
+	type OracleSubmissionInterval = ConstU64<5>;
+	type SubmissionMode = OcwSubmissionMode;
 	type PalletId = KittyPalletId;
+	type AuthorityId = TestAuthId;
 }
 
 impl pallet_balances::Config for Test {
@@ -0,0 +1,15 @@
+//! Prices reported by the off-chain worker's price feed.
+
+use sp_runtime::FixedU128;
+
+/// A source of the USD price of one BTC, and the block it was last updated in.
+pub trait BtcPriceFeed<BlockNumber> {
+	fn btc_price() -> Option<(FixedU128, BlockNumber)>;
+}
+
+/// No feed, leaving kitty prices to `LatestBtcPrice`.
+impl<BlockNumber> BtcPriceFeed<BlockNumber> for () {
+	fn btc_price() -> Option<(FixedU128, BlockNumber)> {
+		None
+	}
+}
@@ -1,5 +1,31 @@
-use crate::{mock::*, Error, Event};
-use frame_support::{assert_noop, assert_ok};
+use crate::{
+	genes, migrations, mock::*, DepositMigrationCursor, Error, Event, Genes, Kitties,
+	KittyChildren, KittyName, KittyOwner, LatestBtcPrice, NextKittyId, OwnedKitties, Payload,
+	PriceMode, SubmissionMode,
+};
+use codec::{Decode, Encode};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{GetStorageVersion, Hooks, ReservableCurrency, StorageVersion},
+	BoundedVec,
+};
+use sp_core::offchain::{
+	testing, OffchainDbExt, OffchainWorkerExt, TransactionPool, TransactionPoolExt,
+};
+use sp_runtime::{
+	testing::{TestSignature, UintAuthorityId},
+	traits::{AccountIdConversion, ValidateUnsigned},
+	transaction_validity::{InvalidTransaction, TransactionSource},
+	DispatchError, FixedU128,
+};
+use std::sync::{
+	atomic::{AtomicU32, Ordering},
+	Arc,
+};
+
+fn name(name: &[u8]) -> KittyName<Test> {
+	name.to_vec().try_into().unwrap()
+}
 
 #[test]
 fn it_works_for_create() {
@@ -13,7 +39,7 @@ fn it_works_for_create() {
 		}
 
 		assert_eq!(KittiesModule::next_kitty_id(), kitty_id);
-		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), *b"aaaa0000"));
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), name(b"aaaa0000")));
 
 		assert_eq!(KittiesModule::next_kitty_id(), kitty_id + 1);
 		assert_eq!(KittiesModule::kitties(kitty_id).is_some(), true);
@@ -23,7 +49,7 @@ fn it_works_for_create() {
 		crate::NextKittyId::<Test>::set(crate::KittyId::max_value());
 
 		assert_noop!(
-			KittiesModule::create(RuntimeOrigin::signed(account_id), *b"aaaa0000"),
+			KittiesModule::create(RuntimeOrigin::signed(account_id), name(b"aaaa0000")),
 			Error::<Test>::InvalidKittyId
 		);
 	});
@@ -45,7 +71,7 @@ fn it_works_for_breed() {
 				RuntimeOrigin::signed(account_id),
 				kitty_id,
 				kitty_id,
-				*b"aaaa0000"
+				name(b"aaaa0000")
 			),
 			Error::<Test>::SameKittyId
 		);
@@ -55,21 +81,31 @@ fn it_works_for_breed() {
 				RuntimeOrigin::signed(account_id),
 				kitty_id,
 				kitty_id + 1,
-				*b"aaaa0000"
+				name(b"aaaa0000")
 			),
 			Error::<Test>::InvalidKittyId
 		);
 
-		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), *b"aaaa0000"));
-		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), *b"aaaa0000"));
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), name(b"aaaa0000")));
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), name(b"aaaa0000")));
 
 		assert_eq!(KittiesModule::next_kitty_id(), kitty_id + 2);
 
+		assert_noop!(
+			KittiesModule::breed(
+				RuntimeOrigin::signed(account_id + 1),
+				kitty_id,
+				kitty_id + 1,
+				name(b"aaaa0000")
+			),
+			Error::<Test>::NotOwner
+		);
+
 		assert_ok!(KittiesModule::breed(
 			RuntimeOrigin::signed(account_id),
 			kitty_id,
 			kitty_id + 1,
-			*b"aaaa0000"
+			name(b"aaaa0000")
 		));
 
 		let breed_kitty_id = 2;
@@ -97,7 +133,7 @@ fn it_works_for_transfer() {
 			Balances::force_set_balance(RuntimeOrigin::root(), recipient, 1_000_000_000);
 		}
 
-		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), *b"aaaa0000"));
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), name(b"aaaa0000")));
 		assert_eq!(KittiesModule::kitty_owner(kitty_id), Some(account_id));
 
 		assert_noop!(
@@ -126,7 +162,7 @@ fn it_works_for_create_event() {
 			Balances::force_set_balance(RuntimeOrigin::root(), account_id, 1_000_000_000);
 		}
 
-		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), *b"aaaa0000"));
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), name(b"aaaa0000")));
 
 		System::assert_has_event(
 			Event::KittyCreated {
@@ -150,13 +186,13 @@ fn it_works_for_bred_event() {
 			Balances::force_set_balance(RuntimeOrigin::root(), account_id, 1_000_000_000);
 		}
 
-		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), *b"aaaa0000"));
-		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), *b"aaaa0000"));
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), name(b"aaaa0000")));
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), name(b"aaaa0000")));
 		assert_ok!(KittiesModule::breed(
 			RuntimeOrigin::signed(account_id),
 			kitty_id,
 			kitty_id + 1,
-			*b"aaaa0000"
+			name(b"aaaa0000")
 		));
 
 		System::assert_has_event(
@@ -183,7 +219,7 @@ fn it_works_for_transferred_event() {
 			Balances::force_set_balance(RuntimeOrigin::root(), recipient, 1_000_000_000);
 		}
 
-		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), *b"aaaa0000"));
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), name(b"aaaa0000")));
 		assert_ok!(KittiesModule::transfer(RuntimeOrigin::signed(account_id), recipient, kitty_id));
 
 		System::assert_has_event(
@@ -204,24 +240,26 @@ fn it_works_for_sale() {
 		}
 
 		assert_noop!(
-			KittiesModule::sale(RuntimeOrigin::signed(account_id), kitty_id),
+			KittiesModule::sale(RuntimeOrigin::signed(account_id), kitty_id, 500),
 			Error::<Test>::InvalidKittyId
 		);
 
-		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), *b"hoodyboo"));
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), name(b"hoodyboo")));
 
 		assert_noop!(
-			KittiesModule::sale(RuntimeOrigin::signed(account_id_2), kitty_id),
+			KittiesModule::sale(RuntimeOrigin::signed(account_id_2), kitty_id, 500),
 			Error::<Test>::NotOwner
 		);
 
-		assert_ok!(KittiesModule::sale(RuntimeOrigin::signed(account_id), kitty_id));
+		assert_ok!(KittiesModule::sale(RuntimeOrigin::signed(account_id), kitty_id, 500));
 		assert!(KittiesModule::kitty_on_sale(kitty_id).is_some());
 
-		System::assert_has_event(Event::KittyOnSale { who: account_id, kitty_id }.into());
+		System::assert_has_event(
+			Event::KittyOnSale { who: account_id, kitty_id, price: 500 }.into(),
+		);
 
 		assert_noop!(
-			KittiesModule::sale(RuntimeOrigin::signed(account_id), kitty_id),
+			KittiesModule::sale(RuntimeOrigin::signed(account_id), kitty_id, 500),
 			Error::<Test>::AlreadyOnSale
 		);
 	});
@@ -241,30 +279,1685 @@ fn it_works_for_buy() {
 		}
 
 		assert_noop!(
-			KittiesModule::buy(RuntimeOrigin::signed(account_id), kitty_id),
+			KittiesModule::buy(RuntimeOrigin::signed(account_id), kitty_id, 500),
 			Error::<Test>::InvalidKittyId
 		);
 
-		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), *b"helobudy"));
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), name(b"helobudy")));
 
 		assert_noop!(
-			KittiesModule::buy(RuntimeOrigin::signed(buy_account_id), kitty_id),
+			KittiesModule::buy(RuntimeOrigin::signed(buy_account_id), kitty_id, 500),
 			Error::<Test>::NotOnSale
 		);
 
-		assert_ok!(KittiesModule::sale(RuntimeOrigin::signed(account_id), kitty_id));
+		assert_ok!(KittiesModule::sale(RuntimeOrigin::signed(account_id), kitty_id, 500));
 
 		assert_noop!(
-			KittiesModule::buy(RuntimeOrigin::signed(account_id), kitty_id),
+			KittiesModule::buy(RuntimeOrigin::signed(account_id), kitty_id, 500),
 			Error::<Test>::AlreadyOwned
 		);
 
-		assert_ok!(KittiesModule::buy(RuntimeOrigin::signed(buy_account_id), kitty_id));
+		assert_ok!(KittiesModule::buy(RuntimeOrigin::signed(buy_account_id), kitty_id, 500));
 
-		System::assert_has_event(Event::KittyBought { who: buy_account_id, kitty_id }.into());
+		System::assert_has_event(
+			Event::KittyBought { who: buy_account_id, seller: account_id, kitty_id, price: 500 }
+				.into(),
+		);
 
 		assert!(KittiesModule::kitty_on_sale(kitty_id).is_none());
 
 		assert_eq!(KittiesModule::kitty_owner(kitty_id), Some(buy_account_id));
 	});
 }
+
+#[test]
+fn buy_pays_the_listed_price() {
+	new_test_ext().execute_with(|| {
+		let kitty_id = 0;
+		let account_id = 1;
+		let buy_account_id = 2;
+
+		#[allow(unused_must_use)]
+		{
+			Balances::force_set_balance(RuntimeOrigin::root(), account_id, 1_000_000_000);
+			Balances::force_set_balance(RuntimeOrigin::root(), buy_account_id, 1_000_000_000);
+		}
+
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), name(b"helobudy")));
+
+		assert_noop!(
+			KittiesModule::update_price(RuntimeOrigin::signed(account_id), kitty_id, 800),
+			Error::<Test>::NotOnSale
+		);
+		assert_ok!(KittiesModule::sale(RuntimeOrigin::signed(account_id), kitty_id, 500));
+		assert_noop!(
+			KittiesModule::update_price(RuntimeOrigin::signed(buy_account_id), kitty_id, 800),
+			Error::<Test>::NotOwner
+		);
+		assert_ok!(KittiesModule::update_price(RuntimeOrigin::signed(account_id), kitty_id, 800));
+		assert_eq!(KittiesModule::kitty_on_sale(kitty_id), Some(800));
+		System::assert_has_event(
+			Event::KittyPriceUpdated { who: account_id, kitty_id, price: 800 }.into(),
+		);
+
+		assert_noop!(
+			KittiesModule::buy(RuntimeOrigin::signed(buy_account_id), kitty_id, 500),
+			Error::<Test>::PriceTooHigh
+		);
+
+		let seller_balance = Balances::free_balance(account_id);
+		let buyer_balance = Balances::free_balance(buy_account_id);
+		assert_ok!(KittiesModule::buy(RuntimeOrigin::signed(buy_account_id), kitty_id, 1_000));
+		assert_eq!(Balances::free_balance(account_id), seller_balance + 800);
+		assert_eq!(Balances::free_balance(buy_account_id), buyer_balance - 800);
+	});
+}
+
+#[test]
+fn listings_are_cleared() {
+	new_test_ext().execute_with(|| {
+		let kitty_id = 0;
+		let account_id = 1;
+		let account_id_2 = 2;
+
+		#[allow(unused_must_use)]
+		{
+			Balances::force_set_balance(RuntimeOrigin::root(), account_id, 1_000_000_000);
+		}
+
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), name(b"helobudy")));
+
+		assert_noop!(
+			KittiesModule::cancel_sale(RuntimeOrigin::signed(account_id), kitty_id),
+			Error::<Test>::NotOnSale
+		);
+		assert_ok!(KittiesModule::sale(RuntimeOrigin::signed(account_id), kitty_id, 500));
+		assert_noop!(
+			KittiesModule::cancel_sale(RuntimeOrigin::signed(account_id_2), kitty_id),
+			Error::<Test>::NotOwner
+		);
+		assert_ok!(KittiesModule::cancel_sale(RuntimeOrigin::signed(account_id), kitty_id));
+		assert!(KittiesModule::kitty_on_sale(kitty_id).is_none());
+		System::assert_has_event(Event::KittySaleCancelled { who: account_id, kitty_id }.into());
+
+		assert_ok!(KittiesModule::sale(RuntimeOrigin::signed(account_id), kitty_id, 500));
+		assert_ok!(KittiesModule::transfer(
+			RuntimeOrigin::signed(account_id),
+			account_id_2,
+			kitty_id
+		));
+		assert!(KittiesModule::kitty_on_sale(kitty_id).is_none());
+	});
+}
+
+fn run_to_block(n: u64) {
+	while System::block_number() < n {
+		System::set_block_number(System::block_number() + 1);
+		KittiesModule::on_initialize(System::block_number());
+	}
+}
+
+#[test]
+fn auction_goes_to_the_highest_bidder() {
+	new_test_ext().execute_with(|| {
+		let kitty_id = 0;
+		let seller = 1;
+		let bidder_1 = 2;
+		let bidder_2 = 3;
+
+		#[allow(unused_must_use)]
+		{
+			Balances::force_set_balance(RuntimeOrigin::root(), seller, 1_000_000_000);
+			Balances::force_set_balance(RuntimeOrigin::root(), bidder_1, 1_000_000_000);
+			Balances::force_set_balance(RuntimeOrigin::root(), bidder_2, 1_000_000_000);
+		}
+
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(seller), name(b"helobudy")));
+		assert_noop!(
+			KittiesModule::start_auction(RuntimeOrigin::signed(seller), kitty_id, 1_000, 0),
+			Error::<Test>::InvalidAuctionDuration
+		);
+		assert_ok!(KittiesModule::start_auction(
+			RuntimeOrigin::signed(seller),
+			kitty_id,
+			1_000,
+			10
+		));
+		System::assert_has_event(
+			Event::AuctionStarted { who: seller, kitty_id, reserve: 1_000, end: 11 }.into(),
+		);
+
+		assert_noop!(
+			KittiesModule::transfer(RuntimeOrigin::signed(seller), bidder_1, kitty_id),
+			Error::<Test>::KittyInAuction
+		);
+		assert_noop!(
+			KittiesModule::sale(RuntimeOrigin::signed(seller), kitty_id, 500),
+			Error::<Test>::KittyInAuction
+		);
+		assert_noop!(
+			KittiesModule::bid(RuntimeOrigin::signed(seller), kitty_id, 1_000),
+			Error::<Test>::AlreadyOwned
+		);
+		assert_noop!(
+			KittiesModule::bid(RuntimeOrigin::signed(bidder_1), kitty_id, 999),
+			Error::<Test>::BidTooLow
+		);
+
+		assert_ok!(KittiesModule::bid(RuntimeOrigin::signed(bidder_1), kitty_id, 1_000));
+		assert_eq!(Balances::reserved_balance(bidder_1), 1_000);
+		assert_noop!(
+			KittiesModule::bid(RuntimeOrigin::signed(bidder_2), kitty_id, 1_000),
+			Error::<Test>::BidTooLow
+		);
+		assert_ok!(KittiesModule::bid(RuntimeOrigin::signed(bidder_2), kitty_id, 1_500));
+		assert_eq!(Balances::reserved_balance(bidder_1), 0);
+		assert_eq!(Balances::reserved_balance(bidder_2), 1_500);
+
+		let seller_balance = Balances::free_balance(seller);
+		run_to_block(11);
+		assert!(KittiesModule::auctions(kitty_id).is_none());
+		assert_eq!(KittiesModule::kitty_owner(kitty_id), Some(bidder_2));
+		assert_eq!(KittiesModule::owned_kitties(bidder_2).to_vec(), vec![kitty_id]);
+		assert!(KittiesModule::owned_kitties(seller).is_empty());
+		assert_eq!(Balances::reserved_balance(bidder_2), 0);
+		assert_eq!(Balances::free_balance(seller), seller_balance + 1_500);
+		System::assert_has_event(
+			Event::AuctionSettled { kitty_id, seller, winner: Some(bidder_2), price: Some(1_500) }
+				.into(),
+		);
+	});
+}
+
+#[test]
+fn auction_without_bids_returns_the_kitty() {
+	new_test_ext().execute_with(|| {
+		let kitty_id = 0;
+		let seller = 1;
+
+		#[allow(unused_must_use)]
+		{
+			Balances::force_set_balance(RuntimeOrigin::root(), seller, 1_000_000_000);
+		}
+
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(seller), name(b"helobudy")));
+		assert_ok!(KittiesModule::start_auction(RuntimeOrigin::signed(seller), kitty_id, 1_000, 5));
+
+		run_to_block(6);
+		assert!(KittiesModule::auctions(kitty_id).is_none());
+		assert_eq!(KittiesModule::kitty_owner(kitty_id), Some(seller));
+		System::assert_has_event(
+			Event::AuctionSettled { kitty_id, seller, winner: None, price: None }.into(),
+		);
+		assert_ok!(KittiesModule::transfer(RuntimeOrigin::signed(seller), 2, kitty_id));
+	});
+}
+
+#[test]
+fn failed_auction_settlement_refunds_the_bid() {
+	new_test_ext().execute_with(|| {
+		let kitty_id = 0;
+		let seller = 1;
+		let bidder = 2;
+
+		#[allow(unused_must_use)]
+		{
+			Balances::force_set_balance(RuntimeOrigin::root(), seller, 1_000_000_000);
+			Balances::force_set_balance(RuntimeOrigin::root(), bidder, 1_000_000_000);
+		}
+
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(seller), name(b"helobudy")));
+		assert_ok!(KittiesModule::start_auction(RuntimeOrigin::signed(seller), kitty_id, 1_000, 5));
+		assert_ok!(KittiesModule::bid(RuntimeOrigin::signed(bidder), kitty_id, 1_000));
+		// Something else released part of the bid before the auction ended.
+		Balances::unreserve(&bidder, 400);
+
+		let seller_balance = Balances::free_balance(seller);
+		run_to_block(6);
+		assert!(KittiesModule::auctions(kitty_id).is_none());
+		assert_eq!(KittiesModule::kitty_owner(kitty_id), Some(seller));
+		assert_eq!(KittiesModule::owned_kitties(seller).to_vec(), vec![kitty_id]);
+		assert!(KittiesModule::owned_kitties(bidder).is_empty());
+		assert_eq!(Balances::reserved_balance(bidder), 0);
+		assert_eq!(Balances::free_balance(seller), seller_balance);
+		System::assert_has_event(
+			Event::AuctionSettlementFailed {
+				kitty_id,
+				bidder,
+				amount: 1_000,
+				error: Error::<Test>::BidNotReserved.into(),
+			}
+			.into(),
+		);
+		System::assert_has_event(
+			Event::AuctionSettled { kitty_id, seller, winner: None, price: None }.into(),
+		);
+	});
+}
+
+#[test]
+fn late_bid_extends_the_auction() {
+	new_test_ext().execute_with(|| {
+		let kitty_id = 0;
+		let seller = 1;
+		let bidder = 2;
+
+		#[allow(unused_must_use)]
+		{
+			Balances::force_set_balance(RuntimeOrigin::root(), seller, 1_000_000_000);
+			Balances::force_set_balance(RuntimeOrigin::root(), bidder, 1_000_000_000);
+		}
+
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(seller), name(b"helobudy")));
+		assert_ok!(KittiesModule::start_auction(RuntimeOrigin::signed(seller), kitty_id, 1_000, 5));
+
+		run_to_block(5);
+		assert_ok!(KittiesModule::bid(RuntimeOrigin::signed(bidder), kitty_id, 1_000));
+		System::assert_has_event(
+			Event::BidPlaced { who: bidder, kitty_id, amount: 1_000, end: 8 }.into(),
+		);
+
+		run_to_block(7);
+		assert_eq!(KittiesModule::kitty_owner(kitty_id), Some(seller));
+		run_to_block(8);
+		assert_eq!(KittiesModule::kitty_owner(kitty_id), Some(bidder));
+	});
+}
+
+#[test]
+fn owned_kitties_follow_the_owner() {
+	new_test_ext().execute_with(|| {
+		let account_id = 1;
+		let buy_account_id = 2;
+
+		#[allow(unused_must_use)]
+		{
+			Balances::force_set_balance(RuntimeOrigin::root(), account_id, 1_000_000_000);
+			Balances::force_set_balance(RuntimeOrigin::root(), buy_account_id, 1_000_000_000);
+		}
+
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), name(b"aaaa0000")));
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), name(b"bbbb0000")));
+		assert_ok!(KittiesModule::breed(
+			RuntimeOrigin::signed(account_id),
+			0,
+			1,
+			name(b"cccc0000")
+		));
+		assert_eq!(KittiesModule::owned_kitties(account_id).to_vec(), vec![0, 1, 2]);
+
+		assert_ok!(KittiesModule::transfer(RuntimeOrigin::signed(account_id), buy_account_id, 1));
+		assert_ok!(KittiesModule::sale(RuntimeOrigin::signed(account_id), 2, 500));
+		assert_ok!(KittiesModule::buy(RuntimeOrigin::signed(buy_account_id), 2, 500));
+
+		assert_eq!(KittiesModule::owned_kitties(account_id).to_vec(), vec![0]);
+		assert_eq!(KittiesModule::kitties_of(buy_account_id, 0, 10), vec![1, 2]);
+		assert_eq!(KittiesModule::kitties_of(buy_account_id, 1, 1), vec![2]);
+		assert!(KittiesModule::kitties_of(buy_account_id, 2, 10).is_empty());
+	});
+}
+
+#[test]
+fn too_many_kitties() {
+	new_test_ext().execute_with(|| {
+		let account_id = 1;
+		let other_account_id = 2;
+
+		#[allow(unused_must_use)]
+		{
+			Balances::force_set_balance(RuntimeOrigin::root(), account_id, 1_000_000_000);
+			Balances::force_set_balance(RuntimeOrigin::root(), other_account_id, 1_000_000_000);
+		}
+
+		for _ in 0..3 {
+			assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), name(b"aaaa0000")));
+		}
+		assert_ok!(KittiesModule::create(
+			RuntimeOrigin::signed(other_account_id),
+			name(b"bbbb0000")
+		));
+
+		assert_noop!(
+			KittiesModule::create(RuntimeOrigin::signed(account_id), name(b"aaaa0000")),
+			Error::<Test>::TooManyKitties
+		);
+		assert_noop!(
+			KittiesModule::breed(RuntimeOrigin::signed(account_id), 0, 1, name(b"aaaa0000")),
+			Error::<Test>::TooManyKitties
+		);
+
+		assert_ok!(KittiesModule::sale(RuntimeOrigin::signed(other_account_id), 3, 500));
+		assert_noop!(
+			KittiesModule::buy(RuntimeOrigin::signed(account_id), 3, 500),
+			Error::<Test>::TooManyKitties
+		);
+	});
+}
+
+#[test]
+fn bred_kitty_mixes_parent_dna() {
+	new_test_ext().execute_with(|| {
+		let account_id = 1;
+
+		#[allow(unused_must_use)]
+		{
+			Balances::force_set_balance(RuntimeOrigin::root(), account_id, 1_000_000_000);
+		}
+
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), name(b"aaaa0000")));
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), name(b"bbbb0000")));
+		assert_ok!(KittiesModule::breed(
+			RuntimeOrigin::signed(account_id),
+			0,
+			1,
+			name(b"cccc0000")
+		));
+
+		let parent_1 = KittiesModule::kitties(0).unwrap();
+		let parent_2 = KittiesModule::kitties(1).unwrap();
+		let child = KittiesModule::kitties(2).unwrap();
+		for i in 0..16 {
+			// Every bit of the child comes from one of the parents.
+			assert_eq!(child.dna[i] & !(parent_1.dna[i] | parent_2.dna[i]), 0);
+			assert_eq!(
+				child.dna[i] & (parent_1.dna[i] & parent_2.dna[i]),
+				parent_1.dna[i] & parent_2.dna[i]
+			);
+		}
+		assert_eq!(child.genes(), Genes::from_dna(&child.dna));
+		assert_eq!(parent_1.generation, 0);
+		assert_eq!(child.generation, 1);
+	});
+}
+
+#[test]
+fn breeding_respects_cooldown() {
+	new_test_ext().execute_with(|| {
+		let account_id = 1;
+
+		#[allow(unused_must_use)]
+		{
+			Balances::force_set_balance(RuntimeOrigin::root(), account_id, 1_000_000_000);
+		}
+
+		for _ in 0..3 {
+			assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), name(b"aaaa0000")));
+		}
+		assert_ok!(KittiesModule::breed(
+			RuntimeOrigin::signed(account_id),
+			0,
+			1,
+			name(b"bbbb0000")
+		));
+		assert_eq!(KittiesModule::kitties(0).unwrap().breeding_cooldown_until, 6);
+		assert_eq!(KittiesModule::kitties(3).unwrap().breeding_cooldown_until, 6);
+
+		assert_noop!(
+			KittiesModule::breed(RuntimeOrigin::signed(account_id), 0, 2, name(b"cccc0000")),
+			Error::<Test>::BreedingCooldown
+		);
+		assert_noop!(
+			KittiesModule::breed(RuntimeOrigin::signed(account_id), 2, 3, name(b"cccc0000")),
+			Error::<Test>::BreedingCooldown
+		);
+
+		System::set_block_number(6);
+		assert_ok!(KittiesModule::breed(
+			RuntimeOrigin::signed(account_id),
+			2,
+			3,
+			name(b"cccc0000")
+		));
+		assert_eq!(KittiesModule::kitties(4).unwrap().generation, 2);
+	});
+}
+
+#[test]
+fn breeding_rejects_inbreeding() {
+	new_test_ext().execute_with(|| {
+		let account_id = 1;
+
+		#[allow(unused_must_use)]
+		{
+			Balances::force_set_balance(RuntimeOrigin::root(), account_id, 1_000_000_000);
+		}
+
+		for _ in 0..3 {
+			assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), name(b"aaaa0000")));
+		}
+		assert_ok!(KittiesModule::breed(
+			RuntimeOrigin::signed(account_id),
+			0,
+			1,
+			name(b"bbbb0000")
+		));
+		System::set_block_number(6);
+		assert_ok!(KittiesModule::breed(
+			RuntimeOrigin::signed(account_id),
+			0,
+			1,
+			name(b"bbbb0000")
+		));
+		assert!(KittyChildren::<Test>::contains_key(0, 3));
+		assert!(KittyChildren::<Test>::contains_key(1, 4));
+		System::set_block_number(12);
+
+		assert_noop!(
+			KittiesModule::breed(RuntimeOrigin::signed(account_id), 3, 4, name(b"cccc0000")),
+			Error::<Test>::InbreedingNotAllowed
+		);
+		assert_noop!(
+			KittiesModule::breed(RuntimeOrigin::signed(account_id), 0, 3, name(b"cccc0000")),
+			Error::<Test>::InbreedingNotAllowed
+		);
+		assert_noop!(
+			KittiesModule::breed(RuntimeOrigin::signed(account_id), 4, 1, name(b"cccc0000")),
+			Error::<Test>::InbreedingNotAllowed
+		);
+		assert_ok!(KittiesModule::breed(
+			RuntimeOrigin::signed(account_id),
+			3,
+			2,
+			name(b"cccc0000")
+		));
+	});
+}
+
+#[test]
+fn lineage_is_walked_to_depth() {
+	new_test_ext().execute_with(|| {
+		let account_id = 1;
+
+		#[allow(unused_must_use)]
+		{
+			Balances::force_set_balance(RuntimeOrigin::root(), account_id, 1_000_000_000);
+		}
+
+		for _ in 0..3 {
+			assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), name(b"aaaa0000")));
+		}
+		assert_ok!(KittiesModule::breed(
+			RuntimeOrigin::signed(account_id),
+			0,
+			1,
+			name(b"bbbb0000")
+		));
+		System::set_block_number(6);
+		assert_ok!(KittiesModule::breed(
+			RuntimeOrigin::signed(account_id),
+			0,
+			1,
+			name(b"bbbb0000")
+		));
+		assert_ok!(KittiesModule::breed(
+			RuntimeOrigin::signed(account_id),
+			3,
+			2,
+			name(b"cccc0000")
+		));
+
+		assert_eq!(KittiesModule::ancestors(5, 0), vec![]);
+		assert_eq!(KittiesModule::ancestors(5, 1), vec![(5, (3, 2))]);
+		assert_eq!(KittiesModule::ancestors(5, 2), vec![(5, (3, 2)), (3, (0, 1))]);
+		assert_eq!(KittiesModule::ancestors(0, 2), vec![]);
+
+		let mut children = KittiesModule::descendants(0, 1);
+		children.sort();
+		assert_eq!(children, vec![(3, (0, 1)), (4, (0, 1))]);
+
+		let mut descendants = KittiesModule::descendants(0, 2);
+		descendants.sort();
+		assert_eq!(descendants, vec![(3, (0, 1)), (4, (0, 1)), (5, (3, 2))]);
+	});
+}
+
+#[test]
+fn genes_are_read_from_dna() {
+	let mut dna = [0u8; 16];
+	dna[0] = 10;
+	dna[1] = 7;
+	dna[2] = 3;
+
+	let genes = Genes::from_dna(&dna);
+	assert_eq!(genes.colour, genes::Colour::Ginger);
+	assert_eq!(genes.pattern, genes::Pattern::Tabby);
+	assert_eq!(genes.eye_shape, genes::EyeShape::Sleepy);
+}
+
+#[test]
+fn deposit_follows_the_kitty_until_burned() {
+	new_test_ext().execute_with(|| {
+		KittyPriceMode::set(PriceMode::Deposit);
+		let kitty_id = 0;
+		let account_id = 1;
+		let account_id_2 = 2;
+		let buy_account_id = 3;
+
+		#[allow(unused_must_use)]
+		{
+			Balances::force_set_balance(RuntimeOrigin::root(), account_id, 1_000_000_000);
+			Balances::force_set_balance(RuntimeOrigin::root(), account_id_2, 1_000_000_000);
+			Balances::force_set_balance(RuntimeOrigin::root(), buy_account_id, 1_000_000_000);
+		}
+
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), name(b"helobudy")));
+		assert_eq!(KittiesModule::kitty_deposit(kitty_id), Some(KittyPrice::get()));
+		assert_eq!(Balances::reserved_balance(account_id), KittyPrice::get());
+
+		assert_ok!(KittiesModule::transfer(
+			RuntimeOrigin::signed(account_id),
+			account_id_2,
+			kitty_id
+		));
+		assert_eq!(Balances::reserved_balance(account_id), 0);
+		assert_eq!(Balances::reserved_balance(account_id_2), KittyPrice::get());
+
+		assert_ok!(KittiesModule::sale(RuntimeOrigin::signed(account_id_2), kitty_id, 500));
+		assert_ok!(KittiesModule::buy(RuntimeOrigin::signed(buy_account_id), kitty_id, 500));
+		assert_eq!(Balances::reserved_balance(account_id_2), 0);
+		assert_eq!(Balances::reserved_balance(buy_account_id), KittyPrice::get());
+
+		assert_noop!(
+			KittiesModule::burn(RuntimeOrigin::signed(account_id_2), kitty_id),
+			Error::<Test>::NotOwner
+		);
+		let balance = Balances::free_balance(buy_account_id);
+		assert_ok!(KittiesModule::burn(RuntimeOrigin::signed(buy_account_id), kitty_id));
+		assert_eq!(Balances::reserved_balance(buy_account_id), 0);
+		assert_eq!(Balances::free_balance(buy_account_id), balance + KittyPrice::get());
+		assert!(KittiesModule::kitties(kitty_id).is_none());
+		assert!(KittiesModule::kitty_owner(kitty_id).is_none());
+		assert!(KittiesModule::kitty_deposit(kitty_id).is_none());
+		assert!(KittiesModule::owned_kitties(buy_account_id).is_empty());
+		System::assert_has_event(
+			Event::KittyBurned { who: buy_account_id, kitty_id, deposit: KittyPrice::get() }.into(),
+		);
+	});
+}
+
+#[test]
+fn transfer_fails_if_the_deposit_is_not_fully_reserved() {
+	new_test_ext().execute_with(|| {
+		KittyPriceMode::set(PriceMode::Deposit);
+		let kitty_id = 0;
+		let account_id = 1;
+		let account_id_2 = 2;
+
+		#[allow(unused_must_use)]
+		{
+			Balances::force_set_balance(RuntimeOrigin::root(), account_id, 1_000_000_000);
+		}
+
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), name(b"helobudy")));
+		// Part of the deposit is released behind the pallet's back, e.g. by another pallet.
+		Balances::unreserve(&account_id, 1);
+
+		assert_noop!(
+			KittiesModule::transfer(RuntimeOrigin::signed(account_id), account_id_2, kitty_id),
+			Error::<Test>::DepositNotReserved
+		);
+		assert_eq!(KittiesModule::kitty_owner(kitty_id), Some(account_id));
+		assert_eq!(Balances::reserved_balance(account_id), KittyPrice::get() - 1);
+	});
+}
+
+#[test]
+fn burn_without_deposit_releases_nothing() {
+	new_test_ext().execute_with(|| {
+		let kitty_id = 0;
+		let account_id = 1;
+
+		#[allow(unused_must_use)]
+		{
+			Balances::force_set_balance(RuntimeOrigin::root(), account_id, 1_000_000_000);
+		}
+
+		assert_noop!(
+			KittiesModule::burn(RuntimeOrigin::signed(account_id), kitty_id),
+			Error::<Test>::InvalidKittyId
+		);
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), name(b"helobudy")));
+		assert!(KittiesModule::kitty_deposit(kitty_id).is_none());
+
+		assert_ok!(KittiesModule::burn(RuntimeOrigin::signed(account_id), kitty_id));
+		System::assert_has_event(
+			Event::KittyBurned { who: account_id, kitty_id, deposit: 0 }.into(),
+		);
+	});
+}
+
+#[test]
+fn migration_turns_payments_into_deposits() {
+	new_test_ext().execute_with(|| {
+		let account_id = 1;
+		let account_id_2 = 2;
+
+		#[allow(unused_must_use)]
+		{
+			Balances::force_set_balance(RuntimeOrigin::root(), account_id, 1_000_000_000);
+			Balances::force_set_balance(RuntimeOrigin::root(), account_id_2, 1_000_000_000);
+		}
+
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), name(b"aaaa0000")));
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), name(b"bbbb0000")));
+		assert_ok!(KittiesModule::transfer(RuntimeOrigin::signed(account_id), account_id_2, 1));
+
+		StorageVersion::new(4).put::<KittiesModule>();
+		KittyPriceMode::set(PriceMode::Deposit);
+		migrations::v5::migrate::<Test>();
+
+		assert_eq!(KittiesModule::on_chain_storage_version(), 5);
+		assert_eq!(KittiesModule::kitty_deposit(0), None);
+
+		run_to_block(2);
+		assert_eq!(DepositMigrationCursor::<Test>::get(), None);
+		assert_eq!(KittiesModule::kitty_deposit(0), Some(KittyPrice::get()));
+		assert_eq!(KittiesModule::kitty_deposit(1), Some(KittyPrice::get()));
+		assert_eq!(Balances::reserved_balance(account_id), KittyPrice::get());
+		assert_eq!(Balances::reserved_balance(account_id_2), KittyPrice::get());
+		assert_eq!(Balances::free_balance(KittyPalletId::get().into_account_truncating()), 0);
+	});
+}
+
+#[test]
+fn deposit_migration_is_spread_over_blocks() {
+	new_test_ext().execute_with(|| {
+		let account_id: u64 = 1;
+		let pallet_account: u64 = KittyPalletId::get().into_account_truncating();
+		let kitties = migrations::v5::KITTIES_PER_BLOCK + 10;
+
+		#[allow(unused_must_use)]
+		{
+			Balances::force_set_balance(
+				RuntimeOrigin::root(),
+				pallet_account,
+				LegacyKittyPrice::get() * kitties as u128 + 1,
+			);
+		}
+		for kitty_id in 0..kitties {
+			KittyOwner::<Test>::insert(kitty_id, account_id);
+		}
+		NextKittyId::<Test>::put(kitties);
+
+		StorageVersion::new(4).put::<KittiesModule>();
+		KittyPriceMode::set(PriceMode::Deposit);
+		migrations::v5::migrate::<Test>();
+		assert_eq!(DepositMigrationCursor::<Test>::get(), Some(0));
+
+		run_to_block(2);
+		assert_eq!(DepositMigrationCursor::<Test>::get(), Some(migrations::v5::KITTIES_PER_BLOCK));
+		assert_eq!(
+			Balances::reserved_balance(account_id),
+			LegacyKittyPrice::get() * migrations::v5::KITTIES_PER_BLOCK as u128
+		);
+		assert_eq!(KittiesModule::kitty_deposit(kitties - 1), None);
+
+		run_to_block(3);
+		assert_eq!(DepositMigrationCursor::<Test>::get(), None);
+		assert_eq!(
+			Balances::reserved_balance(account_id),
+			LegacyKittyPrice::get() * kitties as u128
+		);
+		assert_eq!(KittiesModule::kitty_deposit(kitties - 1), Some(LegacyKittyPrice::get()));
+	});
+}
+
+#[test]
+fn rename_checks_the_name() {
+	new_test_ext().execute_with(|| {
+		let kitty_id = 0;
+		let account_id = 1;
+
+		#[allow(unused_must_use)]
+		{
+			Balances::force_set_balance(RuntimeOrigin::root(), account_id, 1_000_000_000);
+		}
+
+		assert_noop!(
+			KittiesModule::create(RuntimeOrigin::signed(account_id), name(b"")),
+			Error::<Test>::InvalidName
+		);
+		assert_noop!(
+			KittiesModule::create(RuntimeOrigin::signed(account_id), name(&[0xff, 0xfe])),
+			Error::<Test>::InvalidName
+		);
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), name(b"tom")));
+		assert_eq!(KittiesModule::kitties(kitty_id).unwrap().name, name(b"tom"));
+
+		assert_noop!(
+			KittiesModule::rename(RuntimeOrigin::signed(2), kitty_id, name(b"tiger")),
+			Error::<Test>::NotOwner
+		);
+		assert_noop!(
+			KittiesModule::rename(RuntimeOrigin::signed(account_id), kitty_id, name(&[0xc3])),
+			Error::<Test>::InvalidName
+		);
+		assert_ok!(KittiesModule::rename(
+			RuntimeOrigin::signed(account_id),
+			kitty_id,
+			name("Kätzchen".as_bytes())
+		));
+		assert_eq!(KittiesModule::kitties(kitty_id).unwrap().name, name("Kätzchen".as_bytes()));
+		System::assert_has_event(
+			Event::KittyRenamed { who: account_id, kitty_id, name: name("Kätzchen".as_bytes()) }
+				.into(),
+		);
+	});
+}
+
+#[test]
+fn unique_names_are_indexed() {
+	new_test_ext().execute_with(|| {
+		UniqueKittyNames::set(true);
+		let account_id = 1;
+
+		#[allow(unused_must_use)]
+		{
+			Balances::force_set_balance(RuntimeOrigin::root(), account_id, 1_000_000_000);
+		}
+
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), name(b"tom")));
+		assert_eq!(KittiesModule::kitty_by_name(name(b"tom")), Some(0));
+		assert_noop!(
+			KittiesModule::create(RuntimeOrigin::signed(account_id), name(b"tom")),
+			Error::<Test>::NameTaken
+		);
+
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), name(b"jerry")));
+		assert_noop!(
+			KittiesModule::rename(RuntimeOrigin::signed(account_id), 1, name(b"tom")),
+			Error::<Test>::NameTaken
+		);
+		assert_ok!(KittiesModule::rename(RuntimeOrigin::signed(account_id), 0, name(b"thomas")));
+		assert_eq!(KittiesModule::kitty_by_name(name(b"tom")), None);
+		assert_eq!(KittiesModule::kitty_by_name(name(b"thomas")), Some(0));
+		assert_ok!(KittiesModule::rename(RuntimeOrigin::signed(account_id), 1, name(b"tom")));
+
+		assert_ok!(KittiesModule::burn(RuntimeOrigin::signed(account_id), 1));
+		assert_eq!(KittiesModule::kitty_by_name(name(b"tom")), None);
+	});
+}
+
+#[test]
+fn migration_recovers_names() {
+	new_test_ext().execute_with(|| {
+		UniqueKittyNames::set(true);
+		let old_kitty = |name: [u8; 8]| migrations::v3::V3Kitty {
+			dna: [0u8; 16],
+			name,
+			generation: 0,
+			breeding_cooldown_until: 0u64,
+		};
+		let put = |kitty_id: u32, name: [u8; 8]| {
+			frame_support::storage::unhashed::put(
+				&Kitties::<Test>::hashed_key_for(kitty_id),
+				&old_kitty(name),
+			)
+		};
+		put(0, *b"abcdabcd");
+		put(1, *b"tom\0\0\0\0\0");
+		put(2, *b"helobudy");
+		put(3, *b"tom\0\0\0\0\0");
+		put(4, [0u8; 8]);
+		put(5, *b"\xffbcdefgh");
+
+		StorageVersion::new(5).put::<KittiesModule>();
+		migrations::v6::migrate::<Test>();
+
+		assert_eq!(KittiesModule::on_chain_storage_version(), 6);
+		assert_eq!(KittiesModule::kitties(0).unwrap().name, name(b"abcd"));
+		assert_eq!(KittiesModule::kitties(1).unwrap().name, name(b"tom"));
+		assert_eq!(KittiesModule::kitties(2).unwrap().name, name(b"helobudy"));
+		assert_eq!(KittiesModule::kitties(3).unwrap().name, name(b"tom"));
+		assert_eq!(KittiesModule::kitties(4).unwrap().name, name(b"Kitty #4"));
+		assert_eq!(KittiesModule::kitties(5).unwrap().name, name(b"Kitty #5"));
+		assert_eq!(KittiesModule::kitty_by_name(name(b"helobudy")), Some(2));
+		assert_eq!(KittiesModule::kitty_by_name(name(b"Kitty #4")), Some(4));
+		assert!(matches!(KittiesModule::kitty_by_name(name(b"tom")), Some(1) | Some(3)));
+	});
+}
+
+#[test]
+fn migration_names_version_0_kitties() {
+	new_test_ext().execute_with(|| {
+		let key = Kitties::<Test>::hashed_key_for(0);
+		frame_support::storage::unhashed::put(&key, &migrations::v2::OldKitty([7; 16]));
+
+		StorageVersion::new(0).put::<KittiesModule>();
+		migrations::v2::migrate::<Test>();
+
+		assert_eq!(KittiesModule::on_chain_storage_version(), 2);
+		assert_eq!(
+			frame_support::storage::unhashed::get(&key),
+			Some(migrations::v2::V2Kitty { dna: [7; 16], name: *b"abcd0000" })
+		);
+
+		// A chain already past version 2 is left alone.
+		frame_support::storage::unhashed::put(&key, &migrations::v2::OldKitty([7; 16]));
+		StorageVersion::new(3).put::<KittiesModule>();
+		migrations::v2::migrate::<Test>();
+		assert_eq!(frame_support::storage::unhashed::get_raw(&key), Some(vec![7; 16]));
+	});
+}
+
+#[test]
+fn migration_indexes_owned_kitties() {
+	new_test_ext().execute_with(|| {
+		let account_id: u64 = 1;
+		let account_id_2: u64 = 2;
+		let escrow: u64 = KittyPalletId::get().into_account_truncating();
+
+		KittyOwner::<Test>::insert(0u32, account_id);
+		KittyOwner::<Test>::insert(1u32, account_id_2);
+		KittyOwner::<Test>::insert(2u32, account_id);
+		KittyOwner::<Test>::insert(3u32, escrow);
+		for kitty_id in 4u32..8 {
+			KittyOwner::<Test>::insert(kitty_id, account_id_2);
+		}
+		OwnedKitties::<Test>::insert(account_id, BoundedVec::truncate_from(vec![2]));
+
+		StorageVersion::new(6).put::<KittiesModule>();
+		migrations::v7::migrate::<Test>();
+
+		assert_eq!(KittiesModule::on_chain_storage_version(), 7);
+		let mut owned = KittiesModule::owned_kitties(account_id).to_vec();
+		owned.sort();
+		assert_eq!(owned, vec![0, 2]);
+		// Only `MaxKittiesOwned` of the five kitties of `account_id_2` fit in the index.
+		assert_eq!(KittiesModule::owned_kitties(account_id_2).len(), 3);
+		assert!(KittiesModule::owned_kitties(escrow).is_empty());
+	});
+}
+
+#[test]
+fn approved_account_can_transfer_once() {
+	new_test_ext().execute_with(|| {
+		let kitty_id = 0;
+		let owner = 1;
+		let operator = 2;
+		let recipient = 3;
+
+		#[allow(unused_must_use)]
+		{
+			Balances::force_set_balance(RuntimeOrigin::root(), owner, 1_000_000_000);
+		}
+
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(owner), name(b"helobudy")));
+
+		assert_noop!(
+			KittiesModule::transfer_from(
+				RuntimeOrigin::signed(operator),
+				owner,
+				recipient,
+				kitty_id
+			),
+			Error::<Test>::NotApproved
+		);
+		assert_noop!(
+			KittiesModule::approve(RuntimeOrigin::signed(operator), kitty_id, Some(operator)),
+			Error::<Test>::NotApproved
+		);
+		assert_noop!(
+			KittiesModule::approve(RuntimeOrigin::signed(owner), kitty_id, Some(owner)),
+			Error::<Test>::ApproveToOwner
+		);
+
+		assert_ok!(KittiesModule::approve(RuntimeOrigin::signed(owner), kitty_id, Some(operator)));
+		assert_eq!(KittiesModule::kitty_approval(kitty_id), Some(operator));
+		System::assert_has_event(
+			Event::Approval { owner, kitty_id, operator: Some(operator) }.into(),
+		);
+
+		assert_noop!(
+			KittiesModule::transfer_from(
+				RuntimeOrigin::signed(operator),
+				recipient,
+				owner,
+				kitty_id
+			),
+			Error::<Test>::NotOwner
+		);
+		assert_ok!(KittiesModule::transfer_from(
+			RuntimeOrigin::signed(operator),
+			owner,
+			recipient,
+			kitty_id
+		));
+		assert_eq!(KittiesModule::kitty_owner(kitty_id), Some(recipient));
+		assert_eq!(KittiesModule::kitty_approval(kitty_id), None);
+		System::assert_has_event(
+			Event::KittyTransferred { who: owner, recipient, kitty_id }.into(),
+		);
+
+		assert_noop!(
+			KittiesModule::transfer_from(
+				RuntimeOrigin::signed(operator),
+				recipient,
+				owner,
+				kitty_id
+			),
+			Error::<Test>::NotApproved
+		);
+	});
+}
+
+#[test]
+fn operator_can_transfer_every_kitty() {
+	new_test_ext().execute_with(|| {
+		let owner = 1;
+		let operator = 2;
+		let recipient = 3;
+
+		#[allow(unused_must_use)]
+		{
+			Balances::force_set_balance(RuntimeOrigin::root(), owner, 1_000_000_000);
+		}
+
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(owner), name(b"aaaa0000")));
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(owner), name(b"bbbb0000")));
+
+		assert_noop!(
+			KittiesModule::set_approval_for_all(RuntimeOrigin::signed(owner), owner, true),
+			Error::<Test>::ApproveToOwner
+		);
+		assert_ok!(KittiesModule::set_approval_for_all(
+			RuntimeOrigin::signed(owner),
+			operator,
+			true
+		));
+		assert!(KittiesModule::is_approved_for_all(&owner, &operator));
+		System::assert_has_event(Event::ApprovalForAll { owner, operator, approved: true }.into());
+
+		assert_ok!(KittiesModule::approve(RuntimeOrigin::signed(operator), 1, Some(recipient)));
+		assert_ok!(KittiesModule::transfer_from(
+			RuntimeOrigin::signed(operator),
+			owner,
+			recipient,
+			0
+		));
+		assert_eq!(KittiesModule::kitty_owner(0), Some(recipient));
+
+		assert_ok!(KittiesModule::set_approval_for_all(
+			RuntimeOrigin::signed(owner),
+			operator,
+			false
+		));
+		assert!(!KittiesModule::is_approved_for_all(&owner, &operator));
+		assert_noop!(
+			KittiesModule::transfer_from(RuntimeOrigin::signed(operator), owner, recipient, 1),
+			Error::<Test>::NotApproved
+		);
+		assert_ok!(KittiesModule::transfer_from(
+			RuntimeOrigin::signed(recipient),
+			owner,
+			recipient,
+			1
+		));
+	});
+}
+
+#[test]
+fn kitties_are_nonfungible_items() {
+	use crate::impl_nonfungibles::{DNA_KEY, GENERATION_KEY, NAME_KEY};
+	use codec::Encode;
+	use frame_support::traits::tokens::nonfungibles::{Inspect, Mutate, Transfer};
+
+	new_test_ext().execute_with(|| {
+		let kitty_id = 0;
+		let owner = 1;
+		let recipient = 2;
+
+		assert_noop!(
+			<KittiesModule as Mutate<u64>>::mint_into(&(), &1, &owner),
+			Error::<Test>::InvalidKittyId
+		);
+		assert_ok!(<KittiesModule as Mutate<u64>>::mint_into(&(), &kitty_id, &owner));
+		assert_eq!(<KittiesModule as Inspect<u64>>::owner(&(), &kitty_id), Some(owner));
+		assert_eq!(KittiesModule::owned_kitties(owner).to_vec(), vec![kitty_id]);
+
+		let kitty = KittiesModule::kitties(kitty_id).unwrap();
+		assert_eq!(kitty.name, name(b"Kitty #0"));
+		assert_eq!(
+			<KittiesModule as Inspect<u64>>::attribute(&(), &kitty_id, DNA_KEY),
+			Some(kitty.dna.to_vec())
+		);
+		assert_eq!(
+			<KittiesModule as Inspect<u64>>::attribute(&(), &kitty_id, NAME_KEY),
+			Some(b"Kitty #0".to_vec())
+		);
+		assert_eq!(
+			<KittiesModule as Inspect<u64>>::attribute(&(), &kitty_id, GENERATION_KEY),
+			Some(0u32.encode())
+		);
+		assert_eq!(<KittiesModule as Inspect<u64>>::attribute(&(), &kitty_id, b"colour"), None);
+
+		assert!(<KittiesModule as Inspect<u64>>::can_transfer(&(), &kitty_id));
+		assert_ok!(<KittiesModule as Transfer<u64>>::transfer(&(), &kitty_id, &recipient));
+		assert_eq!(KittiesModule::kitty_owner(kitty_id), Some(recipient));
+
+		assert_noop!(
+			<KittiesModule as Mutate<u64>>::burn(&(), &kitty_id, Some(&owner)),
+			Error::<Test>::NotOwner
+		);
+		assert_ok!(<KittiesModule as Mutate<u64>>::burn(&(), &kitty_id, None));
+		assert_eq!(<KittiesModule as Inspect<u64>>::owner(&(), &kitty_id), None);
+		assert!(!<KittiesModule as Inspect<u64>>::can_transfer(&(), &kitty_id));
+	});
+}
+
+#[test]
+fn minted_kitty_keeps_a_taken_name_unindexed() {
+	use frame_support::traits::tokens::nonfungibles::Mutate;
+
+	new_test_ext().execute_with(|| {
+		UniqueKittyNames::set(true);
+		let squatter = 1;
+		let owner = 2;
+
+		#[allow(unused_must_use)]
+		{
+			Balances::force_set_balance(RuntimeOrigin::root(), squatter, 1_000_000_000);
+		}
+
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(squatter), name(b"Kitty #1")));
+		assert_ok!(<KittiesModule as Mutate<u64>>::mint_into(&(), &1, &owner));
+
+		assert_eq!(KittiesModule::kitty_owner(1), Some(owner));
+		assert_eq!(KittiesModule::kitties(1).unwrap().name, name(b"Kitty #1"));
+		assert_eq!(KittiesModule::kitty_by_name(name(b"Kitty #1")), Some(0));
+	});
+}
+
+#[test]
+fn burning_clears_lineage() {
+	new_test_ext().execute_with(|| {
+		let account_id = 1;
+
+		#[allow(unused_must_use)]
+		{
+			Balances::force_set_balance(RuntimeOrigin::root(), account_id, 1_000_000_000);
+		}
+
+		for _ in 0..3 {
+			assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), name(b"aaaa0000")));
+		}
+		assert_ok!(KittiesModule::breed(
+			RuntimeOrigin::signed(account_id),
+			0,
+			1,
+			name(b"bbbb0000")
+		));
+		System::set_block_number(12);
+		assert_ok!(KittiesModule::breed(
+			RuntimeOrigin::signed(account_id),
+			3,
+			2,
+			name(b"cccc0000")
+		));
+
+		assert_ok!(KittiesModule::burn(RuntimeOrigin::signed(account_id), 3));
+
+		assert_eq!(KittiesModule::kitty_parents(3), None);
+		assert!(!KittyChildren::<Test>::contains_key(0, 3));
+		assert!(!KittyChildren::<Test>::contains_key(1, 3));
+		assert!(!KittyChildren::<Test>::contains_key(3, 4));
+		assert_eq!(KittiesModule::kitty_parents(4), Some((3, 2)));
+	});
+}
+
+#[test]
+fn gift_can_be_claimed_with_secret() {
+	use sp_runtime::traits::{BlakeTwo256, Hash};
+
+	new_test_ext().execute_with(|| {
+		let kitty_id = 0;
+		let sender = 1;
+		let recipient = 2;
+		let secret = [7u8; 32];
+		let secret_hash = BlakeTwo256::hash_of(&(secret, recipient));
+		let escrow = KittyPalletId::get().into_account_truncating();
+
+		#[allow(unused_must_use)]
+		{
+			Balances::force_set_balance(RuntimeOrigin::root(), sender, 1_000_000_000);
+			Balances::force_set_balance(RuntimeOrigin::root(), recipient, 1_000_000_000);
+		}
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(sender), name(b"gift")));
+
+		assert_noop!(
+			KittiesModule::create_gift(RuntimeOrigin::signed(recipient), kitty_id, secret_hash, 10),
+			Error::<Test>::NotOwner
+		);
+		assert_noop!(
+			KittiesModule::create_gift(RuntimeOrigin::signed(sender), kitty_id, secret_hash, 1),
+			Error::<Test>::InvalidGiftExpiry
+		);
+		assert_ok!(KittiesModule::sale(RuntimeOrigin::signed(sender), kitty_id, 100));
+		assert_ok!(KittiesModule::create_gift(
+			RuntimeOrigin::signed(sender),
+			kitty_id,
+			secret_hash,
+			10
+		));
+		System::assert_last_event(Event::GiftCreated { who: sender, kitty_id, expiry: 10 }.into());
+		assert_eq!(KittiesModule::kitty_owner(kitty_id), Some(escrow));
+		assert_eq!(KittiesModule::kitty_on_sale(kitty_id), None);
+		assert!(KittiesModule::owned_kitties(sender).is_empty());
+
+		assert_noop!(
+			KittiesModule::transfer(RuntimeOrigin::signed(sender), recipient, kitty_id),
+			Error::<Test>::NotOwner
+		);
+		assert_noop!(
+			KittiesModule::reclaim_gift(RuntimeOrigin::signed(sender), kitty_id, None),
+			Error::<Test>::GiftNotExpired
+		);
+		assert_noop!(
+			KittiesModule::claim_gift(RuntimeOrigin::signed(recipient), [8u8; 32]),
+			Error::<Test>::GiftNotFound
+		);
+		// Anyone else who learns the secret, e.g. from the transaction pool, cannot claim it.
+		assert_noop!(
+			KittiesModule::claim_gift(RuntimeOrigin::signed(3), secret),
+			Error::<Test>::GiftNotFound
+		);
+
+		assert_ok!(KittiesModule::claim_gift(RuntimeOrigin::signed(recipient), secret));
+		System::assert_last_event(Event::GiftClaimed { who: recipient, kitty_id }.into());
+		assert_eq!(KittiesModule::kitty_owner(kitty_id), Some(recipient));
+		assert_eq!(KittiesModule::owned_kitties(recipient).to_vec(), vec![kitty_id]);
+		assert_eq!(KittiesModule::gifts(kitty_id), None);
+
+		assert_noop!(
+			KittiesModule::claim_gift(RuntimeOrigin::signed(recipient), secret),
+			Error::<Test>::GiftNotFound
+		);
+	});
+}
+
+#[test]
+fn expired_gift_returns_to_sender() {
+	use frame_support::traits::tokens::nonfungibles::Inspect;
+	use sp_runtime::traits::{BlakeTwo256, Hash};
+
+	new_test_ext().execute_with(|| {
+		let kitty_id = 0;
+		let sender = 1;
+		let recipient = 2;
+		let secret = [7u8; 32];
+		let secret_hash = BlakeTwo256::hash_of(&(secret, recipient));
+
+		#[allow(unused_must_use)]
+		{
+			Balances::force_set_balance(RuntimeOrigin::root(), sender, 1_000_000_000);
+		}
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(sender), name(b"gift")));
+		assert_ok!(KittiesModule::create_gift(
+			RuntimeOrigin::signed(sender),
+			kitty_id,
+			secret_hash,
+			5
+		));
+		assert!(!<KittiesModule as Inspect<u64>>::can_transfer(&(), &kitty_id));
+
+		run_to_block(5);
+
+		assert_noop!(
+			KittiesModule::claim_gift(RuntimeOrigin::signed(recipient), secret),
+			Error::<Test>::GiftExpired
+		);
+		assert_noop!(
+			KittiesModule::reclaim_gift(RuntimeOrigin::signed(recipient), kitty_id, None),
+			Error::<Test>::NotOwner
+		);
+		assert_ok!(KittiesModule::reclaim_gift(RuntimeOrigin::signed(sender), kitty_id, None));
+		System::assert_last_event(
+			Event::GiftReclaimed { who: sender, kitty_id, recipient: sender }.into(),
+		);
+		assert_eq!(KittiesModule::kitty_owner(kitty_id), Some(sender));
+		assert_eq!(KittiesModule::owned_kitties(sender).to_vec(), vec![kitty_id]);
+		assert!(<KittiesModule as Inspect<u64>>::can_transfer(&(), &kitty_id));
+
+		// The claim code can be reused once the gift is gone.
+		assert_ok!(KittiesModule::create_gift(
+			RuntimeOrigin::signed(sender),
+			kitty_id,
+			secret_hash,
+			10
+		));
+	});
+}
+
+#[test]
+fn full_sender_reclaims_gift_to_another_account() {
+	use sp_runtime::traits::{BlakeTwo256, Hash};
+
+	new_test_ext().execute_with(|| {
+		let kitty_id = 0;
+		let sender = 1;
+		let recipient = 2;
+
+		#[allow(unused_must_use)]
+		{
+			Balances::force_set_balance(RuntimeOrigin::root(), sender, 1_000_000_000);
+		}
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(sender), name(b"gift")));
+		assert_ok!(KittiesModule::create_gift(
+			RuntimeOrigin::signed(sender),
+			kitty_id,
+			BlakeTwo256::hash_of(&([7u8; 32], recipient)),
+			5
+		));
+		for _ in 0..3 {
+			assert_ok!(KittiesModule::create(RuntimeOrigin::signed(sender), name(b"kept")));
+		}
+
+		run_to_block(5);
+
+		assert_noop!(
+			KittiesModule::reclaim_gift(RuntimeOrigin::signed(sender), kitty_id, None),
+			Error::<Test>::TooManyKitties
+		);
+		assert_ok!(KittiesModule::reclaim_gift(
+			RuntimeOrigin::signed(sender),
+			kitty_id,
+			Some(recipient)
+		));
+		System::assert_last_event(Event::GiftReclaimed { who: sender, kitty_id, recipient }.into());
+		assert_eq!(KittiesModule::kitty_owner(kitty_id), Some(recipient));
+		assert_eq!(KittiesModule::owned_kitties(recipient).to_vec(), vec![kitty_id]);
+		assert_eq!(KittiesModule::gifts(kitty_id), None);
+	});
+}
+
+#[test]
+fn stud_fee_goes_to_the_stud_owner() {
+	new_test_ext().execute_with(|| {
+		let stud_id = 0;
+		let kitty_id = 1;
+		let owner = 1;
+		let breeder = 2;
+
+		#[allow(unused_must_use)]
+		{
+			Balances::force_set_balance(RuntimeOrigin::root(), owner, 1_000_000_000);
+			Balances::force_set_balance(RuntimeOrigin::root(), breeder, 1_000_000_000);
+		}
+
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(owner), name(b"stud")));
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(breeder), name(b"queen")));
+
+		assert_noop!(
+			KittiesModule::breed(RuntimeOrigin::signed(breeder), kitty_id, stud_id, name(b"kit")),
+			Error::<Test>::NotOwner
+		);
+		assert_noop!(
+			KittiesModule::breed_with_stud(
+				RuntimeOrigin::signed(breeder),
+				kitty_id,
+				stud_id,
+				1_000,
+				name(b"kit")
+			),
+			Error::<Test>::NotAStud
+		);
+		assert_noop!(
+			KittiesModule::offer_stud(RuntimeOrigin::signed(breeder), stud_id, 1_000),
+			Error::<Test>::NotOwner
+		);
+
+		assert_ok!(KittiesModule::offer_stud(RuntimeOrigin::signed(owner), stud_id, 1_000));
+		System::assert_last_event(
+			Event::StudOffered { who: owner, kitty_id: stud_id, fee: 1_000 }.into(),
+		);
+		assert_eq!(KittiesModule::stud_fee(stud_id), Some(1_000));
+
+		assert_noop!(
+			KittiesModule::breed_with_stud(
+				RuntimeOrigin::signed(breeder),
+				kitty_id,
+				stud_id,
+				500,
+				name(b"kit")
+			),
+			Error::<Test>::PriceTooHigh
+		);
+		assert_noop!(
+			KittiesModule::breed_with_stud(
+				RuntimeOrigin::signed(owner),
+				stud_id,
+				stud_id,
+				1_000,
+				name(b"kit")
+			),
+			Error::<Test>::AlreadyOwned
+		);
+
+		let owner_balance = Balances::free_balance(owner);
+		let breeder_balance = Balances::free_balance(breeder);
+		assert_ok!(KittiesModule::breed_with_stud(
+			RuntimeOrigin::signed(breeder),
+			kitty_id,
+			stud_id,
+			1_000,
+			name(b"kit")
+		));
+		assert_eq!(Balances::free_balance(owner), owner_balance + 1_000);
+		assert_eq!(Balances::free_balance(breeder), breeder_balance - 1_000 - KittyPrice::get());
+		assert_eq!(KittiesModule::kitty_owner(2), Some(breeder));
+		assert_eq!(KittiesModule::kitty_parents(2), Some((kitty_id, stud_id)));
+
+		assert_ok!(KittiesModule::transfer(RuntimeOrigin::signed(owner), 3, stud_id));
+		assert_eq!(KittiesModule::stud_fee(stud_id), None);
+		assert_noop!(
+			KittiesModule::withdraw_stud(RuntimeOrigin::signed(3), stud_id),
+			Error::<Test>::NotAStud
+		);
+	});
+}
+
+#[test]
+fn kitty_price_follows_a_fresh_latest_btc_price() {
+	new_test_ext().execute_with(|| {
+		let account_id = 1;
+		let pallet_account = KittyPalletId::get().into_account_truncating();
+
+		#[allow(unused_must_use)]
+		{
+			Balances::force_set_balance(RuntimeOrigin::root(), account_id, 1_000_000_000);
+		}
+
+		assert_eq!(KittiesModule::kitty_price(), KittyPrice::get());
+
+		// 10 USD at 2.5 USD a BTC is 4 BTC, or 8 tokens of 1_000 units at 2 tokens a BTC.
+		LatestBtcPrice::<Test>::put((FixedU128::from_rational(5, 2), 1));
+		assert_eq!(KittiesModule::kitty_price(), 8_000);
+
+		assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), name(b"aaaa0000")));
+		assert_eq!(Balances::free_balance(pallet_account), 8_000);
+
+		run_to_block(11);
+		assert_eq!(KittiesModule::kitty_price(), 8_000);
+
+		run_to_block(12);
+		assert_eq!(KittiesModule::kitty_price(), KittyPrice::get());
+	});
+}
+
+#[test]
+fn kitty_price_prefers_the_btc_price_feed() {
+	new_test_ext().execute_with(|| {
+		LatestBtcPrice::<Test>::put((FixedU128::from_rational(5, 2), 1));
+		OracleBtcPrice::set(Some((FixedU128::from_u32(5), 1)));
+
+		// 10 USD at 5 USD a BTC is 2 BTC, or 4 tokens of 1_000 units at 2 tokens a BTC.
+		assert_eq!(KittiesModule::kitty_price(), 4_000);
+
+		OracleBtcPrice::set(None);
+		assert_eq!(KittiesModule::kitty_price(), 8_000);
+
+		OracleBtcPrice::set(Some((FixedU128::from_u32(5), 1)));
+		LatestBtcPrice::<Test>::put((FixedU128::from_rational(5, 2), 5));
+		run_to_block(12);
+		assert_eq!(KittiesModule::kitty_price(), 8_000);
+
+		run_to_block(16);
+		assert_eq!(KittiesModule::kitty_price(), KittyPrice::get());
+	});
+}
+
+#[test]
+fn oracle_authorities_are_managed_by_root() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			KittiesModule::add_oracle_authority(RuntimeOrigin::signed(1), 1),
+			DispatchError::BadOrigin
+		);
+
+		assert_ok!(KittiesModule::add_oracle_authority(RuntimeOrigin::root(), 1));
+		System::assert_last_event(Event::OracleAuthorityAdded { who: 1 }.into());
+		assert_noop!(
+			KittiesModule::add_oracle_authority(RuntimeOrigin::root(), 1),
+			Error::<Test>::AlreadyOracleAuthority
+		);
+		assert_ok!(KittiesModule::add_oracle_authority(RuntimeOrigin::root(), 2));
+		assert_noop!(
+			KittiesModule::add_oracle_authority(RuntimeOrigin::root(), 3),
+			Error::<Test>::TooManyOracleAuthorities
+		);
+
+		assert_ok!(KittiesModule::remove_oracle_authority(RuntimeOrigin::root(), 1));
+		System::assert_last_event(Event::OracleAuthorityRemoved { who: 1 }.into());
+		assert_eq!(KittiesModule::oracle_authorities().to_vec(), vec![2]);
+		assert_noop!(
+			KittiesModule::remove_oracle_authority(RuntimeOrigin::root(), 1),
+			Error::<Test>::NotOracleAuthority
+		);
+	});
+}
+
+#[test]
+fn prices_are_only_accepted_in_the_configured_submission_mode() {
+	new_test_ext().execute_with(|| {
+		let price = FixedU128::from_rational(5, 2);
+		assert_ok!(KittiesModule::add_oracle_authority(RuntimeOrigin::root(), 1));
+
+		assert_noop!(
+			KittiesModule::submit_price(RuntimeOrigin::signed(1), b"2.5".to_vec()),
+			Error::<Test>::WrongSubmissionMode
+		);
+
+		OcwSubmissionMode::set(SubmissionMode::Signed);
+		assert_noop!(
+			KittiesModule::submit_price(RuntimeOrigin::signed(2), b"2.5".to_vec()),
+			Error::<Test>::NotOracleAuthority
+		);
+		assert_noop!(
+			KittiesModule::submit_price(RuntimeOrigin::signed(1), b"0".to_vec()),
+			Error::<Test>::InvalidPrice
+		);
+		// Outside of `MinBtcPrice` and `MaxBtcPrice`.
+		assert_noop!(
+			KittiesModule::submit_price(RuntimeOrigin::signed(1), b"0.5".to_vec()),
+			Error::<Test>::InvalidPrice
+		);
+		assert_noop!(
+			KittiesModule::submit_price(RuntimeOrigin::signed(1), b"1000000.1".to_vec()),
+			Error::<Test>::InvalidPrice
+		);
+		assert_ok!(KittiesModule::submit_price(RuntimeOrigin::signed(1), b"2.5".to_vec()));
+		System::assert_last_event(Event::PriceReported { price }.into());
+		assert_eq!(KittiesModule::latest_btc_price(), Some((price, 1)));
+		assert_noop!(
+			KittiesModule::submit_price(RuntimeOrigin::signed(1), b"2.5".to_vec()),
+			Error::<Test>::OracleRateLimited
+		);
+
+		// Blocks 5 to 9 are the next round.
+		run_to_block(5);
+		assert_ok!(KittiesModule::submit_price(RuntimeOrigin::signed(1), b"2.5".to_vec()));
+
+		let submit_unsigned = |block_number| {
+			KittiesModule::submit_price_unsigned(RuntimeOrigin::none(), b"3".to_vec(), block_number)
+		};
+		let unsigned_call = |block_number| crate::Call::submit_price_unsigned {
+			price: b"3".to_vec(),
+			block_number,
+		};
+		assert_noop!(submit_unsigned(5), Error::<Test>::WrongSubmissionMode);
+		assert_eq!(
+			KittiesModule::validate_unsigned(TransactionSource::External, &unsigned_call(5)),
+			Err(InvalidTransaction::Call.into())
+		);
+
+		OcwSubmissionMode::set(SubmissionMode::Unsigned);
+		// Nothing authenticates these, so they are only taken from the local off-chain worker
+		// and not gossiped.
+		assert_eq!(
+			KittiesModule::validate_unsigned(TransactionSource::External, &unsigned_call(4)),
+			Err(InvalidTransaction::BadSigner.into())
+		);
+		let valid = KittiesModule::validate_unsigned(TransactionSource::Local, &unsigned_call(4));
+		assert!(!valid.unwrap().propagate);
+		assert_ok!(KittiesModule::validate_unsigned(TransactionSource::InBlock, &unsigned_call(4)));
+		assert_eq!(
+			KittiesModule::validate_unsigned(TransactionSource::Local, &unsigned_call(6)),
+			Err(InvalidTransaction::Future.into())
+		);
+		assert_ok!(submit_unsigned(5));
+		assert_eq!(KittiesModule::latest_btc_price(), Some((FixedU128::from_u32(3), 5)));
+		assert_noop!(submit_unsigned(5), Error::<Test>::OracleRateLimited);
+		assert_eq!(
+			KittiesModule::validate_unsigned(TransactionSource::Local, &unsigned_call(5)),
+			Err(InvalidTransaction::Stale.into())
+		);
+	});
+}
+
+#[test]
+fn signed_payloads_need_an_oracle_authority() {
+	new_test_ext().execute_with(|| {
+		run_to_block(2);
+		let signed_payload = |who: u64, block_number: u64, signer: u64| {
+			let payload =
+				Payload { price: b"2.5".to_vec(), block_number, public: UintAuthorityId(who) };
+			let signature = TestSignature(signer, payload.encode());
+			(payload, signature)
+		};
+		let validate = |who, block_number, signer| {
+			let (payload, signature) = signed_payload(who, block_number, signer);
+			KittiesModule::validate_unsigned(
+				TransactionSource::External,
+				&crate::Call::unsigned_extrinsic_with_signed_payload { payload, signature },
+			)
+		};
+
+		assert_eq!(validate(1, 2, 1), Err(InvalidTransaction::BadSigner.into()));
+		assert_ok!(KittiesModule::add_oracle_authority(RuntimeOrigin::root(), 1));
+		assert_ok!(KittiesModule::add_oracle_authority(RuntimeOrigin::root(), 2));
+		assert_eq!(validate(1, 2, 2), Err(InvalidTransaction::BadProof.into()));
+		assert_eq!(validate(1, 3, 1), Err(InvalidTransaction::Future.into()));
+
+		// One transaction per authority and round.
+		let provides = |who, block_number| validate(who, block_number, who).unwrap().provides;
+		assert_eq!(provides(1, 2), provides(1, 1));
+		assert_ne!(provides(1, 2), provides(2, 2));
+
+		let (payload, signature) = signed_payload(1, 2, 1);
+		assert_ok!(KittiesModule::unsigned_extrinsic_with_signed_payload(
+			RuntimeOrigin::none(),
+			payload,
+			signature
+		));
+		assert_eq!(KittiesModule::latest_btc_price(), Some((FixedU128::from_rational(5, 2), 2)));
+		assert_eq!(validate(1, 2, 1), Err(InvalidTransaction::Stale.into()));
+
+		run_to_block(8);
+		assert_eq!(validate(2, 2, 2), Err(InvalidTransaction::Stale.into()));
+		assert_ok!(validate(1, 8, 1));
+	});
+}
+
+const BTC_PRICE: &[u8] = b"26891.41503711";
+
+/// Test externalities in which the off-chain worker can run `runs` times, getting `BTC_PRICE`
+/// for each price request and sending its transactions to `pool`.
+fn offchain_test_ext(
+	pool: impl TransactionPool + Send + 'static,
+	runs: usize,
+) -> sp_io::TestExternalities {
+	let (offchain, state) = testing::TestOffchainExt::new();
+	for _ in 0..runs {
+		state.write().expect_request(testing::PendingRequest {
+			method: "GET".into(),
+			uri: "https://data.binance.com/api/v3/avgPrice?symbol=BTCUSDT".into(),
+			response: Some(br#"{"mins":5,"price":"26891.41503711"}"#.to_vec()),
+			sent: true,
+			..Default::default()
+		});
+	}
+
+	let mut ext = new_test_ext();
+	ext.register_extension(OffchainDbExt::new(offchain.clone()));
+	ext.register_extension(OffchainWorkerExt::new(offchain));
+	ext.register_extension(TransactionPoolExt::new(pool));
+	ext
+}
+
+/// Run the off-chain worker for `block_number` and throw away its changes to the chain state,
+/// as a node does.
+fn run_offchain_worker(block_number: u64) {
+	sp_io::storage::start_transaction();
+	KittiesModule::offchain_worker(block_number);
+	sp_io::storage::rollback_transaction();
+}
+
+/// A transaction pool that rejects every transaction, counting them.
+#[derive(Default)]
+struct RejectingPool(Arc<AtomicU32>);
+
+impl TransactionPool for RejectingPool {
+	fn submit_transaction(&mut self, _extrinsic: Vec<u8>) -> Result<(), ()> {
+		self.0.fetch_add(1, Ordering::SeqCst);
+		Err(())
+	}
+}
+
+#[test]
+fn signed_prices_wait_until_the_last_one_is_included() {
+	let (pool, pool_state) = testing::TestTransactionPoolExt::new();
+	UintAuthorityId::set_all_keys(vec![1u64]);
+	OcwSubmissionMode::set(SubmissionMode::Signed);
+
+	offchain_test_ext(pool, 3).execute_with(|| {
+		assert_ok!(KittiesModule::add_oracle_authority(RuntimeOrigin::root(), 1));
+		let sent = || {
+			let tx = pool_state.write().transactions.pop()?;
+			let tx = Extrinsic::decode(&mut &*tx).unwrap();
+			assert_eq!(
+				tx.call,
+				RuntimeCall::KittiesModule(crate::Call::submit_price { price: BTC_PRICE.to_vec() })
+			);
+			tx.signature.map(|(nonce, ())| nonce)
+		};
+
+		run_offchain_worker(1);
+		assert_eq!(sent(), Some(0));
+
+		// The account nonce has not moved, so the transaction may still be in the pool.
+		run_offchain_worker(2);
+		assert_eq!(sent(), None);
+
+		// Once it is included, the account reports again in the next round.
+		assert_ok!(KittiesModule::submit_price(RuntimeOrigin::signed(1), BTC_PRICE.to_vec()));
+		System::inc_account_nonce(1u64);
+		run_offchain_worker(5);
+		assert_eq!(sent(), Some(1));
+		assert!(pool_state.read().transactions.is_empty());
+	});
+}
+
+#[test]
+fn rejected_signers_back_off_longer_each_time() {
+	let pool = RejectingPool::default();
+	let attempts = pool.0.clone();
+	UintAuthorityId::set_all_keys(vec![1u64]);
+	OcwSubmissionMode::set(SubmissionMode::Signed);
+
+	offchain_test_ext(pool, 7).execute_with(|| {
+		assert_ok!(KittiesModule::add_oracle_authority(RuntimeOrigin::root(), 1));
+		let attempts_at = |block_number| {
+			run_offchain_worker(block_number);
+			attempts.load(Ordering::SeqCst)
+		};
+
+		// Waits of one round of 5 blocks, then 10 blocks, then 20.
+		assert_eq!(attempts_at(1), 1);
+		assert_eq!(attempts_at(5), 1);
+		assert_eq!(attempts_at(6), 2);
+		assert_eq!(attempts_at(15), 2);
+		assert_eq!(attempts_at(16), 3);
+		assert_eq!(attempts_at(35), 3);
+		assert_eq!(attempts_at(36), 4);
+	});
+}
@@ -0,0 +1,24 @@
+//! Runtime API definition for the oracle pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_std::vec::Vec;
+
+pub use pallet_oracle::{FeedId, PriceRecord};
+
+sp_api::decl_runtime_apis! {
+	pub trait PriceApi<BlockNumber>
+	where
+		BlockNumber: Codec,
+	{
+		/// The latest aggregated price of `feed_id`.
+		fn price(feed_id: FeedId) -> Option<PriceRecord<BlockNumber>>;
+
+		/// The latest aggregated price of the feed for `symbol`, such as `BTCUSDT`.
+		fn price_of(symbol: Vec<u8>) -> Option<PriceRecord<BlockNumber>>;
+
+		/// Every feed with its symbol.
+		fn feeds() -> Vec<(FeedId, Vec<u8>)>;
+	}
+}
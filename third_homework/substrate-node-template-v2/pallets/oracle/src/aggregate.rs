@@ -0,0 +1,40 @@
+//! Combining the prices submitted for a round into one.
+
+use sp_runtime::{FixedPointNumber, FixedU128, Permill};
+use sp_std::vec::Vec;
+
+/// The median of `prices` after dropping those more than `max_deviation` away from the median of
+/// all of them.
+///
+/// Returns `None` if fewer than `min_count` prices are left, or none at all.
+pub fn median_without_outliers(
+	mut prices: Vec<FixedU128>,
+	max_deviation: Permill,
+	min_count: u32,
+) -> Option<FixedU128> {
+	prices.sort();
+	let median = median(&prices)?;
+	let bound = max_deviation * median.into_inner();
+	prices.retain(|price| {
+		let distance = if *price > median { *price - median } else { median - *price };
+		distance.into_inner() <= bound
+	});
+
+	if (prices.len() as u32) < min_count.max(1) {
+		return None
+	}
+	median(&prices)
+}
+
+/// The median of sorted `prices`, halfway between the middle two if there is an even number.
+fn median(prices: &[FixedU128]) -> Option<FixedU128> {
+	let mid = prices.len() / 2;
+	match prices.len() {
+		0 => None,
+		len if len % 2 == 1 => Some(prices[mid]),
+		_ => {
+			let (low, high) = (prices[mid - 1].into_inner(), prices[mid].into_inner());
+			Some(FixedU128::from_inner(low + (high - low) / 2))
+		},
+	}
+}
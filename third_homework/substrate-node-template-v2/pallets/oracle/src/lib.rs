@@ -0,0 +1,597 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Prices aggregated from several off-chain workers.
+//!
+//! A governance origin keeps a list of feeds, each an HTTP endpoint with the JSON path of the
+//! price in its response. Every round, the off-chain worker of each oracle authority fetches every
+//! feed and submits what it saw. When the round ends, the submissions for each feed are combined
+//! into one price by their median, ignoring those too far from it.
+pub use pallet::*;
+
+pub mod aggregate;
+pub mod source;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+use codec::{Decode, Encode};
+use frame_system::offchain::{
+	AppCrypto, CreateSignedTransaction, SendUnsignedTransaction, SignedPayload, Signer,
+	SigningTypes,
+};
+use sp_runtime::{
+	transaction_validity::{
+		InvalidTransaction, TransactionPriority, TransactionValidity, ValidTransaction,
+	},
+	RuntimeDebug,
+};
+
+use sp_core::crypto::KeyTypeId;
+
+pub const KEY_TYPE: KeyTypeId = KeyTypeId(*b"orcl");
+pub mod crypto {
+	use super::KEY_TYPE;
+	use sp_core::sr25519::Signature as Sr25519Signature;
+	use sp_runtime::{
+		app_crypto::{app_crypto, sr25519},
+		traits::Verify,
+		MultiSignature, MultiSigner,
+	};
+	app_crypto!(sr25519, KEY_TYPE);
+
+	pub struct OracleAuthId;
+
+	impl frame_system::offchain::AppCrypto<MultiSigner, MultiSignature> for OracleAuthId {
+		type RuntimeAppPublic = Public;
+		type GenericSignature = sp_core::sr25519::Signature;
+		type GenericPublic = sp_core::sr25519::Public;
+	}
+
+	// implemented for mock runtime in test
+	impl frame_system::offchain::AppCrypto<<Sr25519Signature as Verify>::Signer, Sr25519Signature>
+		for OracleAuthId
+	{
+		type RuntimeAppPublic = Public;
+		type GenericSignature = sp_core::sr25519::Signature;
+		type GenericPublic = sp_core::sr25519::Public;
+	}
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::offchain::storage::StorageValueRef;
+	use sp_runtime::offchain::{http, Duration};
+	use sp_runtime::traits::{IdentifyAccount, One, Saturating, Zero};
+	use sp_runtime::{FixedU128, Permill, SaturatedConversion};
+	use sp_std::vec::Vec;
+
+	pub type FeedId = u32;
+	/// Rounds are numbered from 0 and are `RoundLength` blocks long.
+	pub type RoundId = u32;
+
+	pub type SymbolOf<T> = BoundedVec<u8, <T as Config>::MaxSymbolLength>;
+	pub type UrlOf<T> = BoundedVec<u8, <T as Config>::MaxUrlLength>;
+	pub type PathOf<T> = BoundedVec<u8, <T as Config>::MaxPathLength>;
+
+	/// Where to fetch a price from.
+	#[derive(
+		Encode,
+		Decode,
+		CloneNoBound,
+		RuntimeDebugNoBound,
+		PartialEqNoBound,
+		EqNoBound,
+		TypeInfo,
+		MaxEncodedLen,
+	)]
+	#[scale_info(skip_type_params(T))]
+	#[codec(mel_bound())]
+	pub struct Feed<T: Config> {
+		/// Unique among feeds, such as `BTCUSDT`.
+		pub symbol: SymbolOf<T>,
+		pub url: UrlOf<T>,
+		/// The location of the price in the JSON response, see [`source::extract_price`].
+		pub path: PathOf<T>,
+		/// The price in the response is in units of `10^-decimals`.
+		pub decimals: u8,
+	}
+
+	/// The result of a round.
+	#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+	pub struct PriceRecord<BlockNumber> {
+		pub price: FixedU128,
+		pub round: RoundId,
+		/// The block the round was aggregated in.
+		pub updated_at: BlockNumber,
+	}
+
+	/// A price seen by an oracle authority, signed with its `AuthorityId` key.
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+	pub struct PricePayload<Public> {
+		pub feed_id: FeedId,
+		pub round: RoundId,
+		pub price: FixedU128,
+		pub public: Public,
+	}
+
+	impl<T: SigningTypes> SignedPayload<T> for PricePayload<T::Public> {
+		fn public(&self) -> T::Public {
+			self.public.clone()
+		}
+	}
+
+	/// Off-chain storage prefix for the last round each feed was submitted for by this node.
+	const LAST_ROUND_KEY: &[u8] = b"ocw-oracle::last-round/";
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// Configure the pallet by specifying the parameters and types on which it depends.
+	#[pallet::config]
+	pub trait Config: frame_system::Config + CreateSignedTransaction<Call<Self>> {
+		/// Because this pallet emits events, it depends on the runtime's definition of an event.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		/// The identifier type for an offchain worker.
+		type AuthorityId: AppCrypto<Self::Public, Self::Signature>;
+		/// The origin that manages feeds and oracle authorities.
+		type ManagerOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+		#[pallet::constant]
+		type RoundLength: Get<Self::BlockNumber>;
+		#[pallet::constant]
+		type MaxFeeds: Get<u32>;
+		#[pallet::constant]
+		type MaxAuthorities: Get<u32>;
+		#[pallet::constant]
+		type MaxSymbolLength: Get<u32>;
+		#[pallet::constant]
+		type MaxUrlLength: Get<u32>;
+		#[pallet::constant]
+		type MaxPathLength: Get<u32>;
+		/// Submissions further than this from the median of a round are ignored.
+		#[pallet::constant]
+		type MaxDeviation: Get<Permill>;
+		/// The fewest submissions left after dropping outliers for a round to produce a price.
+		#[pallet::constant]
+		type MinSubmissions: Get<u32>;
+		#[pallet::constant]
+		type UnsignedPriority: Get<TransactionPriority>;
+	}
+
+	#[pallet::storage]
+	#[pallet::getter(fn next_feed_id)]
+	pub type NextFeedId<T> = StorageValue<_, FeedId, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn feeds)]
+	pub type Feeds<T: Config> = CountedStorageMap<_, Twox64Concat, FeedId, Feed<T>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn feed_by_symbol)]
+	pub type FeedBySymbol<T: Config> = StorageMap<_, Blake2_128Concat, SymbolOf<T>, FeedId>;
+
+	/// The accounts whose off-chain workers may submit prices.
+	#[pallet::storage]
+	#[pallet::getter(fn authorities)]
+	pub type Authorities<T: Config> =
+		StorageValue<_, BoundedVec<T::AccountId, T::MaxAuthorities>, ValueQuery>;
+
+	/// The prices submitted for each feed in the current round.
+	#[pallet::storage]
+	pub type Submissions<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		FeedId,
+		Twox64Concat,
+		RoundId,
+		BoundedVec<(T::AccountId, FixedU128), T::MaxAuthorities>,
+		ValueQuery,
+	>;
+
+	/// The latest aggregated price of each feed.
+	#[pallet::storage]
+	#[pallet::getter(fn prices)]
+	pub type Prices<T: Config> = StorageMap<_, Twox64Concat, FeedId, PriceRecord<T::BlockNumber>>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		pub authorities: Vec<T::AccountId>,
+		/// Symbol, URL, JSON path and decimals of each feed.
+		pub feeds: Vec<(Vec<u8>, Vec<u8>, Vec<u8>, u8)>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self { authorities: Vec::new(), feeds: Vec::new() }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			let authorities =
+				BoundedVec::<_, T::MaxAuthorities>::try_from(self.authorities.clone())
+					.expect("too many genesis oracle authorities");
+			Authorities::<T>::put(authorities);
+
+			for (symbol, url, path, decimals) in &self.feeds {
+				let feed = Feed {
+					symbol: symbol.clone().try_into().expect("genesis feed symbol too long"),
+					url: url.clone().try_into().expect("genesis feed url too long"),
+					path: path.clone().try_into().expect("genesis feed path too long"),
+					decimals: *decimals,
+				};
+				Pallet::<T>::do_add_feed(feed).expect("invalid genesis feed");
+			}
+		}
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		FeedAdded {
+			feed_id: FeedId,
+			symbol: SymbolOf<T>,
+		},
+		FeedRemoved {
+			feed_id: FeedId,
+		},
+		AuthorityAdded {
+			who: T::AccountId,
+		},
+		AuthorityRemoved {
+			who: T::AccountId,
+		},
+		PriceSubmitted {
+			who: T::AccountId,
+			feed_id: FeedId,
+			round: RoundId,
+			price: FixedU128,
+		},
+		/// A round ended with a new price for the feed.
+		PriceAggregated {
+			feed_id: FeedId,
+			round: RoundId,
+			price: FixedU128,
+		},
+		/// A round ended with too few submissions close enough to their median, so the price
+		/// of the feed was left as it was.
+		RoundFailed {
+			feed_id: FeedId,
+			round: RoundId,
+			submissions: u32,
+		},
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		UnknownFeed,
+		/// Another feed has the same symbol.
+		SymbolTaken,
+		/// There are already `MaxFeeds` feeds.
+		TooManyFeeds,
+		/// There are already `MaxAuthorities` authorities.
+		TooManyAuthorities,
+		AlreadyAuthority,
+		NotAuthority,
+		/// The submission is not for the current round.
+		WrongRound,
+		/// The authority already submitted a price for this feed and round.
+		AlreadySubmitted,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		/// Aggregate the round that ended with the previous block.
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			let round_length = T::RoundLength::get();
+			if now.is_zero() || round_length.is_zero() || !(now % round_length).is_zero() {
+				return Weight::zero()
+			}
+
+			let round = Self::round_of(now).saturating_sub(1);
+			let mut feeds = 0u64;
+			for feed_id in Feeds::<T>::iter_keys() {
+				feeds += 1;
+				Self::aggregate_round(feed_id, round);
+			}
+			T::DbWeight::get().reads_writes(1 + 2 * feeds, 2 * feeds)
+		}
+
+		fn offchain_worker(block_number: T::BlockNumber) {
+			// The submissions land in the next block at the earliest.
+			let round = Self::round_of(block_number.saturating_add(One::one()));
+
+			for (feed_id, feed) in Feeds::<T>::iter() {
+				let key = Self::last_round_key(feed_id);
+				let last_round = StorageValueRef::persistent(&key);
+				if let Ok(Some(last)) = last_round.get::<RoundId>() {
+					if last >= round {
+						continue
+					}
+				}
+
+				match Self::fetch_price(&feed) {
+					Ok(price) => {
+						log::info!("OCW ==> {:?} price: {:?}", feed.symbol, price);
+						if Self::send_price(feed_id, round, price) {
+							last_round.set(&round);
+						}
+					},
+					Err(e) => {
+						log::info!("OCW ==> Error while fetching {:?} price! {:?}", feed.symbol, e);
+					},
+				}
+			}
+		}
+	}
+
+	// Dispatchable functions allows users to interact with the pallet and invoke state changes.
+	// These functions materialize as "extrinsics", which are often compared to transactions.
+	// Dispatchable functions must be annotated with a weight and must return a DispatchResult.
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		#[pallet::call_index(0)]
+		#[pallet::weight(10_000)]
+		pub fn add_feed(
+			origin: OriginFor<T>,
+			symbol: SymbolOf<T>,
+			url: UrlOf<T>,
+			path: PathOf<T>,
+			decimals: u8,
+		) -> DispatchResult {
+			T::ManagerOrigin::ensure_origin(origin)?;
+
+			Self::do_add_feed(Feed { symbol, url, path, decimals })?;
+
+			Ok(())
+		}
+
+		/// Remove a feed along with its price and any submissions for the current round.
+		#[pallet::call_index(1)]
+		#[pallet::weight(10_000)]
+		pub fn remove_feed(origin: OriginFor<T>, feed_id: FeedId) -> DispatchResult {
+			T::ManagerOrigin::ensure_origin(origin)?;
+
+			let feed = Feeds::<T>::take(feed_id).ok_or(Error::<T>::UnknownFeed)?;
+			FeedBySymbol::<T>::remove(&feed.symbol);
+			Prices::<T>::remove(feed_id);
+			let _ = Submissions::<T>::clear_prefix(feed_id, u32::MAX, None);
+
+			Self::deposit_event(Event::FeedRemoved { feed_id });
+
+			Ok(())
+		}
+
+		#[pallet::call_index(2)]
+		#[pallet::weight(10_000)]
+		pub fn add_authority(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+			T::ManagerOrigin::ensure_origin(origin)?;
+
+			Authorities::<T>::try_mutate(|authorities| -> DispatchResult {
+				ensure!(!authorities.contains(&who), Error::<T>::AlreadyAuthority);
+				authorities.try_push(who.clone()).map_err(|_| Error::<T>::TooManyAuthorities)?;
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::AuthorityAdded { who });
+
+			Ok(())
+		}
+
+		/// Stop accepting prices from `who`. Its submissions for the current round still count.
+		#[pallet::call_index(3)]
+		#[pallet::weight(10_000)]
+		pub fn remove_authority(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+			T::ManagerOrigin::ensure_origin(origin)?;
+
+			Authorities::<T>::try_mutate(|authorities| -> DispatchResult {
+				let index = authorities
+					.iter()
+					.position(|authority| *authority == who)
+					.ok_or(Error::<T>::NotAuthority)?;
+				authorities.remove(index);
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::AuthorityRemoved { who });
+
+			Ok(())
+		}
+
+		/// Record the price an oracle authority saw for a feed in the current round. The signature
+		/// is checked in `validate_unsigned`.
+		#[pallet::call_index(4)]
+		#[pallet::weight(10_000)]
+		pub fn submit_price(
+			origin: OriginFor<T>,
+			payload: PricePayload<T::Public>,
+			_signature: T::Signature,
+		) -> DispatchResult {
+			ensure_none(origin)?;
+
+			let PricePayload { feed_id, round, price, public } = payload;
+			let who = public.into_account();
+			ensure!(Self::is_authority(&who), Error::<T>::NotAuthority);
+			ensure!(Feeds::<T>::contains_key(feed_id), Error::<T>::UnknownFeed);
+			ensure!(round == Self::current_round(), Error::<T>::WrongRound);
+
+			Submissions::<T>::try_mutate(feed_id, round, |submissions| -> DispatchResult {
+				ensure!(
+					!submissions.iter().any(|(authority, _)| *authority == who),
+					Error::<T>::AlreadySubmitted
+				);
+				// There is room for every authority, but one may have been removed and re-added.
+				submissions
+					.try_push((who.clone(), price))
+					.map_err(|_| Error::<T>::TooManyAuthorities)?;
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::PriceSubmitted { who, feed_id, round, price });
+
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		pub fn round_of(block_number: T::BlockNumber) -> RoundId {
+			let round_length = T::RoundLength::get().max(One::one());
+			(block_number / round_length).saturated_into()
+		}
+
+		pub fn current_round() -> RoundId {
+			Self::round_of(frame_system::Pallet::<T>::block_number())
+		}
+
+		pub fn is_authority(who: &T::AccountId) -> bool {
+			Self::authorities().contains(who)
+		}
+
+		/// The latest aggregated price of the feed for `symbol`.
+		pub fn price_of(symbol: &[u8]) -> Option<PriceRecord<T::BlockNumber>> {
+			let symbol = SymbolOf::<T>::try_from(symbol.to_vec()).ok()?;
+			Self::feed_by_symbol(symbol).and_then(Self::prices)
+		}
+
+		fn do_add_feed(feed: Feed<T>) -> Result<FeedId, DispatchError> {
+			ensure!(!FeedBySymbol::<T>::contains_key(&feed.symbol), Error::<T>::SymbolTaken);
+			ensure!(Feeds::<T>::count() < T::MaxFeeds::get(), Error::<T>::TooManyFeeds);
+
+			let feed_id = Self::next_feed_id();
+			NextFeedId::<T>::put(feed_id.checked_add(1).ok_or(Error::<T>::TooManyFeeds)?);
+			FeedBySymbol::<T>::insert(&feed.symbol, feed_id);
+			let symbol = feed.symbol.clone();
+			Feeds::<T>::insert(feed_id, feed);
+
+			Self::deposit_event(Event::FeedAdded { feed_id, symbol });
+
+			Ok(feed_id)
+		}
+
+		fn aggregate_round(feed_id: FeedId, round: RoundId) {
+			let submissions = Submissions::<T>::take(feed_id, round);
+			if submissions.is_empty() {
+				return
+			}
+
+			let count = submissions.len() as u32;
+			let prices = submissions.into_iter().map(|(_, price)| price).collect();
+			match aggregate::median_without_outliers(
+				prices,
+				T::MaxDeviation::get(),
+				T::MinSubmissions::get(),
+			) {
+				Some(price) => {
+					let updated_at = frame_system::Pallet::<T>::block_number();
+					Prices::<T>::insert(feed_id, PriceRecord { price, round, updated_at });
+					Self::deposit_event(Event::PriceAggregated { feed_id, round, price });
+				},
+				None => {
+					Self::deposit_event(Event::RoundFailed { feed_id, round, submissions: count });
+				},
+			}
+		}
+
+		fn last_round_key(feed_id: FeedId) -> Vec<u8> {
+			feed_id.using_encoded(|encoded_id| {
+				LAST_ROUND_KEY.iter().chain(encoded_id).copied().collect::<Vec<u8>>()
+			})
+		}
+
+		fn fetch_price(feed: &Feed<T>) -> Result<FixedU128, http::Error> {
+			let url = sp_std::str::from_utf8(&feed.url).map_err(|_| http::Error::Unknown)?;
+
+			// prepare for send request
+			let deadline = sp_io::offchain::timestamp().add(Duration::from_millis(8_000));
+			let request = http::Request::get(url);
+			let pending = request.deadline(deadline).send().map_err(|_| http::Error::IoError)?;
+			let response =
+				pending.try_wait(deadline).map_err(|_| http::Error::DeadlineReached)??;
+			if response.code != 200 {
+				log::warn!("Unexpected status code: {}", response.code);
+				return Err(http::Error::Unknown)
+			}
+
+			let body = response.body().collect::<Vec<u8>>();
+			source::extract_price(&body, &feed.path, feed.decimals).ok_or_else(|| {
+				log::warn!("No price at {:?}", feed.path);
+				http::Error::Unknown
+			})
+		}
+
+		/// Submit `price` from every local oracle key. Returns whether any submission was sent.
+		fn send_price(feed_id: FeedId, round: RoundId, price: FixedU128) -> bool {
+			let signer = Signer::<T, T::AuthorityId>::all_accounts();
+			if !signer.can_sign() {
+				log::error!("OCW ==> No local account available");
+				return false
+			}
+
+			let results = signer.send_unsigned_transaction(
+				|acct| PricePayload { feed_id, round, price, public: acct.public.clone() },
+				|payload, signature| Call::submit_price { payload, signature },
+			);
+			let mut sent = false;
+			for (acct, res) in results {
+				match res {
+					Ok(()) => {
+						log::info!("OCW ==> price for feed {} sent by {:?}", feed_id, acct.id);
+						sent = true;
+					},
+					Err(()) => {
+						log::error!("OCW ==> sending price for feed {} failed", feed_id);
+					},
+				}
+			}
+			sent
+		}
+	}
+
+	#[pallet::validate_unsigned]
+	impl<T: Config> ValidateUnsigned for Pallet<T> {
+		type Call = Call<T>;
+
+		/// Accept a price submission if it is signed by an oracle authority, for a known feed and
+		/// the current round, and the authority has not yet submitted for that feed and round.
+		fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+			let (payload, signature) = match call {
+				Call::submit_price { ref payload, ref signature } => (payload, signature),
+				_ => return InvalidTransaction::Call.into(),
+			};
+
+			if !SignedPayload::<T>::verify::<T::AuthorityId>(payload, signature.clone()) {
+				return InvalidTransaction::BadProof.into()
+			}
+			let who = payload.public.clone().into_account();
+			if !Self::is_authority(&who) {
+				return InvalidTransaction::BadSigner.into()
+			}
+			if !Feeds::<T>::contains_key(payload.feed_id) {
+				return InvalidTransaction::Call.into()
+			}
+			let round = Self::current_round();
+			if payload.round < round {
+				return InvalidTransaction::Stale.into()
+			}
+			if payload.round > round {
+				return InvalidTransaction::Future.into()
+			}
+			if Submissions::<T>::get(payload.feed_id, round).iter().any(|(a, _)| *a == who) {
+				return InvalidTransaction::Stale.into()
+			}
+
+			ValidTransaction::with_tag_prefix("oracle")
+				.priority(T::UnsignedPriority::get())
+				.and_provides((payload.feed_id, payload.round, who))
+				.longevity(T::RoundLength::get().saturated_into::<u64>().max(1))
+				.propagate(true)
+				.build()
+		}
+	}
+}
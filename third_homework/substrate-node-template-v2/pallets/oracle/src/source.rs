@@ -0,0 +1,65 @@
+//! Reading prices out of the JSON responses of price feeds.
+
+use serde_json::Value;
+use sp_runtime::{
+	traits::{CheckedAdd, CheckedDiv},
+	FixedPointNumber, FixedU128,
+};
+
+/// Read the price at `path` in the JSON document `body`.
+///
+/// `path` is a list of object keys and array indices separated by dots, such as `data.0.price`;
+/// an empty path is the whole document. The value must be a decimal string, such as
+/// `"26891.41503711"`, or an unsigned integer. It is divided by `10^decimals`.
+pub fn extract_price(body: &[u8], path: &[u8], decimals: u8) -> Option<FixedU128> {
+	let document: Value = serde_json::from_slice(body).ok()?;
+	let mut value = &document;
+	if !path.is_empty() {
+		for key in path.split(|b| *b == b'.') {
+			let key = sp_std::str::from_utf8(key).ok()?;
+			value = match value {
+				Value::Array(items) => items.get(key.parse::<usize>().ok()?)?,
+				_ => value.get(key)?,
+			};
+		}
+	}
+
+	let price = match value {
+		Value::String(price) => parse_decimal(price.as_bytes())?,
+		Value::Number(price) => FixedU128::checked_from_integer(price.as_u64()?)?,
+		_ => return None,
+	};
+	let scale = FixedU128::checked_from_integer(10u128.checked_pow(decimals.into())?)?;
+	price.checked_div(&scale)
+}
+
+/// Parse a decimal string such as `b"26891.41503711"` into a fixed-point price.
+///
+/// Digits past the precision of `FixedU128` are dropped. Returns `None` for anything that is not
+/// an unsigned decimal number, or that does not fit.
+pub fn parse_decimal(raw: &[u8]) -> Option<FixedU128> {
+	let (int_part, frac_part) = match raw.iter().position(|b| *b == b'.') {
+		Some(dot) => (&raw[..dot], &raw[dot + 1..]),
+		None => (raw, &[][..]),
+	};
+	if int_part.is_empty() || !int_part.iter().chain(frac_part).all(u8::is_ascii_digit) {
+		return None
+	}
+
+	let mut int = 0u128;
+	for digit in int_part {
+		int = int.checked_mul(10)?.checked_add((digit - b'0') as u128)?;
+	}
+
+	let mut frac = 0u128;
+	let mut scale = FixedU128::DIV;
+	for digit in frac_part {
+		scale /= 10;
+		if scale == 0 {
+			break
+		}
+		frac += (digit - b'0') as u128 * scale;
+	}
+
+	FixedU128::checked_from_integer(int)?.checked_add(&FixedU128::from_inner(frac))
+}
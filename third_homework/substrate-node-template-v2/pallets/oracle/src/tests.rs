@@ -0,0 +1,314 @@
+use crate::{aggregate, mock::*, source, Error, Event, PricePayload, Submissions, SymbolOf};
+use codec::Decode;
+use frame_support::{assert_noop, assert_ok, traits::Hooks, BoundedVec};
+use sp_core::{
+	offchain::{testing, OffchainDbExt, OffchainWorkerExt, TransactionPoolExt},
+	sr25519, Get,
+};
+use sp_keystore::{testing::MemoryKeystore, Keystore, KeystoreExt};
+use sp_runtime::{
+	traits::ValidateUnsigned,
+	transaction_validity::{InvalidTransaction, TransactionSource},
+	DispatchError, DispatchResult, FixedU128, Permill, RuntimeAppPublic,
+};
+
+fn bounded<S: Get<u32>>(bytes: &[u8]) -> BoundedVec<u8, S> {
+	bytes.to_vec().try_into().unwrap()
+}
+
+fn symbol(bytes: &[u8]) -> SymbolOf<Test> {
+	bounded(bytes)
+}
+
+fn account(seed: u8) -> AccountId {
+	sr25519::Public::from_raw([seed; 32])
+}
+
+fn add_feed(symbol: &[u8], url: &[u8], path: &[u8], decimals: u8) -> DispatchResult {
+	Oracle::add_feed(RuntimeOrigin::root(), bounded(symbol), bounded(url), bounded(path), decimals)
+}
+
+fn submit(who: AccountId, feed_id: u32, round: u32, price: FixedU128) -> DispatchResult {
+	Oracle::submit_price(
+		RuntimeOrigin::none(),
+		PricePayload { feed_id, round, price, public: who },
+		sr25519::Signature::from_raw([0; 64]),
+	)
+}
+
+fn run_to_block(n: u64) {
+	while System::block_number() < n {
+		System::set_block_number(System::block_number() + 1);
+		Oracle::on_initialize(System::block_number());
+	}
+}
+
+#[test]
+fn median_drops_outliers() {
+	let prices = |values: &[u32]| values.iter().map(|v| FixedU128::from_u32(*v)).collect();
+
+	assert_eq!(
+		aggregate::median_without_outliers(prices(&[4, 1, 3, 2]), Permill::one(), 1),
+		Some(FixedU128::from_rational(5, 2))
+	);
+	assert_eq!(
+		aggregate::median_without_outliers(prices(&[100, 150, 102]), Permill::from_percent(5), 2),
+		Some(FixedU128::from_u32(101))
+	);
+	assert_eq!(
+		aggregate::median_without_outliers(prices(&[100, 150, 102]), Permill::from_percent(5), 3),
+		None
+	);
+	assert_eq!(aggregate::median_without_outliers(prices(&[]), Permill::one(), 0), None);
+}
+
+#[test]
+fn prices_are_parsed_as_fixed_point() {
+	assert_eq!(
+		source::parse_decimal(b"26891.41503711"),
+		Some(FixedU128::from_inner(26_891_415_037_110_000_000_000))
+	);
+	assert_eq!(source::parse_decimal(b"2.5"), Some(FixedU128::from_rational(5, 2)));
+	assert_eq!(source::parse_decimal(b"7"), Some(FixedU128::from_u32(7)));
+	assert_eq!(source::parse_decimal(b"0.0000000000000000019"), Some(FixedU128::from_inner(1)));
+	assert_eq!(source::parse_decimal(b""), None);
+	assert_eq!(source::parse_decimal(b".5"), None);
+	assert_eq!(source::parse_decimal(b"-1"), None);
+	assert_eq!(source::parse_decimal(b"1.2.3"), None);
+	assert_eq!(source::parse_decimal(b"1e3"), None);
+}
+
+#[test]
+fn prices_are_read_at_json_path() {
+	assert_eq!(
+		source::extract_price(br#"{"mins":5,"price":"26891.41503711"}"#, b"price", 0),
+		Some(FixedU128::from_inner(26_891_415_037_110_000_000_000))
+	);
+	assert_eq!(
+		source::extract_price(br#"{"data":[{"p":"1.5"}]}"#, b"data.0.p", 0),
+		Some(FixedU128::from_rational(3, 2))
+	);
+	assert_eq!(
+		source::extract_price(br#"{"price":2689141}"#, b"price", 2),
+		Some(FixedU128::from_rational(2_689_141, 100))
+	);
+	assert_eq!(source::extract_price(br#""7""#, b"", 0), Some(FixedU128::from_u32(7)));
+
+	assert_eq!(source::extract_price(br#"{"price":"1"}"#, b"data.price", 0), None);
+	assert_eq!(source::extract_price(br#"{"price":1.5}"#, b"price", 0), None);
+	assert_eq!(source::extract_price(br#"{"price":"-1"}"#, b"price", 0), None);
+	assert_eq!(source::extract_price(b"not json", b"", 0), None);
+}
+
+#[test]
+fn feeds_are_managed_by_root() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Oracle::add_feed(
+				RuntimeOrigin::signed(account(1)),
+				bounded(b"BTCUSDT"),
+				bounded(b"http://localhost/btc"),
+				bounded(b"price"),
+				0
+			),
+			DispatchError::BadOrigin
+		);
+
+		assert_ok!(add_feed(b"BTCUSDT", b"http://localhost/btc", b"price", 0));
+		System::assert_last_event(
+			Event::FeedAdded { feed_id: 0, symbol: symbol(b"BTCUSDT") }.into(),
+		);
+		assert_eq!(Oracle::feed_by_symbol(symbol(b"BTCUSDT")), Some(0));
+		assert_noop!(
+			add_feed(b"BTCUSDT", b"http://localhost/other", b"price", 0),
+			Error::<Test>::SymbolTaken
+		);
+		assert_ok!(add_feed(b"BNBUSDT", b"http://localhost/bnb", b"price", 0));
+		assert_noop!(
+			add_feed(b"ETHUSDT", b"http://localhost/eth", b"price", 0),
+			Error::<Test>::TooManyFeeds
+		);
+
+		assert_ok!(Oracle::remove_feed(RuntimeOrigin::root(), 0));
+		System::assert_last_event(Event::FeedRemoved { feed_id: 0 }.into());
+		assert_eq!(Oracle::feeds(0), None);
+		assert_noop!(Oracle::remove_feed(RuntimeOrigin::root(), 0), Error::<Test>::UnknownFeed);
+
+		assert_ok!(add_feed(b"BTCUSDT", b"http://localhost/btc", b"price", 0));
+		assert_eq!(Oracle::feed_by_symbol(symbol(b"BTCUSDT")), Some(2));
+	});
+}
+
+#[test]
+fn authorities_are_managed_by_root() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Oracle::add_authority(RuntimeOrigin::signed(account(1)), account(1)),
+			DispatchError::BadOrigin
+		);
+
+		for seed in 1..=3 {
+			assert_ok!(Oracle::add_authority(RuntimeOrigin::root(), account(seed)));
+		}
+		System::assert_last_event(Event::AuthorityAdded { who: account(3) }.into());
+		assert_noop!(
+			Oracle::add_authority(RuntimeOrigin::root(), account(1)),
+			Error::<Test>::AlreadyAuthority
+		);
+		assert_noop!(
+			Oracle::add_authority(RuntimeOrigin::root(), account(4)),
+			Error::<Test>::TooManyAuthorities
+		);
+
+		assert_ok!(Oracle::remove_authority(RuntimeOrigin::root(), account(2)));
+		System::assert_last_event(Event::AuthorityRemoved { who: account(2) }.into());
+		assert_eq!(Oracle::authorities().to_vec(), vec![account(1), account(3)]);
+		assert_noop!(
+			Oracle::remove_authority(RuntimeOrigin::root(), account(2)),
+			Error::<Test>::NotAuthority
+		);
+	});
+}
+
+#[test]
+fn round_is_aggregated_by_median() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(add_feed(b"BTCUSDT", b"http://localhost/btc", b"price", 0));
+		for seed in 1..=3 {
+			assert_ok!(Oracle::add_authority(RuntimeOrigin::root(), account(seed)));
+		}
+
+		assert_ok!(submit(account(1), 0, 0, FixedU128::from_u32(100)));
+		System::assert_last_event(
+			Event::PriceSubmitted {
+				who: account(1),
+				feed_id: 0,
+				round: 0,
+				price: FixedU128::from_u32(100),
+			}
+			.into(),
+		);
+		assert_ok!(submit(account(2), 0, 0, FixedU128::from_u32(102)));
+		assert_ok!(submit(account(3), 0, 0, FixedU128::from_u32(150)));
+
+		assert_noop!(
+			submit(account(1), 0, 0, FixedU128::from_u32(100)),
+			Error::<Test>::AlreadySubmitted
+		);
+		assert_noop!(
+			submit(account(9), 0, 0, FixedU128::from_u32(100)),
+			Error::<Test>::NotAuthority
+		);
+		assert_noop!(submit(account(1), 0, 1, FixedU128::from_u32(100)), Error::<Test>::WrongRound);
+		assert_noop!(
+			submit(account(1), 1, 0, FixedU128::from_u32(100)),
+			Error::<Test>::UnknownFeed
+		);
+
+		run_to_block(9);
+		assert_eq!(Oracle::prices(0), None);
+
+		// 150 is more than 5% away from the median of 102.
+		run_to_block(10);
+		System::assert_last_event(
+			Event::PriceAggregated { feed_id: 0, round: 0, price: FixedU128::from_u32(101) }.into(),
+		);
+		let record = Oracle::prices(0).unwrap();
+		assert_eq!(
+			(record.price, record.round, record.updated_at),
+			(FixedU128::from_u32(101), 0, 10)
+		);
+		assert!(Submissions::<Test>::get(0, 0).is_empty());
+		assert_eq!(Oracle::price_of(b"BTCUSDT"), Some(record));
+	});
+}
+
+#[test]
+fn round_without_enough_submissions_keeps_the_old_price() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(add_feed(b"BTCUSDT", b"http://localhost/btc", b"price", 0));
+		assert_ok!(Oracle::add_authority(RuntimeOrigin::root(), account(1)));
+
+		assert_ok!(submit(account(1), 0, 0, FixedU128::from_u32(100)));
+		run_to_block(10);
+
+		System::assert_last_event(
+			Event::RoundFailed { feed_id: 0, round: 0, submissions: 1 }.into(),
+		);
+		assert_eq!(Oracle::prices(0), None);
+	});
+}
+
+#[test]
+fn offchain_worker_submits_fetched_price() {
+	const URL: &str = "http://localhost:8000/btc";
+
+	let (offchain, state) = testing::TestOffchainExt::new();
+	let (pool, pool_state) = testing::TestTransactionPoolExt::new();
+	let keystore = MemoryKeystore::new();
+	let public = keystore
+		.sr25519_generate_new(crate::crypto::Public::ID, Some("//Alice"))
+		.unwrap();
+
+	let mut ext = new_test_ext();
+	ext.register_extension(OffchainDbExt::new(offchain.clone()));
+	ext.register_extension(OffchainWorkerExt::new(offchain));
+	ext.register_extension(TransactionPoolExt::new(pool));
+	ext.register_extension(KeystoreExt::new(keystore));
+
+	state.write().expect_request(testing::PendingRequest {
+		method: "GET".into(),
+		uri: URL.into(),
+		response: Some(br#"{"data":{"price":"26891.41"}}"#.to_vec()),
+		sent: true,
+		..Default::default()
+	});
+
+	ext.execute_with(|| {
+		assert_ok!(add_feed(b"BTCUSDT", URL.as_bytes(), b"data.price", 0));
+		assert_ok!(Oracle::add_authority(RuntimeOrigin::root(), public));
+
+		Oracle::offchain_worker(1);
+
+		let tx = pool_state.write().transactions.pop().unwrap();
+		assert!(pool_state.read().transactions.is_empty());
+		let tx = Extrinsic::decode(&mut &*tx).unwrap();
+		assert_eq!(tx.signature, None);
+		let call = match tx.call {
+			RuntimeCall::Oracle(call) => call,
+			call => panic!("unexpected call {:?}", call),
+		};
+		let crate::Call::submit_price { payload, signature } = call.clone() else {
+			panic!("unexpected call {:?}", call)
+		};
+		assert_eq!(
+			payload,
+			PricePayload {
+				feed_id: 0,
+				round: 0,
+				price: FixedU128::from_rational(2_689_141, 100),
+				public
+			}
+		);
+		assert_ok!(Oracle::validate_unsigned(TransactionSource::External, &call));
+
+		let forged = crate::Call::submit_price {
+			payload: PricePayload { price: FixedU128::from_u32(1), ..payload },
+			signature,
+		};
+		assert_eq!(
+			Oracle::validate_unsigned(TransactionSource::External, &forged),
+			Err(InvalidTransaction::BadProof.into())
+		);
+
+		// Each node submits once per feed and round.
+		Oracle::offchain_worker(2);
+		assert!(pool_state.read().transactions.is_empty());
+
+		assert_ok!(Oracle::remove_authority(RuntimeOrigin::root(), public));
+		assert_eq!(
+			Oracle::validate_unsigned(TransactionSource::External, &call),
+			Err(InvalidTransaction::BadSigner.into())
+		);
+	});
+}
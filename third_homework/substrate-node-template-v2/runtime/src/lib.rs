@@ -18,7 +18,7 @@ use sp_runtime::{
 	traits::{
 		AccountIdLookup, BlakeTwo256, Block as BlockT, IdentifyAccount, NumberFor, One, Verify,
 	},
-	transaction_validity::{TransactionSource, TransactionValidity},
+	transaction_validity::{TransactionPriority, TransactionSource, TransactionValidity},
 	ApplyExtrinsicResult, MultiSignature, SaturatedConversion,
 };
 use sp_std::prelude::*;
@@ -30,7 +30,8 @@ use sp_version::RuntimeVersion;
 pub use frame_support::{
 	construct_runtime, parameter_types,
 	traits::{
-		ConstU128, ConstU32, ConstU64, ConstU8, KeyOwnerProofSystem, Randomness, StorageInfo,
+		ConstBool, ConstU128, ConstU32, ConstU64, ConstU8, KeyOwnerProofSystem, Randomness,
+		StorageInfo,
 	},
 	weights::{
 		constants::{
@@ -46,7 +47,7 @@ pub use pallet_timestamp::Call as TimestampCall;
 use pallet_transaction_payment::{ConstFeeMultiplier, CurrencyAdapter, Multiplier};
 #[cfg(any(feature = "std", test))]
 pub use sp_runtime::BuildStorage;
-pub use sp_runtime::{Perbill, Permill};
+pub use sp_runtime::{FixedU128, Perbill, Permill};
 
 /// Import the template pallet.
 pub use pallet_template;
@@ -54,6 +55,8 @@ pub use pallet_template;
 /// Import the template pallet.
 pub use pallet_kitties;
 
+pub use pallet_oracle;
+
 /// An index to a block.
 pub type BlockNumber = u32;
 
@@ -109,10 +112,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	//   `spec_version`, and `authoring_version` are the same between Wasm and native.
 	// This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
 	//   the compatible custom types.
-	spec_version: 302,
+	spec_version: 324,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 2,
 	state_version: 1,
 };
 
@@ -282,7 +285,30 @@ impl pallet_template::Config for Runtime {
 
 parameter_types! {
 	pub  KittyPrice: Balance = EXISTENTIAL_DEPOSIT * 100;
+	/// What `KittyPrice` was before storage version 5 of the kitties pallet.
+	pub const LegacyKittyPrice: Balance = EXISTENTIAL_DEPOSIT * 100;
 	pub const KittyPalletId: PalletId = PalletId(*b"py/kitty");
+	pub const KittyPriceMode: pallet_kitties::PriceMode = pallet_kitties::PriceMode::Deposit;
+	pub const KittyPriceUsd: FixedU128 = FixedU128::from_u32(10);
+	/// The development chain's token has no market, so one token is valued at 1/10_000 BTC.
+	pub const KittyNativeTokensPerBtc: FixedU128 = FixedU128::from_u32(10_000);
+	/// Units in one whole token, with 12 decimals.
+	pub const KittyNativeUnit: Balance = 1_000_000_000_000;
+	/// Oracle reports outside of 1_000 to 10_000_000 USDT per BTC are rejected.
+	pub const KittyMinBtcPrice: FixedU128 = FixedU128::from_u32(1_000);
+	pub const KittyMaxBtcPrice: FixedU128 = FixedU128::from_u32(10_000_000);
+	/// Price reports are paid for by, and attributed to, the oracle authority sending them.
+	pub const KittySubmissionMode: pallet_kitties::SubmissionMode =
+		pallet_kitties::SubmissionMode::Signed;
+}
+
+/// The oracle's aggregated `BTCUSDT` feed, which kitty prices follow.
+pub struct OracleBtcPrice;
+
+impl pallet_kitties::BtcPriceFeed<BlockNumber> for OracleBtcPrice {
+	fn btc_price() -> Option<(FixedU128, BlockNumber)> {
+		Oracle::price_of(b"BTCUSDT").map(|record| (record.price, record.updated_at))
+	}
 }
 
 impl pallet_kitties::Config for Runtime {
@@ -290,10 +316,49 @@ impl pallet_kitties::Config for Runtime {
 	type Randomness = RandomnessModule;
 	type Currency = Balances;
 	type KittyPrice = KittyPrice;
+	type PriceMode = KittyPriceMode;
+	type LegacyKittyPrice = LegacyKittyPrice;
+	type KittyPriceUsd = KittyPriceUsd;
+	type NativeTokensPerBtc = KittyNativeTokensPerBtc;
+	type NativeUnit = KittyNativeUnit;
+	type BtcPriceFeed = OracleBtcPrice;
+	type PriceStaleAfter = ConstU32<{ 10 * MINUTES }>;
+	type MinBtcPrice = KittyMinBtcPrice;
+	type MaxBtcPrice = KittyMaxBtcPrice;
+	type MaxKittiesOwned = ConstU32<100>;
+	type MaxNameLength = ConstU32<32>;
+	type UniqueNames = ConstBool<true>;
+	type BreedingCooldownPeriod = ConstU32<{ 10 * MINUTES }>;
+	type MaxAuctionDuration = ConstU32<{ 7 * DAYS }>;
+	type AuctionExtension = ConstU32<{ 5 * MINUTES }>;
+	type MaxAuctionsPerBlock = ConstU32<50>;
+	type MaxOracleAuthorities = ConstU32<16>;
+	type OracleSubmissionInterval = ConstU32<{ MINUTES }>;
+	type SubmissionMode = KittySubmissionMode;
 	type PalletId = KittyPalletId;
 	type AuthorityId = pallet_kitties::crypto::TestAuthId;
 }
 
+parameter_types! {
+	pub const OracleMaxDeviation: Permill = Permill::from_percent(5);
+	pub const OracleUnsignedPriority: TransactionPriority = TransactionPriority::max_value() / 2;
+}
+
+impl pallet_oracle::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type AuthorityId = pallet_oracle::crypto::OracleAuthId;
+	type ManagerOrigin = frame_system::EnsureRoot<AccountId>;
+	type RoundLength = ConstU32<{ MINUTES }>;
+	type MaxFeeds = ConstU32<16>;
+	type MaxAuthorities = ConstU32<16>;
+	type MaxSymbolLength = ConstU32<16>;
+	type MaxUrlLength = ConstU32<256>;
+	type MaxPathLength = ConstU32<64>;
+	type MaxDeviation = OracleMaxDeviation;
+	type MinSubmissions = ConstU32<1>;
+	type UnsignedPriority = OracleUnsignedPriority;
+}
+
 /// Configure the offchain runtime part
 impl<LocalCall> frame_system::offchain::CreateSignedTransaction<LocalCall> for Runtime
 where
@@ -370,6 +435,7 @@ construct_runtime!(
 		TemplateModule: pallet_template,
 		RandomnessModule: pallet_insecure_randomness_collective_flip,
 		KittiesModule: pallet_kitties,
+		Oracle: pallet_oracle,
 		Utility: pallet_utility,
 		PoeModule: pallet_poe,
 	}
@@ -590,6 +656,44 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_kitties_runtime_api::KittiesApi<Block, AccountId> for Runtime {
+		fn kitties_of(owner: AccountId, start: u32, limit: u32) -> Vec<pallet_kitties::KittyId> {
+			KittiesModule::kitties_of(owner, start, limit)
+		}
+
+		fn ancestors(
+			kitty_id: pallet_kitties::KittyId,
+			depth: u32,
+		) -> Vec<(pallet_kitties::KittyId, (pallet_kitties::KittyId, pallet_kitties::KittyId))> {
+			KittiesModule::ancestors(kitty_id, depth)
+		}
+
+		fn descendants(
+			kitty_id: pallet_kitties::KittyId,
+			depth: u32,
+		) -> Vec<(pallet_kitties::KittyId, (pallet_kitties::KittyId, pallet_kitties::KittyId))> {
+			KittiesModule::descendants(kitty_id, depth)
+		}
+	}
+
+	impl pallet_oracle_runtime_api::PriceApi<Block, BlockNumber> for Runtime {
+		fn price(
+			feed_id: pallet_oracle::FeedId,
+		) -> Option<pallet_oracle::PriceRecord<BlockNumber>> {
+			Oracle::prices(feed_id)
+		}
+
+		fn price_of(symbol: Vec<u8>) -> Option<pallet_oracle::PriceRecord<BlockNumber>> {
+			Oracle::price_of(&symbol)
+		}
+
+		fn feeds() -> Vec<(pallet_oracle::FeedId, Vec<u8>)> {
+			pallet_oracle::Feeds::<Runtime>::iter()
+				.map(|(feed_id, feed)| (feed_id, feed.symbol.into_inner()))
+				.collect()
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn benchmark_metadata(extra: bool) -> (